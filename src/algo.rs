@@ -0,0 +1,475 @@
+/*!
+Graph algorithms that operate directly on the DOT model.
+
+All functions consider every node and edge in the graph, including those declared inside nested
+sub-graphs and clusters, and respect `Graph::is_directed`; for undirected graphs each edge may be
+traversed in either direction. Results are returned as `Identifier` collections so that they may
+be used to drive styling of the graph before it is written.
+
+# Example
+
+ */
+
+use crate::graph::{Adjacency, Graph};
+use crate::{Identifier, RootGraph};
+use std::collections::{HashMap, VecDeque};
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the nodes of one cycle in the graph, in traversal order, or `None` if the graph is
+/// acyclic. A self-loop is reported as a cycle of one node.
+///
+pub fn find_cycle(graph: &RootGraph) -> Option<Vec<Identifier>> {
+    let indexed = Indexed::new(graph);
+    let cycle = if indexed.directed {
+        find_directed_cycle(&indexed)
+    } else {
+        find_undirected_cycle(&indexed)
+    };
+    cycle.map(|cycle| indexed.identifiers(cycle))
+}
+
+pub fn is_acyclic(graph: &RootGraph) -> bool {
+    find_cycle(graph).is_none()
+}
+
+///
+/// Returns all nodes such that each node appears before any of its successors, or `None` if the
+/// graph contains a cycle. As every edge in an undirected graph may be traversed in either
+/// direction, only an undirected graph with no edges has a topological order.
+///
+pub fn topological_sort(graph: &RootGraph) -> Option<Vec<Identifier>> {
+    let indexed = Indexed::new(graph);
+    if !indexed.directed && indexed.adjacency.edge_count() > 0 {
+        return None;
+    }
+    let mut in_degree: Vec<usize> = indexed.predecessors.iter().map(Vec::len).collect();
+    let mut queue: VecDeque<usize> = (0..indexed.len()).filter(|i| in_degree[*i] == 0).collect();
+    let mut order: Vec<usize> = Vec::with_capacity(indexed.len());
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for successor in indexed.successors[node].iter() {
+            in_degree[*successor] -= 1;
+            if in_degree[*successor] == 0 {
+                queue.push_back(*successor);
+            }
+        }
+    }
+    if order.len() == indexed.len() {
+        Some(indexed.identifiers(order))
+    } else {
+        None
+    }
+}
+
+///
+/// Returns all nodes reachable from `from`, in breadth-first order, starting with `from`
+/// itself. If `from` is not in the graph the result is empty.
+///
+pub fn reachable(graph: &RootGraph, from: &Identifier) -> Vec<Identifier> {
+    let indexed = Indexed::new(graph);
+    match indexed.adjacency.index_of(from) {
+        Some(start) => {
+            let (order, _) = breadth_first(&indexed, &[start]);
+            indexed.identifiers(order)
+        }
+        None => Vec::default(),
+    }
+}
+
+///
+/// Returns all nodes that are not reachable from any of the `roots`, in declaration order.
+///
+pub fn unreachable(graph: &RootGraph, roots: &[Identifier]) -> Vec<Identifier> {
+    let indexed = Indexed::new(graph);
+    let starts: Vec<usize> = roots
+        .iter()
+        .filter_map(|id| indexed.adjacency.index_of(id))
+        .collect();
+    let (_, parents) = breadth_first(&indexed, &starts);
+    indexed.identifiers(
+        (0..indexed.len())
+            .filter(|i| parents[*i].is_none())
+            .collect(),
+    )
+}
+
+///
+/// Returns the nodes on a path with the fewest edges from `from` to `to`, including both ends,
+/// or `None` if `to` is not reachable from `from`.
+///
+pub fn shortest_path(
+    graph: &RootGraph,
+    from: &Identifier,
+    to: &Identifier,
+) -> Option<Vec<Identifier>> {
+    let indexed = Indexed::new(graph);
+    let start = indexed.adjacency.index_of(from)?;
+    let end = indexed.adjacency.index_of(to)?;
+    let (_, parents) = breadth_first(&indexed, &[start]);
+    parents[end]?;
+    let mut path = vec![end];
+    let mut current = end;
+    while current != start {
+        current = parents[current].unwrap();
+        path.push(current);
+    }
+    path.reverse();
+    Some(indexed.identifiers(path))
+}
+
+///
+/// Returns the strongly connected components of the graph; each component is a list of
+/// nodes, and components are returned in reverse topological order. For undirected graphs
+/// this is the same as `weakly_connected_components`.
+///
+pub fn strongly_connected_components(graph: &RootGraph) -> Vec<Vec<Identifier>> {
+    let indexed = Indexed::new(graph);
+    tarjan(&indexed)
+        .into_iter()
+        .map(|component| indexed.identifiers(component))
+        .collect()
+}
+
+///
+/// Returns the connected components of the graph, ignoring edge direction; components are
+/// returned in the order their first node was declared.
+///
+pub fn weakly_connected_components(graph: &RootGraph) -> Vec<Vec<Identifier>> {
+    let indexed = Indexed::new(graph);
+    let mut component_of: Vec<Option<usize>> = vec![None; indexed.len()];
+    let mut components: Vec<Vec<usize>> = Vec::default();
+    for start in 0..indexed.len() {
+        if component_of[start].is_some() {
+            continue;
+        }
+        let component_index = components.len();
+        let mut component = vec![start];
+        component_of[start] = Some(component_index);
+        let mut next = 0;
+        while next < component.len() {
+            let node = component[next];
+            next += 1;
+            for other in indexed.successors[node]
+                .iter()
+                .chain(indexed.predecessors[node].iter())
+            {
+                if component_of[*other].is_none() {
+                    component_of[*other] = Some(component_index);
+                    component.push(*other);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+        .into_iter()
+        .map(|component| indexed.identifiers(component))
+        .collect()
+}
+
+///
+/// Returns the immediate dominator of every node reachable from `root`, other than `root`
+/// itself. A node `d` dominates `n` if every path from `root` to `n` passes through `d`.
+///
+pub fn dominators(graph: &RootGraph, root: &Identifier) -> HashMap<Identifier, Identifier> {
+    let indexed = Indexed::new(graph);
+    let mut result: HashMap<Identifier, Identifier> = Default::default();
+    if let Some(start) = indexed.adjacency.index_of(root) {
+        let immediate = immediate_dominators(&indexed, start);
+        for (node, dominator) in immediate.into_iter().enumerate() {
+            if let Some(dominator) = dominator {
+                if node != start {
+                    let _ = result.insert(
+                        indexed.adjacency.node_at(node).clone(),
+                        indexed.adjacency.node_at(dominator).clone(),
+                    );
+                }
+            }
+        }
+    }
+    result
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) struct Indexed<'a> {
+    pub(crate) adjacency: Adjacency<'a>,
+    pub(crate) directed: bool,
+    pub(crate) successors: Vec<Vec<usize>>,
+    pub(crate) predecessors: Vec<Vec<usize>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Indexed<'a> {
+    pub(crate) fn new(graph: &'a RootGraph) -> Self {
+        let adjacency = graph.adjacency();
+        Self {
+            directed: adjacency.is_directed(),
+            successors: adjacency.successor_lists(),
+            predecessors: adjacency.predecessor_lists(),
+            adjacency,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.adjacency.node_count()
+    }
+
+    pub(crate) fn identifiers(&self, indices: Vec<usize>) -> Vec<Identifier> {
+        indices
+            .into_iter()
+            .map(|i| self.adjacency.node_at(i).clone())
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const UNVISITED: u8 = 0;
+const IN_PROGRESS: u8 = 1;
+const DONE: u8 = 2;
+
+fn find_directed_cycle(indexed: &Indexed<'_>) -> Option<Vec<usize>> {
+    let mut state: Vec<u8> = vec![UNVISITED; indexed.len()];
+    let mut parent: Vec<usize> = (0..indexed.len()).collect();
+    for start in 0..indexed.len() {
+        if state[start] != UNVISITED {
+            continue;
+        }
+        let mut stack: Vec<(usize, usize)> = vec![(start, 0)];
+        state[start] = IN_PROGRESS;
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            if let Some(successor) = indexed.successors[node].get(*next).copied() {
+                *next += 1;
+                if state[successor] == UNVISITED {
+                    state[successor] = IN_PROGRESS;
+                    parent[successor] = node;
+                    stack.push((successor, 0));
+                } else if state[successor] == IN_PROGRESS {
+                    let mut cycle = vec![node];
+                    let mut current = node;
+                    while current != successor {
+                        current = parent[current];
+                        cycle.push(current);
+                    }
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+            } else {
+                state[node] = DONE;
+                let _ = stack.pop();
+            }
+        }
+    }
+    None
+}
+
+fn find_undirected_cycle(indexed: &Indexed<'_>) -> Option<Vec<usize>> {
+    let mut visited: Vec<bool> = vec![false; indexed.len()];
+    let mut parent: Vec<Option<usize>> = vec![None; indexed.len()];
+    for start in 0..indexed.len() {
+        if visited[start] {
+            continue;
+        }
+        // each frame records whether the edge back to the parent has already been skipped,
+        // so that parallel edges between two nodes are still reported as a cycle.
+        let mut stack: Vec<(usize, usize, bool)> = vec![(start, 0, false)];
+        visited[start] = true;
+        while let Some((node, next, skipped_parent)) = stack.last_mut() {
+            let node = *node;
+            if let Some(other) = indexed.successors[node].get(*next).copied() {
+                *next += 1;
+                if Some(other) == parent[node] && !*skipped_parent {
+                    *skipped_parent = true;
+                } else if !visited[other] {
+                    visited[other] = true;
+                    parent[other] = Some(node);
+                    stack.push((other, 0, false));
+                } else {
+                    let mut cycle = vec![node];
+                    let mut current = node;
+                    while current != other {
+                        current = parent[current].unwrap();
+                        cycle.push(current);
+                    }
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+            } else {
+                let _ = stack.pop();
+            }
+        }
+    }
+    None
+}
+
+fn breadth_first(indexed: &Indexed<'_>, starts: &[usize]) -> (Vec<usize>, Vec<Option<usize>>) {
+    let mut parents: Vec<Option<usize>> = vec![None; indexed.len()];
+    let mut order: Vec<usize> = Vec::default();
+    let mut queue: VecDeque<usize> = VecDeque::default();
+    for start in starts {
+        if parents[*start].is_none() {
+            parents[*start] = Some(*start);
+            queue.push_back(*start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for successor in indexed.successors[node].iter() {
+            if parents[*successor].is_none() {
+                parents[*successor] = Some(node);
+                queue.push_back(*successor);
+            }
+        }
+    }
+    (order, parents)
+}
+
+pub(crate) fn tarjan(indexed: &Indexed<'_>) -> Vec<Vec<usize>> {
+    let mut next_index = 0;
+    let mut index: Vec<Option<usize>> = vec![None; indexed.len()];
+    let mut low_link: Vec<usize> = vec![0; indexed.len()];
+    let mut on_stack: Vec<bool> = vec![false; indexed.len()];
+    let mut stack: Vec<usize> = Vec::default();
+    let mut components: Vec<Vec<usize>> = Vec::default();
+
+    for start in 0..indexed.len() {
+        if index[start].is_some() {
+            continue;
+        }
+        let mut call_stack: Vec<(usize, usize)> = vec![(start, 0)];
+        while let Some((node, next)) = call_stack.last_mut() {
+            let node = *node;
+            if *next == 0 && index[node].is_none() {
+                index[node] = Some(next_index);
+                low_link[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+            if let Some(successor) = indexed.successors[node].get(*next).copied() {
+                *next += 1;
+                match index[successor] {
+                    None => call_stack.push((successor, 0)),
+                    Some(successor_index) if on_stack[successor] => {
+                        low_link[node] = low_link[node].min(successor_index);
+                    }
+                    _ => {}
+                }
+            } else {
+                let _ = call_stack.pop();
+                if let Some((parent, _)) = call_stack.last() {
+                    low_link[*parent] = low_link[*parent].min(low_link[node]);
+                }
+                if Some(low_link[node]) == index[node] {
+                    let mut component: Vec<usize> = Vec::default();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    component.reverse();
+                    components.push(component);
+                }
+            }
+        }
+    }
+    components
+}
+
+///
+/// Uses the iterative algorithm from Cooper, Harvey and Kennedy, "A Simple, Fast Dominance
+/// Algorithm".
+///
+fn immediate_dominators(indexed: &Indexed<'_>, start: usize) -> Vec<Option<usize>> {
+    // post-order numbering of the nodes reachable from start.
+    let mut post_order: Vec<usize> = Vec::default();
+    let mut visited: Vec<bool> = vec![false; indexed.len()];
+    let mut stack: Vec<(usize, usize)> = vec![(start, 0)];
+    visited[start] = true;
+    while let Some((node, next)) = stack.last_mut() {
+        let node = *node;
+        if let Some(successor) = indexed.successors[node].get(*next).copied() {
+            *next += 1;
+            if !visited[successor] {
+                visited[successor] = true;
+                stack.push((successor, 0));
+            }
+        } else {
+            post_order.push(node);
+            let _ = stack.pop();
+        }
+    }
+    let mut post_number: Vec<usize> = vec![0; indexed.len()];
+    for (number, node) in post_order.iter().enumerate() {
+        post_number[*node] = number;
+    }
+
+    let mut dominator: Vec<Option<usize>> = vec![None; indexed.len()];
+    dominator[start] = Some(start);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for node in post_order.iter().rev().filter(|n| **n != start) {
+            let mut new_dominator: Option<usize> = None;
+            for predecessor in indexed.predecessors[*node].iter() {
+                if dominator[*predecessor].is_some() {
+                    new_dominator = Some(match new_dominator {
+                        None => *predecessor,
+                        Some(current) => intersect(&dominator, &post_number, *predecessor, current),
+                    });
+                }
+            }
+            if new_dominator.is_some() && dominator[*node] != new_dominator {
+                dominator[*node] = new_dominator;
+                changed = true;
+            }
+        }
+    }
+    dominator
+}
+
+fn intersect(
+    dominator: &[Option<usize>],
+    post_number: &[usize],
+    left: usize,
+    right: usize,
+) -> usize {
+    let mut left = left;
+    let mut right = right;
+    while left != right {
+        while post_number[left] < post_number[right] {
+            left = dominator[left].unwrap();
+        }
+        while post_number[right] < post_number[left] {
+            right = dominator[right].unwrap();
+        }
+    }
+    left
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
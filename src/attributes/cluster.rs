@@ -8,9 +8,9 @@ More detailed description, with
  */
 
 use crate::attributes::{
-    Attribute, Attributes, ClusterStyles, Color, ColorOrList, Double, EscapedString, FontName, Int,
-    LabelJustification, LabelLocation, LabelString, LayerRange, Point, PointOrBoth, Positive,
-    PositiveNonZero, RankType, Style, Unsigned,
+    Attribute, Attributes, ClusterStyles, Color, ColorOrList, ColorScheme, Double, EscapedString,
+    Fill, FontName, Int, LabelJustification, LabelLocation, LabelString, LayerRange, Length,
+    LengthUnit, Margin, Point, Positive, PositiveNonZero, RankType, Style, Unsigned,
 };
 use std::fmt::Display;
 
@@ -44,11 +44,11 @@ impl ClusterAttributes {
     attribute_setter!(background_color, BackgroundColor, ColorOrList);
     attribute_setter!(class, Class, Vec<String>);
     attribute_setter!(color, Color, ColorOrList);
-    attribute_setter!(color_scheme, ColorScheme, String);
+    attribute_setter!(color_scheme, ColorScheme);
     attribute_setter!(fill_color, FillColor, ColorOrList);
     attribute_setter!(font_color, FontColor, Color);
     attribute_setter!(font_name, FontName, FontName);
-    attribute_setter!(font_size, FontSize, Double);
+    length_attribute_setter!(font_size, FontSize, to_points);
     attribute_setter!(gradient_angle, GradientAngle, Int);
     attribute_setter!(id, Id, EscapedString);
     attribute_setter!(spring_constant, SpringConstant, Positive);
@@ -59,9 +59,8 @@ impl ClusterAttributes {
     attribute_setter!(label_position, LabelPosition, Point);
     attribute_setter!(label_width, LabelWidth, Double);
     attribute_setter!(layer, Layer, LayerRange);
-    attribute_setter!(margin, Margin, PointOrBoth);
     boolean_attribute_setter!(no_justification, NoJustification);
-    attribute_setter!(pen_width, PenWidth, Positive);
+    length_attribute_setter!(pen_width, PenWidth, positive_points);
     attribute_setter!(peripheries, Peripheries, Unsigned);
     attribute_setter!(rank, Rank, RankType);
     attribute_setter!(sort_value, SortValue, Unsigned);
@@ -69,6 +68,24 @@ impl ClusterAttributes {
     attribute_setter!(target, Target, EscapedString);
     attribute_setter!(tooltip, Tooltip, EscapedString);
     attribute_setter!(url, Url, EscapedString);
+
+    ///
+    /// Sets the space between the cluster's contents and its border, converted to points.
+    ///
+    pub fn margin(self, margin: Margin) -> Self {
+        self.push(Attribute::Margin(margin.in_unit(LengthUnit::Points)))
+    }
+
+    ///
+    /// Sets the fill color from `fill`, replacing any earlier fill, and the matching fill style
+    /// alongside any other styles already set.
+    ///
+    pub fn fill(self, fill: Fill) -> Result<Self, crate::error::Error> {
+        let mut self_mut = self;
+        let style = Style::Cluster(fill.cluster_style()?);
+        fill.apply_to(&mut self_mut.0, style)?;
+        Ok(self_mut)
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -0,0 +1,387 @@
+/*!
+Named colors, color schemes and conversions between color representations.
+
+Color names are resolved in a [`ColorScheme`]; the default scheme is `x11`, the `svg` scheme
+provides the SVG/CSS names, and the ColorBrewer schemes such as `blues9` or `accent8` provide
+numbered colors. A name may also name its own scheme, as in `/blues9/3` or `/svg/gray`, and
+`//name` always refers to the default scheme.
+
+# Example
+
+```rust
+use simple_dot::attributes::{Color, ColorScheme};
+use std::str::FromStr;
+
+let scheme = ColorScheme::from_str("blues9").unwrap();
+assert_eq!(
+    Color::named("3").unwrap().resolve(Some(&scheme)).unwrap(),
+    [0xc6, 0xdb, 0xef, 0xff]
+);
+
+let color = Color::named("/blues9/9").unwrap();
+assert_eq!(color.contrasting().unwrap(), Color::named("white").unwrap());
+```
+
+ */
+
+use crate::attributes::{Color, Double, Scale};
+use crate::error::{invalid_value, Error};
+use std::{fmt::Display, str::FromStr};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The scheme used to look up color names, the value of the `colorscheme` attribute.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ColorScheme {
+    #[default]
+    X11,
+    Svg,
+    Brewer {
+        name: String,
+        size: usize,
+    },
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for ColorScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::X11 => write!(f, "x11"),
+            Self::Svg => write!(f, "svg"),
+            Self::Brewer { name, size } => write!(f, "{}{}", name, size),
+        }
+    }
+}
+
+impl FromStr for ColorScheme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase();
+        match name.as_str() {
+            "x11" => Ok(Self::X11),
+            "svg" => Ok(Self::Svg),
+            _ => match brewer::brewer_scheme(&name) {
+                Some((name, size)) => Ok(Self::Brewer {
+                    name: name.to_string(),
+                    size,
+                }),
+                None => Err(invalid_value(COLOR_SCHEME_TYPE_NAME, &s)),
+            },
+        }
+    }
+}
+
+impl ColorScheme {
+    ///
+    /// Returns the brewer scheme `name` with `size` colors, such as `brewer("blues", 9)`.
+    ///
+    pub fn brewer(name: &str, size: usize) -> Result<Self, Error> {
+        Self::from_str(&format!("{}{}", name, size))
+    }
+
+    ///
+    /// Returns the names of all brewer schemes, with every valid size.
+    ///
+    pub fn brewer_names() -> Vec<String> {
+        brewer::brewer_scheme_names().collect()
+    }
+
+    ///
+    /// Returns the number of colors in a brewer scheme, or `None` for a named scheme.
+    ///
+    pub fn size(&self) -> Option<usize> {
+        match self {
+            Self::Brewer { size, .. } => Some(*size),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns `true` if `name` is a color in this scheme; brewer colors are named by their
+    /// 1-based index.
+    ///
+    pub fn contains(&self, name: &str) -> bool {
+        self.lookup(name).is_some()
+    }
+
+    fn lookup(&self, name: &str) -> Option<u32> {
+        let name = name.to_ascii_lowercase();
+        let find = |table: &[(&str, u32)]| {
+            table
+                .binary_search_by_key(&name.as_str(), |(name, _)| name)
+                .ok()
+                .map(|i| table[i].1)
+        };
+        match self {
+            Self::X11 => find(x11::X11_COLORS),
+            Self::Svg => find(svg::SVG_COLORS),
+            Self::Brewer { name: base, size } => {
+                brewer::brewer_color(base, *size, name.parse().ok()?)
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl FromStr for Color {
+    type Err = Error;
+
+    ///
+    /// Parses `#rrggbb`, `#rrggbbaa`, three HSV values in `0.0..=1.0` separated by commas or
+    /// spaces, or a color name; a single number is a name in a numbered scheme.
+    ///
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if let Some(hex) = value.strip_prefix('#') {
+            let bytes: Option<Vec<u8>> = if hex.is_ascii() && (hex.len() == 6 || hex.len() == 8) {
+                (0..hex.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+                    .collect()
+            } else {
+                None
+            };
+            match bytes.as_deref() {
+                Some([r, g, b]) => Ok(Self::rgb(*r, *g, *b)),
+                Some([r, g, b, a]) => Ok(Self::rgba(*r, *g, *b, *a)),
+                _ => Err(invalid_value("Color", &value)),
+            }
+        } else if value.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+            && value.contains(|c: char| c == ',' || c.is_whitespace())
+        {
+            let hsv: Vec<Scale> = value
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    s.parse::<Double>()
+                        .map_err(|_| invalid_value("Color", &value))
+                        .and_then(Scale::try_from)
+                })
+                .collect::<Result<_, _>>()?;
+            match <[Scale; 3]>::try_from(hsv) {
+                Ok([h, s, v]) => Ok(Self::hsv(h, s, v)),
+                Err(_) => Err(invalid_value("Color", &value)),
+            }
+        } else {
+            Self::named(value)
+        }
+    }
+}
+
+impl Color {
+    ///
+    /// Returns the color `name` in `scheme`, written as `/scheme/name`.
+    ///
+    pub fn named_in(scheme: &ColorScheme, name: &str) -> Result<Self, Error> {
+        if scheme.contains(name) {
+            Ok(Self::Name(format!("/{}/{}", scheme, name)))
+        } else {
+            Err(invalid_value(COLOR_TYPE_NAME, &name))
+        }
+    }
+
+    ///
+    /// Resolves this color to `[r, g, b, a]`. Names that don't specify their own scheme are
+    /// looked up in `scheme` first, then in the X11 and SVG schemes.
+    ///
+    pub fn resolve(&self, scheme: Option<&ColorScheme>) -> Result<[u8; 4], Error> {
+        match self {
+            Self::Rgb { r, g, b } => Ok([*r, *g, *b, 0xff]),
+            Self::Rgba { r, g, b, a } => Ok([*r, *g, *b, *a]),
+            Self::Hsv { h, s, v } => {
+                let [r, g, b] = hsv_to_rgb(*h.as_ref(), *s.as_ref(), *v.as_ref());
+                Ok([r, g, b, 0xff])
+            }
+            Self::Name(name) => resolve_name(name, scheme)
+                .map(|rgb| {
+                    let [_, r, g, b] = rgb.to_be_bytes();
+                    [r, g, b, 0xff]
+                })
+                .ok_or_else(|| invalid_value(COLOR_TYPE_NAME, name)),
+        }
+    }
+
+    ///
+    /// Returns this color as a [`Color::Rgba`] value.
+    ///
+    pub fn to_rgba(&self) -> Result<Self, Error> {
+        let [r, g, b, a] = self.resolve(None)?;
+        Ok(Self::rgba(r, g, b, a))
+    }
+
+    ///
+    /// Returns this color as a [`Color::Rgb`] value, any alpha channel is dropped.
+    ///
+    pub fn to_rgb(&self) -> Result<Self, Error> {
+        let [r, g, b, _] = self.resolve(None)?;
+        Ok(Self::rgb(r, g, b))
+    }
+
+    ///
+    /// Returns this color as a [`Color::Hsv`] value, any alpha channel is dropped.
+    ///
+    pub fn to_hsv(&self) -> Result<Self, Error> {
+        if let Self::Hsv { .. } = self {
+            return Ok(self.clone());
+        }
+        let [r, g, b, _] = self.resolve(None)?;
+        let [h, s, v] = rgb_to_hsv(r, g, b);
+        Ok(Self::hsv(
+            Scale::try_from(h)?,
+            Scale::try_from(s)?,
+            Scale::try_from(v)?,
+        ))
+    }
+
+    ///
+    /// Returns the X11, or failing that SVG, name for this color if it is opaque and exactly
+    /// matches a named color.
+    ///
+    pub fn to_name(&self) -> Option<Self> {
+        let [r, g, b, a] = self.resolve(None).ok()?;
+        if a != 0xff {
+            return None;
+        }
+        let rgb = u32::from_be_bytes([0, r, g, b]);
+        [x11::X11_COLORS, svg::SVG_COLORS]
+            .iter()
+            .find_map(|table| table.iter().find(|(_, v)| *v == rgb))
+            .map(|(name, _)| Self::Name(name.to_string()))
+    }
+
+    ///
+    /// Returns the relative luminance of this color, from `0.0` for black to `1.0` for white.
+    ///
+    pub fn luminance(&self) -> Result<Double, Error> {
+        let [r, g, b, _] = self.resolve(None)?;
+        let linear = |c: u8| {
+            let c = c as Double / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        Ok(0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b))
+    }
+
+    ///
+    /// Returns the contrast ratio between this color and `other`, from `1.0` to `21.0`.
+    ///
+    pub fn contrast_ratio(&self, other: &Color) -> Result<Double, Error> {
+        let (a, b) = (self.luminance()?, other.luminance()?);
+        Ok((a.max(b) + 0.05) / (a.min(b) + 0.05))
+    }
+
+    ///
+    /// Returns either `black` or `white`, whichever contrasts most with this color; useful as
+    /// the font color for text drawn on a background of this color.
+    ///
+    pub fn contrasting(&self) -> Result<Self, Error> {
+        let black = Self::Name("black".to_string());
+        let white = Self::Name("white".to_string());
+        if self.contrast_ratio(&black)? >= self.contrast_ratio(&white)? {
+            Ok(black)
+        } else {
+            Ok(white)
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const COLOR_TYPE_NAME: &str = "Color";
+
+const COLOR_SCHEME_TYPE_NAME: &str = "ColorScheme";
+
+///
+/// Returns `true` if `name` is valid as an unresolved color name: a known X11 or SVG name, a
+/// brewer index to be resolved against the `colorscheme` attribute, or a `/scheme/name` pair
+/// that names an existing color.
+///
+pub(super) fn is_valid_color_name(name: &str) -> bool {
+    if name.starts_with('/') {
+        resolve_name(name, None).is_some()
+    } else {
+        !name.is_empty()
+            && (name.chars().all(|c| c.is_ascii_digit())
+                || ColorScheme::X11.contains(name)
+                || ColorScheme::Svg.contains(name))
+    }
+}
+
+fn resolve_name(name: &str, scheme: Option<&ColorScheme>) -> Option<u32> {
+    if let Some(rest) = name.strip_prefix('/') {
+        let (scheme, name) = rest.split_once('/')?;
+        if scheme.is_empty() {
+            ColorScheme::X11.lookup(name)
+        } else {
+            ColorScheme::from_str(scheme).ok()?.lookup(name)
+        }
+    } else {
+        scheme
+            .and_then(|scheme| scheme.lookup(name))
+            .or_else(|| ColorScheme::X11.lookup(name))
+            .or_else(|| ColorScheme::Svg.lookup(name))
+    }
+}
+
+fn hsv_to_rgb(h: Double, s: Double, v: Double) -> [u8; 3] {
+    let to_byte = |c: Double| (c * 255.0).round() as u8;
+    let h = (h * 6.0) % 6.0;
+    let c = v * s;
+    let x = c * (1.0 - ((h % 2.0) - 1.0).abs());
+    let m = v - c;
+    let (r, g, b) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    [to_byte(r + m), to_byte(g + m), to_byte(b + m)]
+}
+
+fn rgb_to_hsv(r: u8, g: u8, b: u8) -> [Double; 3] {
+    let (r, g, b) = (
+        r as Double / 255.0,
+        g as Double / 255.0,
+        b as Double / 255.0,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let h = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.0) / 6.0
+    } else if max == g {
+        ((b - r) / delta + 2.0) / 6.0
+    } else {
+        ((r - g) / delta + 4.0) / 6.0
+    };
+    let s = if max == 0.0 { 0.0 } else { delta / max };
+    [h, s, max]
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+mod brewer;
+
+mod svg;
+
+mod x11;
@@ -8,9 +8,9 @@ More detailed description, with
  */
 
 use crate::attributes::{
-    ArrowType, Attribute, Attributes, Color, ColorOrList, Direction, Double, EdgeStyles,
-    EscapedString, FontName, LabelString, LayerRange, Point, PortPosition, Position, Positive,
-    Style, Unsigned,
+    ArrowType, Attribute, Attributes, Color, ColorOrList, ColorScheme, Direction, Double,
+    EdgeStyles, EscapedString, FontName, LabelString, LayerRange, Length, Point, PortPosition,
+    Position, Positive, Style, Unsigned,
 };
 use std::fmt::Display;
 
@@ -48,7 +48,7 @@ impl EdgeAttributes {
     attribute_setter!(arrow_tail, ArrowTail, ArrowType);
     attribute_setter!(class, Class, Vec<String>);
     attribute_setter!(color, Color, ColorOrList);
-    attribute_setter!(color_scheme, ColorScheme, String);
+    attribute_setter!(color_scheme, ColorScheme);
     attribute_setter!(comment, Comment, String);
     boolean_attribute_setter!(no_layout_constraint, Constraint);
     boolean_attribute_setter!(decorate_label, Decorate);
@@ -59,7 +59,7 @@ impl EdgeAttributes {
     attribute_setter!(fill_color, FillColor, ColorOrList);
     attribute_setter!(font_color, FontColor, Color);
     attribute_setter!(font_name, FontName, FontName);
-    attribute_setter!(font_size, FontSize, Double);
+    length_attribute_setter!(font_size, FontSize, to_points);
     attribute_setter!(head_label_position, HeadLabelPosition, Point);
     boolean_attribute_setter!(dont_clip_head, HeadClip);
     attribute_setter!(head_label, HeadLabel, LabelString);
@@ -74,7 +74,7 @@ impl EdgeAttributes {
     boolean_attribute_setter!(allow_label_float, LabelFloat);
     attribute_setter!(label_font_color, LabelFontColor, Color);
     attribute_setter!(label_font_name, LabelFontName, FontName);
-    attribute_setter!(label_font_size, LabelFontSize, Double);
+    length_attribute_setter!(label_font_size, LabelFontSize, to_points);
     attribute_setter!(label_position, LabelPosition, Point);
     attribute_setter!(label_target, LabelTarget, EscapedString);
     attribute_setter!(label_tooltip, LabelTooltip, EscapedString);
@@ -85,7 +85,7 @@ impl EdgeAttributes {
     attribute_setter!(logical_tail, LogicalTail, String);
     attribute_setter!(minimum_length, MinLength, Unsigned);
     boolean_attribute_setter!(no_justification, NoJustification);
-    attribute_setter!(pen_width, PenWidth, Positive);
+    length_attribute_setter!(pen_width, PenWidth, positive_points);
     attribute_setter!(position, Position);
     attribute_setter!(same_head, SameHead, String);
     attribute_setter!(same_tail, SameTail, String);
@@ -104,6 +104,15 @@ impl EdgeAttributes {
     attribute_setter!(weight, Weight, Unsigned);
     attribute_setter!(external_label, ExternalLabel, LabelString);
     attribute_setter!(external_label_position, ExternalLabelPosition, Point);
+
+    ///
+    /// Swap each attribute that applies to one end of an edge with its counterpart for the
+    /// other end, as required when an edge is reversed. The `dir` attribute is not changed, so
+    /// the arrow of a reversed edge always points the other way.
+    ///
+    pub fn reversed(self) -> Self {
+        Self(self.0.into_iter().map(reverse_attribute).collect())
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -115,6 +124,32 @@ impl EdgeAttributes {
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+fn reverse_attribute(attribute: Attribute) -> Attribute {
+    match attribute {
+        Attribute::ArrowHead(v) => Attribute::ArrowTail(v),
+        Attribute::ArrowTail(v) => Attribute::ArrowHead(v),
+        Attribute::HeadLabelPosition(v) => Attribute::TailLabelPosition(v),
+        Attribute::TailLabelPosition(v) => Attribute::HeadLabelPosition(v),
+        Attribute::HeadClip(v) => Attribute::TailClip(v),
+        Attribute::TailClip(v) => Attribute::HeadClip(v),
+        Attribute::HeadUrl(v) => Attribute::TailUrl(v),
+        Attribute::TailUrl(v) => Attribute::HeadUrl(v),
+        Attribute::HeadLabel(v) => Attribute::TailLabel(v),
+        Attribute::TailLabel(v) => Attribute::HeadLabel(v),
+        Attribute::HeadPort(v) => Attribute::TailPort(v),
+        Attribute::TailPort(v) => Attribute::HeadPort(v),
+        Attribute::HeadTarget(v) => Attribute::TailTarget(v),
+        Attribute::TailTarget(v) => Attribute::HeadTarget(v),
+        Attribute::HeadTooltip(v) => Attribute::TailTooltip(v),
+        Attribute::TailTooltip(v) => Attribute::HeadTooltip(v),
+        Attribute::LogicalHead(v) => Attribute::LogicalTail(v),
+        Attribute::LogicalTail(v) => Attribute::LogicalHead(v),
+        Attribute::SameHead(v) => Attribute::SameTail(v),
+        Attribute::SameTail(v) => Attribute::SameHead(v),
+        _ => attribute,
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Modules
 // ------------------------------------------------------------------------------------------------
@@ -0,0 +1,264 @@
+/*!
+Solid, gradient and multi-color fills for nodes and clusters.
+
+A `Fill` describes both the `fillcolor` value and the style that makes Graphviz use it; setting
+a fill with `NodeAttributes::fill` or `ClusterAttributes::fill` validates it and replaces any
+earlier fill style with the matching one.
+
+* Linear and radial gradients blend two colors; the first may have a weight giving the
+  proportion of the fill before the blend starts, and either may have an angle.
+* Striped and wedged fills divide the fill between their colors in proportion to their weights,
+  which must not add up to more than `1.0`; any colors without a weight share what is left.
+  Wedged fills are only available for nodes.
+
+# Example
+
+```rust
+use simple_dot::attributes::{Color, Fill, NodeAttributes};
+
+let pie = Fill::ratios(vec![
+    (Color::named("green").unwrap(), 3.0),
+    (Color::named("red").unwrap(), 1.0),
+])
+.unwrap();
+assert_eq!(
+    NodeAttributes::default().fill(pie).unwrap().to_string(),
+    "[ fillcolor = \"green;0.75:red;0.25\"; style = wedged ]"
+);
+```
+
+ */
+
+use crate::attributes::{
+    Attribute, ClusterStyles, Color, ColorOrList, Double, Int, NodeStyles, Scale, Style,
+    WeightedColor,
+};
+use crate::error::{invalid_state, invalid_value, Error};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillKind {
+    Solid,
+    Linear,
+    Radial,
+    Striped,
+    Wedged,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fill {
+    kind: FillKind,
+    colors: Vec<WeightedColor>,
+    angle: Option<Int>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<Color> for Fill {
+    fn from(v: Color) -> Self {
+        Self::solid(v)
+    }
+}
+
+impl Fill {
+    pub fn solid(color: Color) -> Self {
+        Self::new(FillKind::Solid, vec![WeightedColor::new(color)])
+    }
+
+    pub fn linear(from: Color, to: Color) -> Self {
+        Self::new(
+            FillKind::Linear,
+            vec![WeightedColor::new(from), WeightedColor::new(to)],
+        )
+    }
+
+    pub fn radial(from: Color, to: Color) -> Self {
+        Self::new(
+            FillKind::Radial,
+            vec![WeightedColor::new(from), WeightedColor::new(to)],
+        )
+    }
+
+    pub fn striped(colors: Vec<WeightedColor>) -> Self {
+        Self::new(FillKind::Striped, colors)
+    }
+
+    pub fn wedged(colors: Vec<WeightedColor>) -> Self {
+        Self::new(FillKind::Wedged, colors)
+    }
+
+    ///
+    /// A wedged fill with one wedge per color, sized in proportion to its value; values must
+    /// not be negative and at least one must be greater than zero.
+    ///
+    pub fn ratios(values: Vec<(Color, Double)>) -> Result<Self, Error> {
+        let total: Double = values.iter().map(|(_, v)| v).sum();
+        if values.iter().any(|(_, v)| !v.is_finite() || *v < 0.0) || total <= 0.0 {
+            return Err(invalid_value(
+                FILL_TYPE_NAME,
+                &values.iter().map(|(_, v)| *v).collect::<Vec<Double>>(),
+            ));
+        }
+        Ok(Self::wedged(
+            values
+                .into_iter()
+                .map(|(color, v)| {
+                    Scale::try_from(v / total).map(|weight| WeightedColor::weighted(color, weight))
+                })
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    ///
+    /// Sets the proportion of a gradient filled with its first color before blending starts.
+    ///
+    pub fn weight(self, weight: Scale) -> Self {
+        let mut self_mut = self;
+        if let Some(first) = self_mut.colors.first_mut() {
+            *first = WeightedColor::weighted(first.color().clone(), weight);
+        }
+        self_mut
+    }
+
+    ///
+    /// Sets the angle of a gradient, in degrees.
+    ///
+    pub fn angle(self, degrees: Int) -> Self {
+        let mut self_mut = self;
+        self_mut.angle = Some(degrees);
+        self_mut
+    }
+
+    pub fn kind(&self) -> FillKind {
+        self.kind
+    }
+
+    pub fn colors(&self) -> &[WeightedColor] {
+        &self.colors
+    }
+
+    ///
+    /// Checks the number of colors, their weights and the use of an angle against the kind of
+    /// fill.
+    ///
+    pub fn validate(&self) -> Result<(), Error> {
+        let gradient = matches!(self.kind, FillKind::Linear | FillKind::Radial);
+        match self.kind {
+            FillKind::Solid if self.colors.len() != 1 || self.colors[0].weight().is_some() => {
+                Err(invalid_state(FILL_TYPE_NAME, "a solid fill has one color"))
+            }
+            FillKind::Linear | FillKind::Radial
+                if self.colors.len() != 2 || self.colors[1].weight().is_some() =>
+            {
+                Err(invalid_state(
+                    FILL_TYPE_NAME,
+                    "a gradient has two colors and only the first may have a weight",
+                ))
+            }
+            FillKind::Striped | FillKind::Wedged if self.colors.is_empty() => Err(invalid_state(
+                FILL_TYPE_NAME,
+                "a striped or wedged fill needs at least one color",
+            )),
+            _ if self.angle.is_some() && !gradient => Err(invalid_state(
+                FILL_TYPE_NAME,
+                "an angle may only be set on a two-color gradient",
+            )),
+            _ if total_weight(&self.colors) > 1.0 + WEIGHT_TOLERANCE => Err(invalid_state(
+                FILL_TYPE_NAME,
+                "the weights of a fill must not add up to more than 1.0",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn node_style(&self) -> NodeStyles {
+        match self.kind {
+            FillKind::Solid | FillKind::Linear => NodeStyles::Filled,
+            FillKind::Radial => NodeStyles::Radial,
+            FillKind::Striped => NodeStyles::Striped,
+            FillKind::Wedged => NodeStyles::Wedged,
+        }
+    }
+
+    pub(crate) fn cluster_style(&self) -> Result<ClusterStyles, Error> {
+        match self.kind {
+            FillKind::Solid | FillKind::Linear => Ok(ClusterStyles::Filled),
+            FillKind::Radial => Ok(ClusterStyles::Radial),
+            FillKind::Striped => Ok(ClusterStyles::Striped),
+            FillKind::Wedged => Err(invalid_state(
+                FILL_TYPE_NAME,
+                "a wedged fill may only be used on nodes",
+            )),
+        }
+    }
+
+    ///
+    /// Validates this fill and replaces the fill color, gradient angle and fill style in
+    /// `attributes`, keeping any other styles.
+    ///
+    pub(crate) fn apply_to(
+        &self,
+        attributes: &mut Vec<Attribute>,
+        style: Style,
+    ) -> Result<(), Error> {
+        self.validate()?;
+        let color = match self.kind {
+            FillKind::Solid => ColorOrList::Color(self.colors[0].color().clone()),
+            _ => ColorOrList::List(self.colors.clone()),
+        };
+        attributes.retain(|a| !matches!(a, Attribute::FillColor(_) | Attribute::GradientAngle(_)));
+        attributes.push(Attribute::FillColor(color));
+        if let Some(angle) = self.angle {
+            attributes.push(Attribute::GradientAngle(angle));
+        }
+        match attributes.iter_mut().find_map(|a| match a {
+            Attribute::Style(styles) => Some(styles),
+            _ => None,
+        }) {
+            Some(styles) => {
+                styles.retain(|s| !is_fill_style(s));
+                styles.push(style);
+            }
+            None => attributes.push(Attribute::Style(vec![style])),
+        }
+        Ok(())
+    }
+
+    fn new(kind: FillKind, colors: Vec<WeightedColor>) -> Self {
+        Self {
+            kind,
+            colors,
+            angle: None,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const FILL_TYPE_NAME: &str = "Fill";
+
+const WEIGHT_TOLERANCE: Double = 1e-9;
+
+fn total_weight(colors: &[WeightedColor]) -> Double {
+    colors
+        .iter()
+        .filter_map(|c| c.weight())
+        .map(|w| *w.as_ref())
+        .sum()
+}
+
+fn is_fill_style(style: &Style) -> bool {
+    matches!(
+        style,
+        Style::Node(
+            NodeStyles::Filled | NodeStyles::Radial | NodeStyles::Striped | NodeStyles::Wedged
+        ) | Style::Cluster(ClusterStyles::Filled | ClusterStyles::Radial | ClusterStyles::Striped)
+    )
+}
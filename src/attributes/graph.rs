@@ -8,11 +8,12 @@ More detailed description, with
  */
 
 use crate::attributes::{
-    AspectRatio, Attribute, Attributes, Color, ColorOrList, Degrees, DistanceMatrix, Double,
-    EscapedString, FontName, FontNameMapping, GraphStyles, Int, LabelJustification, LabelLocation,
-    LabelString, LayerList, LayerRange, LayoutOptimization, Ordering, OutputOrder, Overlap,
-    PackMode, PageOutputDirection, Point, PointOrBoth, Positive, QuadTreeType, RankDirection,
-    RankSeparation, RankType, Rectangle, Smoothing, Splines, Start, Style, Unsigned, ViewPort,
+    AspectRatio, Attribute, Attributes, Color, ColorOrList, ColorScheme, Degrees, DistanceMatrix,
+    Double, EscapedString, FontName, FontNameMapping, GraphStyles, Int, LabelJustification,
+    LabelLocation, LabelString, LayerList, LayerRange, LayoutOptimization, Length, LengthUnit,
+    Margin, Ordering, OutputOrder, Overlap, PackMode, PageOutputDirection, Point, PointOrBoth,
+    Positive, QuadTreeType, RankDirection, RankSeparation, RankType, Rectangle, Size, Smoothing,
+    Splines, Start, Style, Unsigned, ViewPort,
 };
 use std::fmt::Display;
 
@@ -48,7 +49,7 @@ impl GraphAttributes {
     boolean_attribute_setter!(is_centered, Center);
     attribute_setter!(charset, Charset, String);
     attribute_setter!(class, Class, Vec<String>);
-    attribute_setter!(color_scheme, ColorScheme, String);
+    attribute_setter!(color_scheme, ColorScheme);
     attribute_setter!(comment, Comment, String);
     boolean_attribute_setter!(is_compound, Compound);
     boolean_attribute_setter!(concentrate_edges, Concentrate);
@@ -63,7 +64,7 @@ impl GraphAttributes {
     attribute_setter!(font_name, FontName);
     attribute_setter!(font_names_for_svg, FontNames, FontNameMapping);
     attribute_setter!(font_path, FontPath, String);
-    attribute_setter!(font_size, FontSize, Double);
+    length_attribute_setter!(font_size, FontSize, to_points);
     boolean_attribute_setter!(force_external_labels, ForceLabels);
     attribute_setter!(gradient_angle, GradientAngle, Int);
     attribute_setter!(id, Id, EscapedString);
@@ -83,7 +84,6 @@ impl GraphAttributes {
     attribute_setter!(layout_engine, LayoutEngine, String);
     attribute_setter!(levels, Levels, Int);
     attribute_setter!(levels_gap, LevelsGap, Double);
-    attribute_setter!(margin, Margin, PointOrBoth);
     attribute_setter!(max_iterations, MaxIterations, Int);
     attribute_setter!(mc_limit, McLimit, Double);
     attribute_setter!(min_distance, MinDistance, Positive);
@@ -91,7 +91,7 @@ impl GraphAttributes {
     attribute_setter!(model, Model, DistanceMatrix);
     boolean_attribute_setter!(use_mosek, Mosek);
     boolean_attribute_setter!(use_new_ranking, NewRank);
-    attribute_setter!(node_separation, NodeSeparation, Positive);
+    length_attribute_setter!(node_separation, NodeSeparation, positive_inches);
     boolean_attribute_setter!(no_justification, NoJustification);
     attribute_setter!(normalize, Normalize, Double);
     boolean_attribute_setter!(no_layout_translation, NoTranslate);
@@ -105,8 +105,6 @@ impl GraphAttributes {
     boolean_attribute_setter!(no_overlap_shrink, OverlapShrink);
     boolean_attribute_setter!(pack_graphs, Pack);
     attribute_setter!(pack_mode, PackMode);
-    attribute_setter!(pad, Pad, PointOrBoth);
-    attribute_setter!(page_size, PageSize, PointOrBoth);
     attribute_setter!(page_output_direction, PageOutputDirection);
     attribute_setter!(quad_tree, QuadTree, QuadTreeType);
     attribute_setter!(quantum, Quantum, Positive);
@@ -124,7 +122,6 @@ impl GraphAttributes {
     attribute_setter!(search_size, SearchSize, Int);
     attribute_setter!(separation, Separation, PointOrBoth);
     attribute_setter!(show_boxes, ShowBoxes, Unsigned);
-    attribute_setter!(size, Size, PointOrBoth);
     attribute_setter!(smoothing, Smoothing);
     attribute_setter!(sort_value, SortValue, Unsigned);
     attribute_setter!(splines, Splines);
@@ -139,6 +136,37 @@ impl GraphAttributes {
     attribute_setter!(xdot_version, XDotVersion, String);
 
     style_attribute_setter!(Graph, GraphStyles);
+
+    ///
+    /// Sets the margin around the drawing, converted to inches.
+    ///
+    pub fn margin(self, margin: Margin) -> Self {
+        self.push(Attribute::Margin(margin.in_unit(LengthUnit::Inches)))
+    }
+
+    ///
+    /// Sets the padding inside the drawing's bounding box, converted to inches.
+    ///
+    pub fn pad(self, pad: Margin) -> Self {
+        self.push(Attribute::Pad(pad.in_unit(LengthUnit::Inches)))
+    }
+
+    ///
+    /// Sets the size of a page when paginating, converted to inches; a page cannot be filled
+    /// so `Size::fill` is ignored.
+    ///
+    pub fn page_size(self, page_size: Size) -> Self {
+        self.push(Attribute::PageSize(
+            page_size.in_unit(LengthUnit::Inches).without_fill(),
+        ))
+    }
+
+    ///
+    /// Sets the maximum size of the drawing, converted to inches.
+    ///
+    pub fn size(self, size: Size) -> Self {
+        self.push(Attribute::Size(size.in_unit(LengthUnit::Inches)))
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
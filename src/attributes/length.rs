@@ -0,0 +1,297 @@
+/*!
+Lengths with units, and the sizes and margins built from them.
+
+Graphviz measures most lengths in inches, such as `width`, `height`, `nodesep`, `ranksep`,
+`size` and the `margin` of graphs and nodes, but font sizes, pen widths and the `margin` of
+clusters are in points. A `Length` may be given in any unit and each setter converts it to the
+unit its attribute expects. A `Size` or `Margin` holds its lengths in inches, so that one used
+directly in an `Attribute` is also written in the unit most attributes expect.
+
+# Example
+
+```rust
+use simple_dot::attributes::{GraphAttributes, Length, Margin, Size};
+
+let a4 = Size::new(
+    Length::millimeters(210.0).unwrap(),
+    Length::millimeters(297.0).unwrap(),
+);
+let attributes = GraphAttributes::default()
+    .size(a4)
+    .margin(Margin::all(Length::points(36.0).unwrap()));
+assert_eq!(
+    attributes.to_string(),
+    "[ size = \"8.267716535433072,11.692913385826772\"; margin = 0.5 ]"
+);
+```
+
+ */
+
+use crate::attributes::{Double, Positive};
+use crate::error::{invalid_value, Error};
+use std::fmt::Display;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LengthUnit {
+    Inches,
+    Points,
+    Millimeters,
+}
+
+///
+/// A length that is not negative, in a given unit; when written it is only the value so it must
+/// first be converted to the unit the attribute expects.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Length {
+    value: Double,
+    unit: LengthUnit,
+}
+
+///
+/// A width and height in inches, optionally scaled up to fill the size when used for `size`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Size {
+    width: Length,
+    height: Length,
+    fill: bool,
+}
+
+///
+/// The same margin on all sides, or separate horizontal and vertical margins; the constructors
+/// hold each length in inches.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum Margin {
+    All(Length),
+    Sides { x: Length, y: Length },
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for LengthUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Inches => "in",
+                Self::Points => "pt",
+                Self::Millimeters => "mm",
+            }
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Length {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl Length {
+    pub fn new(value: Double, unit: LengthUnit) -> Result<Self, Error> {
+        if value.is_finite() && value.is_sign_positive() {
+            Ok(Self { value, unit })
+        } else {
+            Err(invalid_value(LENGTH_TYPE_NAME, &value))
+        }
+    }
+
+    pub fn inches(value: Double) -> Result<Self, Error> {
+        Self::new(value, LengthUnit::Inches)
+    }
+
+    pub fn points(value: Double) -> Result<Self, Error> {
+        Self::new(value, LengthUnit::Points)
+    }
+
+    pub fn millimeters(value: Double) -> Result<Self, Error> {
+        Self::new(value, LengthUnit::Millimeters)
+    }
+
+    ///
+    /// A length of `pixels` on a device with `dpi` pixels per inch.
+    ///
+    pub fn pixels(pixels: Double, dpi: Double) -> Result<Self, Error> {
+        if dpi.is_finite() && dpi > 0.0 {
+            Self::inches(pixels / dpi)
+        } else {
+            Err(invalid_value("Dpi", &dpi))
+        }
+    }
+
+    pub fn value(&self) -> Double {
+        self.value
+    }
+
+    pub fn unit(&self) -> LengthUnit {
+        self.unit
+    }
+
+    ///
+    /// This length expressed in `unit`.
+    ///
+    pub fn to_unit(self, unit: LengthUnit) -> Self {
+        if unit == self.unit {
+            self
+        } else {
+            Self {
+                value: match unit {
+                    LengthUnit::Inches => self.to_inches(),
+                    LengthUnit::Points => self.to_points(),
+                    LengthUnit::Millimeters => self.to_millimeters(),
+                },
+                unit,
+            }
+        }
+    }
+
+    pub fn to_inches(&self) -> Double {
+        match self.unit {
+            LengthUnit::Inches => self.value,
+            LengthUnit::Points => self.value / POINTS_PER_INCH,
+            LengthUnit::Millimeters => self.value / MILLIMETERS_PER_INCH,
+        }
+    }
+
+    pub fn to_points(&self) -> Double {
+        match self.unit {
+            LengthUnit::Inches => self.value * POINTS_PER_INCH,
+            LengthUnit::Points => self.value,
+            LengthUnit::Millimeters => self.value / MILLIMETERS_PER_INCH * POINTS_PER_INCH,
+        }
+    }
+
+    pub fn to_millimeters(&self) -> Double {
+        match self.unit {
+            LengthUnit::Inches => self.value * MILLIMETERS_PER_INCH,
+            LengthUnit::Points => self.value / POINTS_PER_INCH * MILLIMETERS_PER_INCH,
+            LengthUnit::Millimeters => self.value,
+        }
+    }
+
+    pub(crate) fn positive_inches(&self) -> Positive {
+        Positive(self.to_inches())
+    }
+
+    pub(crate) fn positive_points(&self) -> Positive {
+        Positive(self.to_points())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\"{},{}{}\"",
+            self.width,
+            self.height,
+            if self.fill { "!" } else { "" }
+        )
+    }
+}
+
+impl Size {
+    pub fn new(width: Length, height: Length) -> Self {
+        Self {
+            width: width.to_unit(LengthUnit::Inches),
+            height: height.to_unit(LengthUnit::Inches),
+            fill: false,
+        }
+    }
+
+    pub fn square(side: Length) -> Self {
+        Self::new(side, side)
+    }
+
+    ///
+    /// Scales the drawing up until it fills this size; only used by the graph's `size`.
+    ///
+    pub fn fill(self) -> Self {
+        let mut self_mut = self;
+        self_mut.fill = true;
+        self_mut
+    }
+
+    pub fn width(&self) -> Length {
+        self.width
+    }
+
+    pub fn height(&self) -> Length {
+        self.height
+    }
+
+    pub fn is_fill(&self) -> bool {
+        self.fill
+    }
+
+    pub(crate) fn in_unit(self, unit: LengthUnit) -> Self {
+        Self {
+            width: self.width.to_unit(unit),
+            height: self.height.to_unit(unit),
+            fill: self.fill,
+        }
+    }
+
+    pub(crate) fn without_fill(self) -> Self {
+        Self {
+            fill: false,
+            ..self
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Margin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All(v) => write!(f, "{}", v),
+            Self::Sides { x, y } => write!(f, "\"{},{}\"", x, y),
+        }
+    }
+}
+
+impl Margin {
+    pub fn all(margin: Length) -> Self {
+        Self::All(margin.to_unit(LengthUnit::Inches))
+    }
+
+    pub fn sides(x: Length, y: Length) -> Self {
+        Self::Sides {
+            x: x.to_unit(LengthUnit::Inches),
+            y: y.to_unit(LengthUnit::Inches),
+        }
+    }
+
+    pub(crate) fn in_unit(self, unit: LengthUnit) -> Self {
+        match self {
+            Self::All(v) => Self::All(v.to_unit(unit)),
+            Self::Sides { x, y } => Self::Sides {
+                x: x.to_unit(unit),
+                y: y.to_unit(unit),
+            },
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const LENGTH_TYPE_NAME: &str = "Length";
+
+const POINTS_PER_INCH: Double = 72.0;
+const MILLIMETERS_PER_INCH: Double = 25.4;
@@ -7,7 +7,7 @@ More detailed description, with
 
  */
 
-use crate::error::invalid_value;
+use crate::error::{invalid_state, invalid_value};
 use std::{fmt::Display, str::FromStr};
 
 // ------------------------------------------------------------------------------------------------
@@ -20,6 +20,8 @@ where
 {
     fn attributes(&self) -> Option<&A>;
 
+    fn attributes_mut(&mut self) -> Option<&mut A>;
+
     fn has_attributes(&self) -> bool {
         self.attributes()
             .map(|v| !v.as_ref().is_empty())
@@ -31,7 +33,7 @@ where
         Self: Sized;
 }
 
-pub trait Attributes: Default + Display + AsRef<Vec<Attribute>> {}
+pub trait Attributes: Default + Display + AsRef<Vec<Attribute>> + AsMut<Vec<Attribute>> {}
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Attribute {
@@ -55,7 +57,7 @@ pub enum Attribute {
     Class(Vec<String>),
     ClusterRank(ClusterRank),
     Color(ColorOrList),
-    ColorScheme(String),
+    ColorScheme(ColorScheme),
     Comment(String),
     Compound(bool),
     Concentrate(bool),
@@ -125,7 +127,7 @@ pub enum Attribute {
     LevelsGap(Double),
     LogicalHead(String),
     LogicalTail(String),
-    Margin(PointOrBoth),
+    Margin(Margin),
     MaxIterations(Int),
     McLimit(Double),
     MinDistance(Positive),
@@ -148,8 +150,8 @@ pub enum Attribute {
     OverlapShrink(bool),
     Pack(bool), // or i16
     PackMode(PackMode),
-    Pad(PointOrBoth),
-    PageSize(PointOrBoth),
+    Pad(Margin),
+    PageSize(Size),
     PageOutputDirection(PageOutputDirection),
     PenColor(Color),
     PenWidth(Positive),
@@ -178,9 +180,10 @@ pub enum Attribute {
     SearchSize(Int),
     Separation(PointOrBoth),
     Shape(Shape),
+    ShapeFile(String),
     ShowBoxes(Unsigned),
     Sides(Unsigned),
-    Size(PointOrBoth),
+    Size(Size),
     Skew(Double),
     Smoothing(Smoothing),
     SortValue(Unsigned),
@@ -586,9 +589,6 @@ pub struct Rectangle {
     upper_right_y: Double,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Shape(String);
-
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Smoothing {
     None,
@@ -816,6 +816,7 @@ impl Display for Attribute {
                 Attribute::SearchSize(v) => v.to_string(),
                 Attribute::Separation(v) => v.to_string(),
                 Attribute::Shape(v) => v.to_string(),
+                Attribute::ShapeFile(v) => format!("\"{}\"", v),
                 Attribute::ShowBoxes(v) => v.to_string(),
                 Attribute::Sides(v) => v.to_string(),
                 Attribute::Size(v) => v.to_string(),
@@ -967,7 +968,7 @@ impl Attribute {
             Attribute::Pack(_) => "pack",
             Attribute::PackMode(_) => "packmode",
             Attribute::Pad(_) => "pad",
-            Attribute::PageSize(_) => "pagesize",
+            Attribute::PageSize(_) => "page",
             Attribute::PageOutputDirection(_) => "pagedir",
             Attribute::PenColor(_) => "pencolor",
             Attribute::PenWidth(_) => "penwidth",
@@ -996,6 +997,7 @@ impl Attribute {
             Attribute::SearchSize(_) => "searchsize",
             Attribute::Separation(_) => "sep",
             Attribute::Shape(_) => "shape",
+            Attribute::ShapeFile(_) => "shapefile",
             Attribute::ShowBoxes(_) => "showboxes",
             Attribute::Sides(_) => "sides",
             Attribute::Size(_) => "size",
@@ -1086,14 +1088,79 @@ impl Default for ArrowType {
     }
 }
 
+impl FromStr for ArrowType {
+    type Err = crate::error::Error;
+
+    ///
+    /// Parses an arrow name such as `lteeoldiamond`; the older synonyms `ediamond`, `open`,
+    /// `halfopen`, `empty` and `invempty` are accepted, as are modifiers in either order.
+    ///
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let arrow_name = match value {
+            "ediamond" => "odiamond",
+            "open" => "vee",
+            "halfopen" => "lvee",
+            "empty" => "onormal",
+            "invempty" => "oinv",
+            _ => value,
+        };
+        let mut shapes: Vec<ArrowType> = Default::default();
+        let mut rest = arrow_name;
+        while !rest.is_empty() {
+            let mut open = false;
+            let mut clip_side = ClipSide::None;
+            loop {
+                if !open && rest.starts_with('o') {
+                    open = true;
+                } else if clip_side == ClipSide::None && rest.starts_with('l') {
+                    clip_side = ClipSide::Left;
+                } else if clip_side == ClipSide::None && rest.starts_with('r') {
+                    clip_side = ClipSide::Right;
+                } else {
+                    break;
+                }
+                rest = &rest[1..];
+            }
+            let (name, shape) = ARROW_SHAPE_NAMES
+                .iter()
+                .find(|(name, _)| rest.starts_with(name))
+                .ok_or_else(|| invalid_value(ARROW_TYPE_TYPE_NAME, &value))?;
+            rest = &rest[name.len()..];
+            shapes.push(ArrowType::new(clip_side, open, *shape)?);
+        }
+        let mut shapes = shapes.into_iter().rev();
+        let last = shapes
+            .next()
+            .ok_or_else(|| invalid_value(ARROW_TYPE_TYPE_NAME, &value))?;
+        shapes.try_fold(last, |next, shape| shape.set_next_shape(next))
+    }
+}
+
 impl ArrowType {
-    pub fn new(clip_side: ClipSide, open: bool, shape: ArrowShape) -> Self {
-        Self {
+    ///
+    /// Fails if the modifiers are not valid for `shape`; `none` takes no modifiers and `dot`
+    /// cannot be clipped.
+    ///
+    pub fn new(
+        clip_side: ClipSide,
+        open: bool,
+        shape: ArrowShape,
+    ) -> Result<Self, crate::error::Error> {
+        check_arrow_modifiers(shape, open, clip_side)?;
+        Ok(Self {
             clip_side,
             open,
             shape,
             next: Default::default(),
-        }
+        })
+    }
+
+    ///
+    /// Returns the canonical name of the arrow named `value`, so that different spellings of
+    /// the same arrow, such as `ediamond` and `odiamond`, can be compared.
+    ///
+    pub fn canonical_name(value: &str) -> Result<String, crate::error::Error> {
+        Ok(Self::from_str(value)?.to_string())
     }
 
     pub fn a_box() -> Self {
@@ -1173,9 +1240,10 @@ impl ArrowType {
         }
     }
 
-    pub fn open(mut self) -> Self {
+    pub fn open(mut self) -> Result<Self, crate::error::Error> {
+        check_arrow_modifiers(self.shape, true, self.clip_side)?;
         self.open = true;
-        self
+        Ok(self)
     }
 
     pub fn filled(mut self) -> Self {
@@ -1183,14 +1251,16 @@ impl ArrowType {
         self
     }
 
-    pub fn clip_to_left(mut self) -> Self {
+    pub fn clip_to_left(mut self) -> Result<Self, crate::error::Error> {
+        check_arrow_modifiers(self.shape, self.open, ClipSide::Left)?;
         self.clip_side = ClipSide::Left;
-        self
+        Ok(self)
     }
 
-    pub fn clip_to_right(mut self) -> Self {
+    pub fn clip_to_right(mut self) -> Result<Self, crate::error::Error> {
+        check_arrow_modifiers(self.shape, self.open, ClipSide::Right)?;
         self.clip_side = ClipSide::Right;
-        self
+        Ok(self)
     }
 
     pub fn no_clipping(mut self) -> Self {
@@ -1198,9 +1268,25 @@ impl ArrowType {
         self
     }
 
-    pub fn set_next_shape(mut self, next_shape: ArrowType) -> Self {
+    ///
+    /// Follows this shape with `next_shape`, replacing any shapes that followed it. An arrow
+    /// has at most four shapes, and `none` may only be the last shape if it is the only one.
+    ///
+    pub fn set_next_shape(mut self, next_shape: ArrowType) -> Result<Self, crate::error::Error> {
+        if next_shape.shape_count() >= ARROW_SHAPES_MAX {
+            return Err(invalid_state(
+                ARROW_TYPE_TYPE_NAME,
+                "an arrow may have at most four shapes",
+            ));
+        }
+        if next_shape.shapes().last().map(ArrowType::shape) == Some(ArrowShape::None) {
+            return Err(invalid_state(
+                ARROW_TYPE_TYPE_NAME,
+                "none may only be the last shape of an arrow with one shape",
+            ));
+        }
         self.next = Some(Box::new(next_shape));
-        self
+        Ok(self)
     }
 
     pub fn unset_next_shape(mut self) -> Self {
@@ -1223,6 +1309,17 @@ impl ArrowType {
     pub fn next_shape(&self) -> Option<&ArrowType> {
         self.next.as_ref().map(|b| b.as_ref())
     }
+
+    ///
+    /// This shape followed by each of the shapes after it.
+    ///
+    pub fn shapes(&self) -> impl Iterator<Item = &ArrowType> {
+        std::iter::successors(Some(self), |arrow| arrow.next_shape())
+    }
+
+    pub fn shape_count(&self) -> usize {
+        self.shapes().count()
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -1340,10 +1437,12 @@ impl Color {
         Self::Hsv { h, s, v }
     }
 
+    ///
+    /// A color name from the X11 or SVG schemes, a brewer color index, or a name qualified by
+    /// its scheme such as `/blues9/3`; see [`ColorScheme`].
+    ///
     pub fn named(s: &str) -> Result<Self, crate::error::Error> {
-        if s.chars()
-            .all(|c| c.is_ascii_alphabetic() || c.is_ascii_digit())
-        {
+        if color::is_valid_color_name(s) {
             Ok(Self::Name(s.to_string()))
         } else {
             Err(invalid_value("Color", &s))
@@ -1360,11 +1459,14 @@ impl Display for ColorOrList {
             "{}",
             match self {
                 Self::Color(v) => v.to_string(),
-                Self::List(vs) => vs
-                    .iter()
-                    .map(WeightedColor::to_string)
-                    .collect::<Vec<String>>()
-                    .join(":"),
+                // A list contains ';' and ':' separators and so is always quoted.
+                Self::List(vs) => format!(
+                    "\"{}\"",
+                    vs.iter()
+                        .map(WeightedColor::to_string)
+                        .collect::<Vec<String>>()
+                        .join(":")
+                ),
             }
         )
     }
@@ -1579,6 +1681,29 @@ impl FontName {
         s.is_ascii()
     }
 
+    ///
+    /// The families named, in order; a list names more than one, each without its slant or
+    /// weight.
+    ///
+    pub fn families(&self) -> Vec<&str> {
+        if self.family.contains(',') {
+            self.family
+                .split(',')
+                .map(|f| strip_font_style(f.trim()))
+                .collect()
+        } else {
+            vec![self.family.as_str()]
+        }
+    }
+
+    pub fn font_slant(&self) -> Option<FontSlant> {
+        self.slant
+    }
+
+    pub fn font_weight(&self) -> Option<FontWeight> {
+        self.weight
+    }
+
     pub fn to_string_inner(&self, separator: Option<&str>) -> String {
         let separator = if let Some(separator) = separator {
             separator
@@ -1733,7 +1858,9 @@ impl Display for LabelLocation {
 
 impl std::fmt::Display for LabelString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self
+        if self.is_html() || self.is_quoted() {
+            write!(f, "{}", self.0)
+        } else if self
             .0
             .chars()
             .any(|c| !c.is_ascii() || c.is_whitespace() || c.is_control() || c == '"')
@@ -1766,6 +1893,33 @@ impl LabelString {
     pub fn new_unchecked(s: &str) -> Self {
         Self(s.to_string())
     }
+
+    ///
+    /// An HTML-like label; `s` is the markup without the enclosing angle brackets.
+    ///
+    pub fn html(s: &str) -> Self {
+        Self(format!("<{}>", s))
+    }
+
+    ///
+    /// A label showing the text `s`, quoted unless it is a valid unquoted identifier so that
+    /// any punctuation in it is kept.
+    ///
+    pub fn plain_text(s: &str) -> Self {
+        if crate::is_valid_id_string(s) && !s.starts_with(['"', '<']) {
+            Self(s.to_string())
+        } else {
+            Self(crate::quote_id_string(s))
+        }
+    }
+
+    pub fn is_html(&self) -> bool {
+        self.0.starts_with('<') && self.0.ends_with('>')
+    }
+
+    pub fn is_quoted(&self) -> bool {
+        self.0.len() > 1 && self.0.starts_with('"') && self.0.ends_with('"')
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -2220,30 +2374,30 @@ impl Display for RankSeparation {
 }
 
 impl RankSeparation {
-    fn new_inner(min_vertical_distances: Vec<Double>, equally: bool) -> Self {
+    fn new_inner(min_vertical_distances: Vec<Length>, equally: bool) -> Self {
         const MIN: f64 = 0.02f64;
         Self {
             min_vertical_distance: min_vertical_distances
                 .into_iter()
-                .map(|v| if v < MIN { MIN } else { v })
+                .map(|v| v.to_inches().max(MIN))
                 .collect(),
             equally,
         }
     }
 
-    pub fn new(min_vertical_distances: Vec<Double>) -> Self {
+    pub fn new(min_vertical_distances: Vec<Length>) -> Self {
         Self::new_inner(min_vertical_distances, false)
     }
 
-    pub fn new_one(min_vertical_distance: Double) -> Self {
+    pub fn new_one(min_vertical_distance: Length) -> Self {
         Self::new_inner(vec![min_vertical_distance], false)
     }
 
-    pub fn new_equally(min_vertical_distances: Vec<Double>) -> Self {
+    pub fn new_equally(min_vertical_distances: Vec<Length>) -> Self {
         Self::new_inner(min_vertical_distances, true)
     }
 
-    pub fn new_one_equally(min_vertical_distance: Double) -> Self {
+    pub fn new_one_equally(min_vertical_distance: Length) -> Self {
         Self::new_inner(vec![min_vertical_distance], true)
     }
 }
@@ -2256,8 +2410,8 @@ impl Display for RankType {
             f,
             "{}",
             match self {
-                Self::Max => "min",
-                Self::Min => "max",
+                Self::Max => "max",
+                Self::Min => "min",
                 Self::Same => "same",
                 Self::Sink => "sink",
                 Self::Source => "source",
@@ -2304,204 +2458,6 @@ double_newtype!(Scale, "Scale", is_valid_scale);
 
 // ------------------------------------------------------------------------------------------------
 
-impl Default for Shape {
-    fn default() -> Self {
-        Self::ellipse()
-    }
-}
-
-impl Display for Shape {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-impl AsRef<str> for Shape {
-    fn as_ref(&self) -> &str {
-        &self.0
-    }
-}
-
-impl Shape {
-    pub fn rectangle() -> Self {
-        Self(String::from("box"))
-    }
-    pub fn polygon() -> Self {
-        Self(String::from("polygon"))
-    }
-    pub fn ellipse() -> Self {
-        Self(String::from("ellipse"))
-    }
-    pub fn oval() -> Self {
-        Self(String::from("oval"))
-    }
-    pub fn circle() -> Self {
-        Self(String::from("circle"))
-    }
-    pub fn point() -> Self {
-        Self(String::from("point"))
-    }
-    pub fn egg() -> Self {
-        Self(String::from("egg"))
-    }
-    pub fn triangle() -> Self {
-        Self(String::from("triangle"))
-    }
-    pub fn plaintext() -> Self {
-        Self(String::from("plaintext"))
-    }
-    pub fn none() -> Self {
-        Self(String::from("plaintext"))
-    }
-    pub fn plain() -> Self {
-        Self(String::from("plain"))
-    }
-    pub fn diamond() -> Self {
-        Self(String::from("diamond"))
-    }
-    pub fn trapezium() -> Self {
-        Self(String::from("trapezium"))
-    }
-    pub fn parallelogram() -> Self {
-        Self(String::from("parallelogram"))
-    }
-    pub fn house() -> Self {
-        Self(String::from("house"))
-    }
-    pub fn pentagon() -> Self {
-        Self(String::from("pentagon"))
-    }
-    pub fn hexagon() -> Self {
-        Self(String::from("hexagon"))
-    }
-    pub fn septagon() -> Self {
-        Self(String::from("septagon"))
-    }
-    pub fn octagon() -> Self {
-        Self(String::from("octagon"))
-    }
-    pub fn double_circle() -> Self {
-        Self(String::from("doublecircle"))
-    }
-    pub fn double_octagon() -> Self {
-        Self(String::from("doubleoctagon"))
-    }
-    pub fn triple_octagon() -> Self {
-        Self(String::from("tripleoctagon"))
-    }
-    pub fn inverted_triangle() -> Self {
-        Self(String::from("invtriangle"))
-    }
-    pub fn inverted_trapezium() -> Self {
-        Self(String::from("invtrapezium"))
-    }
-    pub fn inverted_house() -> Self {
-        Self(String::from("invhouse"))
-    }
-    pub fn m_diamond() -> Self {
-        Self(String::from("Mdiamond"))
-    }
-    pub fn m_square() -> Self {
-        Self(String::from("Msquare"))
-    }
-    pub fn m_circle() -> Self {
-        Self(String::from("Mcircle"))
-    }
-    pub fn square() -> Self {
-        Self(String::from("square"))
-    }
-    pub fn star() -> Self {
-        Self(String::from("star"))
-    }
-    pub fn underline() -> Self {
-        Self(String::from("underline"))
-    }
-    pub fn cylinder() -> Self {
-        Self(String::from("cylinder"))
-    }
-    pub fn note() -> Self {
-        Self(String::from("note"))
-    }
-    pub fn tab() -> Self {
-        Self(String::from("tab"))
-    }
-    pub fn folder() -> Self {
-        Self(String::from("folder"))
-    }
-    pub fn box3d() -> Self {
-        Self(String::from("box3d"))
-    }
-    pub fn component() -> Self {
-        Self(String::from("component"))
-    }
-    pub fn promoter() -> Self {
-        Self(String::from("promoter"))
-    }
-    pub fn cds() -> Self {
-        Self(String::from("cds"))
-    }
-    pub fn terminator() -> Self {
-        Self(String::from("terminator"))
-    }
-    pub fn utr() -> Self {
-        Self(String::from("utr"))
-    }
-    pub fn primersite() -> Self {
-        Self(String::from("primersite"))
-    }
-    pub fn restrictionsite() -> Self {
-        Self(String::from("restrictionsite"))
-    }
-    pub fn fivepoverhang() -> Self {
-        Self(String::from("fivepoverhang"))
-    }
-    pub fn threepoverhang() -> Self {
-        Self(String::from("threepoverhang"))
-    }
-    pub fn noverhang() -> Self {
-        Self(String::from("noverhang"))
-    }
-    pub fn assembly() -> Self {
-        Self(String::from("assembly"))
-    }
-    pub fn signature() -> Self {
-        Self(String::from("signature"))
-    }
-    pub fn insulator() -> Self {
-        Self(String::from("insulator"))
-    }
-    pub fn ribosite() -> Self {
-        Self(String::from("ribosite"))
-    }
-    pub fn rnastab() -> Self {
-        Self(String::from("rnastab"))
-    }
-    pub fn proteasesite() -> Self {
-        Self(String::from("proteasesite"))
-    }
-    pub fn proteinstab() -> Self {
-        Self(String::from("proteinstab"))
-    }
-    pub fn rpromoter() -> Self {
-        Self(String::from("rpromoter"))
-    }
-    pub fn rarrow() -> Self {
-        Self(String::from("rarrow"))
-    }
-    pub fn larrow() -> Self {
-        Self(String::from("larrow"))
-    }
-    pub fn lpromoter() -> Self {
-        Self(String::from("lpromoter"))
-    }
-
-    pub fn record() -> Self {
-        Self(String::from("record"))
-    }
-}
-
-// ------------------------------------------------------------------------------------------------
-
 impl Default for Smoothing {
     fn default() -> Self {
         Self::None
@@ -2698,6 +2654,36 @@ impl Display for ViewPortCenter {
 
 // ------------------------------------------------------------------------------------------------
 
+impl From<Color> for WeightedColor {
+    fn from(v: Color) -> Self {
+        Self::new(v)
+    }
+}
+
+impl WeightedColor {
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            weight: None,
+        }
+    }
+
+    pub fn weighted(color: Color, weight: Scale) -> Self {
+        Self {
+            color,
+            weight: Some(weight),
+        }
+    }
+
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+
+    pub fn weight(&self) -> Option<&Scale> {
+        self.weight.as_ref()
+    }
+}
+
 impl Display for WeightedColor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(weight) = &self.weight {
@@ -2712,10 +2698,57 @@ impl Display for WeightedColor {
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+const ARROW_TYPE_TYPE_NAME: &str = "ArrowType";
+const ARROW_SHAPES_MAX: usize = 4;
+
+const ARROW_SHAPE_NAMES: &[(&str, ArrowShape)] = &[
+    ("box", ArrowShape::Box),
+    ("crow", ArrowShape::Crow),
+    ("curve", ArrowShape::Curve),
+    ("diamond", ArrowShape::Diamond),
+    ("dot", ArrowShape::Dot),
+    ("icurve", ArrowShape::InverseCurve),
+    ("inv", ArrowShape::Inverse),
+    ("none", ArrowShape::None),
+    ("normal", ArrowShape::Normal),
+    ("tee", ArrowShape::Tee),
+    ("vee", ArrowShape::Vee),
+];
+
 const DOUBLE_ZERO: Double = 0.0f64;
 const DOUBLE_ONE: Double = 1.0f64;
 const DOUBLE_DEGREE_MAX: Double = 360.0f64;
 
+fn check_arrow_modifiers(
+    shape: ArrowShape,
+    open: bool,
+    clip_side: ClipSide,
+) -> Result<(), crate::error::Error> {
+    match shape {
+        ArrowShape::None if open || clip_side != ClipSide::None => Err(invalid_state(
+            ARROW_TYPE_TYPE_NAME,
+            "the none arrow shape takes no modifiers",
+        )),
+        ArrowShape::Dot if clip_side != ClipSide::None => Err(invalid_state(
+            ARROW_TYPE_TYPE_NAME,
+            "the dot arrow shape cannot be clipped to one side",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn strip_font_style(family: &str) -> &str {
+    const STYLES: &[&str] = &[
+        "light", "medium", "demibold", "bold", "black", "italic", "oblique", "roman",
+    ];
+    STYLES.iter().fold(family, |family, style| {
+        family
+            .strip_suffix(style)
+            .and_then(|rest| rest.strip_suffix(' '))
+            .unwrap_or(family)
+    })
+}
+
 #[inline]
 fn is_valid_degrees(value: Double) -> bool {
     value >= DOUBLE_ZERO && value <= DOUBLE_DEGREE_MAX
@@ -2750,12 +2783,24 @@ fn is_valid_label_string(_s: &str) -> bool {
 // Modules
 // ------------------------------------------------------------------------------------------------
 
+pub mod color;
+pub use color::ColorScheme;
+
 pub mod cluster;
 pub use cluster::ClusterAttributes;
 
+pub mod fill;
+pub use fill::{Fill, FillKind};
+
 pub mod graph;
 pub use graph::GraphAttributes;
 
+pub mod length;
+pub use length::{Length, LengthUnit, Margin, Size};
+
+pub mod shape;
+pub use shape::{PolygonShape, Shape};
+
 pub mod node;
 pub use node::NodeAttributes;
 
@@ -8,9 +8,9 @@ More detailed description, with
  */
 
 use crate::attributes::{
-    Attribute, Attributes, Color, ColorOrList, Degrees, Double, EscapedString, FontName,
-    ImagePosition, Int, LabelLocation, LabelString, LayerRange, NodeStyles, Ordering, Point,
-    PointOrBoth, Position, Positive, PositiveNonZero, Rectangle, Shape, Style, Unsigned,
+    Attribute, Attributes, Color, ColorOrList, ColorScheme, EscapedString, Fill, FontName,
+    ImagePosition, Int, LabelLocation, LabelString, LayerRange, Length, LengthUnit, Margin,
+    NodeStyles, Ordering, Point, Position, PositiveNonZero, Rectangle, Shape, Style, Unsigned,
 };
 use std::fmt::Display;
 
@@ -43,17 +43,16 @@ impl NodeAttributes {
     attribute_setter!(area, Area, PositiveNonZero);
     attribute_setter!(class, Class, Vec<String>);
     attribute_setter!(color, Color, ColorOrList);
-    attribute_setter!(color_scheme, ColorScheme, String);
+    attribute_setter!(color_scheme, ColorScheme);
     attribute_setter!(comment, Comment, String);
-    attribute_setter!(distortion, Distortion, Double);
     attribute_setter!(fill_color, FillColor, ColorOrList);
     boolean_attribute_setter!(has_fixed_size, FixedSize);
     attribute_setter!(font_color, FontColor, Color);
     attribute_setter!(font_name, FontName);
-    attribute_setter!(font_size, FontSize, Double);
+    length_attribute_setter!(font_size, FontSize, to_points);
     attribute_setter!(gradient_angle, GradientAngle, Int);
     attribute_setter!(group, Group, String);
-    attribute_setter!(height, Height, Positive);
+    length_attribute_setter!(height, Height, positive_inches);
     attribute_setter!(id, Id, EscapedString);
     attribute_setter!(image, Image, String);
     attribute_setter!(image_position, ImagePosition);
@@ -61,31 +60,53 @@ impl NodeAttributes {
     attribute_setter!(label, Label, LabelString);
     attribute_setter!(label_location, LabelLocation);
     attribute_setter!(layer, Layer, LayerRange);
-    attribute_setter!(margin, Margin, PointOrBoth);
     boolean_attribute_setter!(no_justification, NoJustification);
     attribute_setter!(ordering, Ordering);
-    attribute_setter!(orientation, Orientation, Degrees);
-    attribute_setter!(pen_width, PenWidth, Positive);
+    length_attribute_setter!(pen_width, PenWidth, positive_points);
     attribute_setter!(peripheries, Peripheries, Unsigned);
     boolean_attribute_setter!(pin_position, Pin);
     attribute_setter!(position, Position);
     attribute_setter!(record_rectangles, RecordRectangles, Rectangle);
-    boolean_attribute_setter!(force_regular, Regular);
     boolean_attribute_setter!(is_root_node, Root);
     attribute_setter!(sample_points, SamplePoints, Unsigned);
-    attribute_setter!(shape, Shape);
     attribute_setter!(show_boxes, ShowBoxes, Unsigned);
-    attribute_setter!(sides, Sides, Unsigned);
-    attribute_setter!(skew, Skew, Double);
     attribute_setter!(sort_value, SortValue, Unsigned);
     style_attribute_setter!(Node, NodeStyles);
     attribute_setter!(target, Target, EscapedString);
     attribute_setter!(tooltip, Tooltip, EscapedString);
     attribute_setter!(url, Url, EscapedString);
     attribute_setter!(vertices, Vertices, Vec<Point>);
-    attribute_setter!(width, Width, Positive);
+    length_attribute_setter!(width, Width, positive_inches);
     attribute_setter!(external_label, ExternalLabel, LabelString);
     attribute_setter!(external_label_position, ExternalLabelPosition, Point);
+
+    ///
+    /// Sets the space around the node's label, converted to inches.
+    ///
+    pub fn margin(self, margin: Margin) -> Self {
+        self.push(Attribute::Margin(margin.in_unit(LengthUnit::Inches)))
+    }
+
+    ///
+    /// Sets the fill color from `fill`, replacing any earlier fill, and the matching fill style
+    /// alongside any other styles already set.
+    ///
+    pub fn fill(self, fill: Fill) -> Result<Self, crate::error::Error> {
+        let mut self_mut = self;
+        let style = Style::Node(fill.node_style());
+        fill.apply_to(&mut self_mut.0, style)?;
+        Ok(self_mut)
+    }
+
+    ///
+    /// Sets the shape, replacing any earlier shape along with its polygon parameters or shape
+    /// file.
+    ///
+    pub fn shape(self, shape: Shape) -> Self {
+        let mut self_mut = self;
+        shape.apply_to(&mut self_mut.0);
+        self_mut
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -0,0 +1,439 @@
+/*!
+Node shapes, including the parameters of user-defined polygons.
+
+`Shape` covers every node shape Graphviz provides. The polygon parameters `sides`, `skew`,
+`distortion`, `regular` and `orientation` only mean something for `Shape::Polygon`, so they
+are held by `PolygonShape` rather than set independently; `NodeAttributes::shape` writes them
+out alongside the shape. In the same way `Shape::Epsf` and `Shape::Custom` carry the file used
+as their `shapefile`.
+
+# Example
+
+```rust
+use simple_dot::attributes::{NodeAttributes, PolygonShape, Shape};
+
+let shape = PolygonShape::new(5).unwrap().skew(0.4).unwrap().regular();
+assert_eq!(
+    NodeAttributes::default().shape(shape.into()).to_string(),
+    "[ shape = polygon; sides = 5; skew = 0.4; regular = true ]"
+);
+assert!(PolygonShape::new(2).is_err());
+assert!("hexagon".parse::<Shape>().is_ok());
+```
+
+ */
+
+use crate::attributes::{Attribute, Degrees, Double, Unsigned};
+use crate::error::{invalid_value, Error};
+use std::{fmt::Display, str::FromStr};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Shape {
+    Box,
+    Polygon(PolygonShape),
+    #[default]
+    Ellipse,
+    Oval,
+    Circle,
+    Point,
+    Egg,
+    Triangle,
+    PlainText,
+    Plain,
+    Diamond,
+    Trapezium,
+    Parallelogram,
+    House,
+    Pentagon,
+    Hexagon,
+    Septagon,
+    Octagon,
+    DoubleCircle,
+    DoubleOctagon,
+    TripleOctagon,
+    InvertedTriangle,
+    InvertedTrapezium,
+    InvertedHouse,
+    MDiamond,
+    MSquare,
+    MCircle,
+    Square,
+    Star,
+    None,
+    Underline,
+    Cylinder,
+    Note,
+    Tab,
+    Folder,
+    Box3d,
+    Component,
+    Promoter,
+    Cds,
+    Terminator,
+    Utr,
+    PrimerSite,
+    RestrictionSite,
+    FivePOverhang,
+    ThreePOverhang,
+    NOverhang,
+    Assembly,
+    Signature,
+    Insulator,
+    RiboSite,
+    RnaStab,
+    ProteaseSite,
+    ProteinStab,
+    RPromoter,
+    RArrow,
+    LArrow,
+    LPromoter,
+    Record,
+    MRecord,
+    /// An encapsulated PostScript file, only used by the PostScript renderer.
+    Epsf(String),
+    /// An image file drawn as the node, for renderers that support it.
+    Custom(String),
+}
+
+///
+/// The parameters of `Shape::Polygon`; values are checked as they are set so an invalid polygon
+/// cannot be constructed.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolygonShape {
+    sides: Unsigned,
+    skew: Double,
+    distortion: Double,
+    regular: bool,
+    orientation: Option<Degrees>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl AsRef<str> for Shape {
+    fn as_ref(&self) -> &str {
+        self.name()
+    }
+}
+
+impl From<PolygonShape> for Shape {
+    fn from(v: PolygonShape) -> Self {
+        Self::Polygon(v)
+    }
+}
+
+impl FromStr for Shape {
+    type Err = Error;
+
+    ///
+    /// Parses a shape name; `epsf` and `custom` are rejected as they need a file and
+    /// `polygon` is given the default parameters.
+    ///
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "box" | "rect" | "rectangle" => Self::Box,
+            "polygon" => Self::Polygon(Default::default()),
+            "ellipse" => Self::Ellipse,
+            "oval" => Self::Oval,
+            "circle" => Self::Circle,
+            "point" => Self::Point,
+            "egg" => Self::Egg,
+            "triangle" => Self::Triangle,
+            "plaintext" => Self::PlainText,
+            "plain" => Self::Plain,
+            "diamond" => Self::Diamond,
+            "trapezium" => Self::Trapezium,
+            "parallelogram" => Self::Parallelogram,
+            "house" => Self::House,
+            "pentagon" => Self::Pentagon,
+            "hexagon" => Self::Hexagon,
+            "septagon" => Self::Septagon,
+            "octagon" => Self::Octagon,
+            "doublecircle" => Self::DoubleCircle,
+            "doubleoctagon" => Self::DoubleOctagon,
+            "tripleoctagon" => Self::TripleOctagon,
+            "invtriangle" => Self::InvertedTriangle,
+            "invtrapezium" => Self::InvertedTrapezium,
+            "invhouse" => Self::InvertedHouse,
+            "Mdiamond" => Self::MDiamond,
+            "Msquare" => Self::MSquare,
+            "Mcircle" => Self::MCircle,
+            "square" => Self::Square,
+            "star" => Self::Star,
+            "none" => Self::None,
+            "underline" => Self::Underline,
+            "cylinder" => Self::Cylinder,
+            "note" => Self::Note,
+            "tab" => Self::Tab,
+            "folder" => Self::Folder,
+            "box3d" => Self::Box3d,
+            "component" => Self::Component,
+            "promoter" => Self::Promoter,
+            "cds" => Self::Cds,
+            "terminator" => Self::Terminator,
+            "utr" => Self::Utr,
+            "primersite" => Self::PrimerSite,
+            "restrictionsite" => Self::RestrictionSite,
+            "fivepoverhang" => Self::FivePOverhang,
+            "threepoverhang" => Self::ThreePOverhang,
+            "noverhang" => Self::NOverhang,
+            "assembly" => Self::Assembly,
+            "signature" => Self::Signature,
+            "insulator" => Self::Insulator,
+            "ribosite" => Self::RiboSite,
+            "rnastab" => Self::RnaStab,
+            "proteasesite" => Self::ProteaseSite,
+            "proteinstab" => Self::ProteinStab,
+            "rpromoter" => Self::RPromoter,
+            "rarrow" => Self::RArrow,
+            "larrow" => Self::LArrow,
+            "lpromoter" => Self::LPromoter,
+            "record" => Self::Record,
+            "Mrecord" => Self::MRecord,
+            _ => return Err(invalid_value(SHAPE_TYPE_NAME, &value)),
+        })
+    }
+}
+
+impl Shape {
+    pub fn epsf<S>(file: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Epsf(file.into())
+    }
+
+    pub fn custom<S>(file: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Custom(file.into())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Box => "box",
+            Self::Polygon(_) => "polygon",
+            Self::Ellipse => "ellipse",
+            Self::Oval => "oval",
+            Self::Circle => "circle",
+            Self::Point => "point",
+            Self::Egg => "egg",
+            Self::Triangle => "triangle",
+            Self::PlainText => "plaintext",
+            Self::Plain => "plain",
+            Self::Diamond => "diamond",
+            Self::Trapezium => "trapezium",
+            Self::Parallelogram => "parallelogram",
+            Self::House => "house",
+            Self::Pentagon => "pentagon",
+            Self::Hexagon => "hexagon",
+            Self::Septagon => "septagon",
+            Self::Octagon => "octagon",
+            Self::DoubleCircle => "doublecircle",
+            Self::DoubleOctagon => "doubleoctagon",
+            Self::TripleOctagon => "tripleoctagon",
+            Self::InvertedTriangle => "invtriangle",
+            Self::InvertedTrapezium => "invtrapezium",
+            Self::InvertedHouse => "invhouse",
+            Self::MDiamond => "Mdiamond",
+            Self::MSquare => "Msquare",
+            Self::MCircle => "Mcircle",
+            Self::Square => "square",
+            Self::Star => "star",
+            Self::None => "none",
+            Self::Underline => "underline",
+            Self::Cylinder => "cylinder",
+            Self::Note => "note",
+            Self::Tab => "tab",
+            Self::Folder => "folder",
+            Self::Box3d => "box3d",
+            Self::Component => "component",
+            Self::Promoter => "promoter",
+            Self::Cds => "cds",
+            Self::Terminator => "terminator",
+            Self::Utr => "utr",
+            Self::PrimerSite => "primersite",
+            Self::RestrictionSite => "restrictionsite",
+            Self::FivePOverhang => "fivepoverhang",
+            Self::ThreePOverhang => "threepoverhang",
+            Self::NOverhang => "noverhang",
+            Self::Assembly => "assembly",
+            Self::Signature => "signature",
+            Self::Insulator => "insulator",
+            Self::RiboSite => "ribosite",
+            Self::RnaStab => "rnastab",
+            Self::ProteaseSite => "proteasesite",
+            Self::ProteinStab => "proteinstab",
+            Self::RPromoter => "rpromoter",
+            Self::RArrow => "rarrow",
+            Self::LArrow => "larrow",
+            Self::LPromoter => "lpromoter",
+            Self::Record => "record",
+            Self::MRecord => "Mrecord",
+            Self::Epsf(_) => "epsf",
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    ///
+    /// Record shapes take their fields from the node's label.
+    ///
+    pub fn is_record(&self) -> bool {
+        matches!(self, Self::Record | Self::MRecord)
+    }
+
+    ///
+    /// Replaces the shape and any shape parameters in `attributes` with this shape and its own
+    /// parameters.
+    ///
+    pub(crate) fn apply_to(&self, attributes: &mut Vec<Attribute>) {
+        attributes.retain(|a| {
+            !matches!(
+                a,
+                Attribute::Shape(_)
+                    | Attribute::ShapeFile(_)
+                    | Attribute::Sides(_)
+                    | Attribute::Skew(_)
+                    | Attribute::Distortion(_)
+                    | Attribute::Regular(_)
+                    | Attribute::Orientation(_)
+            )
+        });
+        attributes.push(Attribute::Shape(self.clone()));
+        match self {
+            Self::Polygon(polygon) => attributes.extend(polygon.attributes()),
+            Self::Epsf(file) | Self::Custom(file) => {
+                attributes.push(Attribute::ShapeFile(file.clone()))
+            }
+            _ => {}
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for PolygonShape {
+    fn default() -> Self {
+        Self {
+            sides: DEFAULT_SIDES,
+            skew: 0.0,
+            distortion: 0.0,
+            regular: false,
+            orientation: None,
+        }
+    }
+}
+
+impl PolygonShape {
+    ///
+    /// A polygon with `sides` sides, which must be between 3 and 100.
+    ///
+    pub fn new(sides: Unsigned) -> Result<Self, Error> {
+        if (MIN_SIDES..=MAX_SIDES).contains(&sides) {
+            Ok(Self {
+                sides,
+                ..Default::default()
+            })
+        } else {
+            Err(invalid_value("Sides", &sides))
+        }
+    }
+
+    ///
+    /// Skews the top of the polygon to the right for positive values and to the left for
+    /// negative ones; the value must not be less than -100.
+    ///
+    pub fn skew(self, skew: Double) -> Result<Self, Error> {
+        if is_valid_factor(skew) {
+            let mut self_mut = self;
+            self_mut.skew = skew;
+            Ok(self_mut)
+        } else {
+            Err(invalid_value("Skew", &skew))
+        }
+    }
+
+    ///
+    /// Widens the top of the polygon relative to the bottom for positive values, and narrows it
+    /// for negative ones; the value must not be less than -100.
+    ///
+    pub fn distortion(self, distortion: Double) -> Result<Self, Error> {
+        if is_valid_factor(distortion) {
+            let mut self_mut = self;
+            self_mut.distortion = distortion;
+            Ok(self_mut)
+        } else {
+            Err(invalid_value("Distortion", &distortion))
+        }
+    }
+
+    ///
+    /// Forces the polygon to be regular, with equal width and height.
+    ///
+    pub fn regular(self) -> Self {
+        let mut self_mut = self;
+        self_mut.regular = true;
+        self_mut
+    }
+
+    pub fn orientation(self, orientation: Degrees) -> Self {
+        let mut self_mut = self;
+        self_mut.orientation = Some(orientation);
+        self_mut
+    }
+
+    pub fn sides(&self) -> Unsigned {
+        self.sides
+    }
+
+    pub fn is_regular(&self) -> bool {
+        self.regular
+    }
+
+    fn attributes(&self) -> Vec<Attribute> {
+        let mut attributes = vec![Attribute::Sides(self.sides)];
+        if self.skew != 0.0 {
+            attributes.push(Attribute::Skew(self.skew));
+        }
+        if self.distortion != 0.0 {
+            attributes.push(Attribute::Distortion(self.distortion));
+        }
+        if self.regular {
+            attributes.push(Attribute::Regular(true));
+        }
+        if let Some(orientation) = &self.orientation {
+            attributes.push(Attribute::Orientation(orientation.clone()));
+        }
+        attributes
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const SHAPE_TYPE_NAME: &str = "Shape";
+
+const DEFAULT_SIDES: Unsigned = 4;
+const MIN_SIDES: Unsigned = 3;
+const MAX_SIDES: Unsigned = 100;
+
+const MIN_FACTOR: Double = -100.0;
+
+#[inline]
+fn is_valid_factor(value: Double) -> bool {
+    value.is_finite() && value >= MIN_FACTOR
+}
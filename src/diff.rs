@@ -0,0 +1,793 @@
+/*!
+Compares two graphs and reports the nodes, edges, sub-graphs and attributes that have changed.
+
+Nodes and sub-graphs are matched by `Identifier` regardless of the scope in which they are
+declared, so that a node moved from one cluster to another is reported as changed rather than
+as removed and added. Anonymous sub-graphs, such as rank constraints, have generated identifiers
+and so are matched by their position among the anonymous sub-graphs of the same parent. Edges are
+matched by their head and tail, in either order if either graph is undirected; where a graph
+contains parallel edges they are matched in declaration order. Attributes are matched by name.
+
+The function `diff_graph` combines both graphs into a single graph, based on the later graph,
+with additions, removals and changes highlighted according to a `DiffStyle`.
+
+# Example
+
+ */
+
+use crate::attributes::{
+    Attribute, ClusterAttributes, Color, ColorOrList, EdgeAttributes, EdgeStyles, NodeAttributes,
+    NodeStyles, Style, Styled,
+};
+use crate::graph::{Cluster, Graph, InScope, SubGraphKind};
+use crate::{Edge, Identified, Identifier, Node, RootGraph};
+use std::collections::HashMap;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difference {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeChange {
+    Added(Attribute),
+    Removed(Attribute),
+    Changed { from: Attribute, to: Attribute },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeDiff {
+    id: Identifier,
+    difference: Difference,
+    from_scope: Option<Identifier>,
+    to_scope: Option<Identifier>,
+    attributes: Vec<AttributeChange>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgeDiff {
+    head: Identifier,
+    tail: Identifier,
+    difference: Difference,
+    attributes: Vec<AttributeChange>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubGraphDiff {
+    id: Identifier,
+    cluster: bool,
+    difference: Difference,
+    from_scope: Option<Identifier>,
+    to_scope: Option<Identifier>,
+    attributes: Vec<AttributeChange>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GraphDiff {
+    attributes: Vec<AttributeChange>,
+    nodes: Vec<NodeDiff>,
+    edges: Vec<EdgeDiff>,
+    sub_graphs: Vec<SubGraphDiff>,
+}
+
+///
+/// The overlays applied by `diff_graph`; the `color` attribute of each added, removed or changed
+/// node, edge and cluster is replaced, and removed nodes and edges are also given a style.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffStyle {
+    added: Color,
+    removed: Color,
+    changed: Color,
+    removed_node_styles: Vec<NodeStyles>,
+    removed_edge_styles: Vec<EdgeStyles>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the differences between the graph `from` and the later graph `to`. Only nodes that
+/// are declared are compared; nodes that only appear as the end of an edge are reported through
+/// the edge.
+///
+pub fn diff(from: &RootGraph, to: &RootGraph) -> GraphDiff {
+    let from_keys = SubGraphKeys::new(from);
+    let to_keys = SubGraphKeys::new(to);
+    let scope_id = |scope: &Option<Identifier>| {
+        scope.as_ref().map(|key| {
+            to_keys
+                .id(key)
+                .or_else(|| from_keys.id(key))
+                .cloned()
+                .unwrap_or_else(|| key.clone())
+        })
+    };
+
+    let from_nodes = scoped(from.all_nodes(), &from_keys, |node| node.id().clone());
+    let to_nodes = scoped(to.all_nodes(), &to_keys, |node| node.id().clone());
+    let mut nodes: Vec<NodeDiff> = Vec::default();
+    for (id, (from_scope, from_node)) in from_nodes.iter() {
+        match to_nodes.get(id) {
+            None => nodes.push(NodeDiff {
+                id: id.clone(),
+                difference: Difference::Removed,
+                from_scope: scope_id(from_scope),
+                to_scope: None,
+                attributes: Vec::default(),
+            }),
+            Some((to_scope, to_node)) => {
+                let attributes = attribute_changes(
+                    from_node.attributes().map(AsRef::as_ref),
+                    to_node.attributes().map(AsRef::as_ref),
+                );
+                if from_scope != to_scope || !attributes.is_empty() {
+                    nodes.push(NodeDiff {
+                        id: id.clone(),
+                        difference: Difference::Changed,
+                        from_scope: scope_id(from_scope),
+                        to_scope: scope_id(to_scope),
+                        attributes,
+                    });
+                }
+            }
+        }
+    }
+    for (id, (to_scope, _)) in to_nodes.iter() {
+        if !from_nodes.contains_key(id) {
+            nodes.push(NodeDiff {
+                id: id.clone(),
+                difference: Difference::Added,
+                from_scope: None,
+                to_scope: scope_id(to_scope),
+                attributes: Vec::default(),
+            });
+        }
+    }
+
+    let from_edges: Vec<&Edge> = from.all_edges().map(|e| e.item()).collect();
+    let to_edges: Vec<&Edge> = to.all_edges().map(|e| e.item()).collect();
+    let edges = match_edges(&from_edges, &to_edges, is_directed(from, to))
+        .into_iter()
+        .filter_map(|pair| match pair {
+            (Some(from), None) => Some(EdgeDiff::new(from_edges[from], Difference::Removed)),
+            (None, Some(to)) => Some(EdgeDiff::new(to_edges[to], Difference::Added)),
+            (Some(from), Some(to)) => {
+                let attributes = attribute_changes(
+                    from_edges[from].attributes().map(AsRef::as_ref),
+                    to_edges[to].attributes().map(AsRef::as_ref),
+                );
+                (!attributes.is_empty()).then(|| {
+                    let mut edge = EdgeDiff::new(to_edges[to], Difference::Changed);
+                    edge.attributes = attributes;
+                    edge
+                })
+            }
+            (None, None) => None,
+        })
+        .collect();
+
+    let from_sub_graphs = scoped(from.all_sub_graphs(), &from_keys, |sub_graph| {
+        from_keys.key(sub_graph.id())
+    });
+    let to_sub_graphs = scoped(to.all_sub_graphs(), &to_keys, |sub_graph| {
+        to_keys.key(sub_graph.id())
+    });
+    let mut sub_graphs: Vec<SubGraphDiff> = Vec::default();
+    for (key, (from_scope, from_sub_graph)) in from_sub_graphs.iter() {
+        match to_sub_graphs.get(key) {
+            None => sub_graphs.push(SubGraphDiff {
+                id: from_sub_graph.id().clone(),
+                cluster: from_sub_graph.is_cluster_graph(),
+                difference: Difference::Removed,
+                from_scope: scope_id(from_scope),
+                to_scope: None,
+                attributes: Vec::default(),
+            }),
+            Some((to_scope, to_sub_graph)) => {
+                let attributes = attribute_changes(
+                    sub_graph_attributes(from_sub_graph),
+                    sub_graph_attributes(to_sub_graph),
+                );
+                if from_scope != to_scope || !attributes.is_empty() {
+                    sub_graphs.push(SubGraphDiff {
+                        id: to_sub_graph.id().clone(),
+                        cluster: to_sub_graph.is_cluster_graph(),
+                        difference: Difference::Changed,
+                        from_scope: scope_id(from_scope),
+                        to_scope: scope_id(to_scope),
+                        attributes,
+                    });
+                }
+            }
+        }
+    }
+    for (key, (to_scope, to_sub_graph)) in to_sub_graphs.iter() {
+        if !from_sub_graphs.contains_key(key) {
+            sub_graphs.push(SubGraphDiff {
+                id: to_sub_graph.id().clone(),
+                cluster: to_sub_graph.is_cluster_graph(),
+                difference: Difference::Added,
+                from_scope: None,
+                to_scope: scope_id(to_scope),
+                attributes: Vec::default(),
+            });
+        }
+    }
+
+    GraphDiff {
+        attributes: attribute_changes(
+            from.attributes().map(AsRef::as_ref),
+            to.attributes().map(AsRef::as_ref),
+        ),
+        nodes,
+        edges,
+        sub_graphs,
+    }
+}
+
+///
+/// Returns a copy of `to` with the differences from `from` highlighted. Removed clusters, nodes
+/// and edges are added back, into their original scope where it still exists and into the root
+/// graph otherwise; removed sub-graphs that are not clusters have no appearance of their own and
+/// so are not added back.
+///
+pub fn diff_graph(from: &RootGraph, to: &RootGraph, style: &DiffStyle) -> RootGraph {
+    let differences = diff(from, to);
+    let mut graph = to.clone();
+
+    let from_edges: Vec<&Edge> = from.all_edges().map(|e| e.item()).collect();
+    let to_edges: Vec<&Edge> = to.all_edges().map(|e| e.item()).collect();
+    let matched = match_edges(&from_edges, &to_edges, is_directed(from, to));
+    let mut overlays: Vec<Option<&Color>> = vec![None; to_edges.len()];
+    for pair in matched.iter() {
+        match pair {
+            (None, Some(to)) => overlays[*to] = Some(&style.added),
+            (Some(from), Some(to))
+                if !attribute_changes(
+                    from_edges[*from].attributes().map(AsRef::as_ref),
+                    to_edges[*to].attributes().map(AsRef::as_ref),
+                )
+                .is_empty() =>
+            {
+                overlays[*to] = Some(&style.changed)
+            }
+            _ => {}
+        }
+    }
+    let mut overlays = overlays.into_iter();
+    graph.for_each_edge_mut(|edge| {
+        if let Some(Some(color)) = overlays.next() {
+            set_edge_overlay(edge, color, &[]);
+        }
+    });
+
+    for sub_graph in differences.sub_graphs.iter() {
+        match sub_graph.difference {
+            Difference::Removed => {
+                if let Some(SubGraphKind::Cluster(cluster)) = from.find_sub_graph(&sub_graph.id) {
+                    let mut shell = Cluster::new(sub_graph.id.clone());
+                    if let Some(attributes) = cluster.attributes() {
+                        shell = shell.set_attributes(attributes.clone());
+                    }
+                    insert_in_scope(
+                        &mut graph,
+                        sub_graph.from_scope.as_ref(),
+                        SubGraphKind::from(set_cluster_overlay(shell, &style.removed)),
+                    );
+                }
+            }
+            Difference::Added | Difference::Changed => {
+                let color = if sub_graph.difference == Difference::Added {
+                    &style.added
+                } else {
+                    &style.changed
+                };
+                if let Some(mut found) = graph.sub_graph_mut(&sub_graph.id) {
+                    if let SubGraphKind::Cluster(cluster) = &mut *found {
+                        let taken = std::mem::take(cluster);
+                        *cluster = set_cluster_overlay(taken, color);
+                    }
+                }
+            }
+        }
+    }
+
+    for node in differences.nodes.iter() {
+        match node.difference {
+            Difference::Removed => {
+                let mut removed = from
+                    .find_node(&node.id)
+                    .cloned()
+                    .unwrap_or_else(|| Node::new(node.id.clone()));
+                set_node_overlay(&mut removed, &style.removed, &style.removed_node_styles);
+                match node.from_scope.as_ref() {
+                    Some(scope) if graph.find_sub_graph(scope).is_some() => {
+                        graph.sub_graph_mut(scope).unwrap().insert_node(removed)
+                    }
+                    _ => graph.insert_node(removed),
+                }
+            }
+            Difference::Added | Difference::Changed => {
+                let color = if node.difference == Difference::Added {
+                    &style.added
+                } else {
+                    &style.changed
+                };
+                if let Some(node) = graph.node_mut(&node.id) {
+                    set_node_overlay(node, color, &[]);
+                }
+            }
+        }
+    }
+
+    for pair in matched {
+        if let (Some(from), None) = pair {
+            let mut removed = from_edges[from].clone();
+            set_edge_overlay(&mut removed, &style.removed, &style.removed_edge_styles);
+            graph.insert_edge(removed);
+        }
+    }
+
+    graph
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Items indexed by key, in declaration order, with the key of the sub-graph they are declared
+/// in; the root graph is recorded as `None` so that graphs with different identifiers may be
+/// compared.
+///
+struct Scoped<'a, T> {
+    order: Vec<Identifier>,
+    items: HashMap<Identifier, (Option<Identifier>, &'a T)>,
+}
+
+///
+/// The keys used to match the sub-graphs of two graphs. A named sub-graph is keyed by its
+/// identifier; an anonymous sub-graph is keyed by the key of its parent and its position among
+/// the anonymous sub-graphs of that parent, as `parent{n}`, which no valid identifier can
+/// collide with.
+///
+struct SubGraphKeys<'a> {
+    keys: HashMap<&'a Identifier, Identifier>,
+    ids: HashMap<Identifier, &'a Identifier>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl NodeDiff {
+    pub fn id(&self) -> &Identifier {
+        &self.id
+    }
+
+    pub fn difference(&self) -> Difference {
+        self.difference
+    }
+
+    ///
+    /// The sub-graph or cluster the node was declared in, `None` for the root graph.
+    ///
+    pub fn from_scope(&self) -> Option<&Identifier> {
+        self.from_scope.as_ref()
+    }
+
+    ///
+    /// The sub-graph or cluster the node is now declared in, `None` for the root graph.
+    ///
+    pub fn to_scope(&self) -> Option<&Identifier> {
+        self.to_scope.as_ref()
+    }
+
+    pub fn is_moved(&self) -> bool {
+        self.difference == Difference::Changed && self.from_scope != self.to_scope
+    }
+
+    pub fn attributes(&self) -> &[AttributeChange] {
+        &self.attributes
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl EdgeDiff {
+    fn new(edge: &Edge, difference: Difference) -> Self {
+        Self {
+            head: edge.head().clone(),
+            tail: edge.tail().clone(),
+            difference,
+            attributes: Vec::default(),
+        }
+    }
+
+    pub fn head(&self) -> &Identifier {
+        &self.head
+    }
+
+    pub fn tail(&self) -> &Identifier {
+        &self.tail
+    }
+
+    pub fn difference(&self) -> Difference {
+        self.difference
+    }
+
+    pub fn attributes(&self) -> &[AttributeChange] {
+        &self.attributes
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl SubGraphDiff {
+    pub fn id(&self) -> &Identifier {
+        &self.id
+    }
+
+    pub fn is_cluster_graph(&self) -> bool {
+        self.cluster
+    }
+
+    pub fn difference(&self) -> Difference {
+        self.difference
+    }
+
+    pub fn from_scope(&self) -> Option<&Identifier> {
+        self.from_scope.as_ref()
+    }
+
+    pub fn to_scope(&self) -> Option<&Identifier> {
+        self.to_scope.as_ref()
+    }
+
+    pub fn is_moved(&self) -> bool {
+        self.difference == Difference::Changed && self.from_scope != self.to_scope
+    }
+
+    pub fn attributes(&self) -> &[AttributeChange] {
+        &self.attributes
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl GraphDiff {
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+            && self.nodes.is_empty()
+            && self.edges.is_empty()
+            && self.sub_graphs.is_empty()
+    }
+
+    ///
+    /// Changes to the attributes of the root graph itself.
+    ///
+    pub fn attributes(&self) -> &[AttributeChange] {
+        &self.attributes
+    }
+
+    pub fn nodes(&self) -> &[NodeDiff] {
+        &self.nodes
+    }
+
+    pub fn edges(&self) -> &[EdgeDiff] {
+        &self.edges
+    }
+
+    pub fn sub_graphs(&self) -> &[SubGraphDiff] {
+        &self.sub_graphs
+    }
+
+    pub fn clusters(&self) -> impl Iterator<Item = &SubGraphDiff> + '_ {
+        self.sub_graphs.iter().filter(|s| s.cluster)
+    }
+
+    pub fn added_nodes(&self) -> impl Iterator<Item = &Identifier> + '_ {
+        self.nodes_with(Difference::Added)
+    }
+
+    pub fn removed_nodes(&self) -> impl Iterator<Item = &Identifier> + '_ {
+        self.nodes_with(Difference::Removed)
+    }
+
+    pub fn changed_nodes(&self) -> impl Iterator<Item = &Identifier> + '_ {
+        self.nodes_with(Difference::Changed)
+    }
+
+    fn nodes_with(&self, difference: Difference) -> impl Iterator<Item = &Identifier> + '_ {
+        self.nodes
+            .iter()
+            .filter(move |n| n.difference == difference)
+            .map(|n| &n.id)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a, T> Scoped<'a, T> {
+    fn iter(&self) -> impl Iterator<Item = (&Identifier, &(Option<Identifier>, &'a T))> + '_ {
+        self.order.iter().map(|id| (id, &self.items[id]))
+    }
+
+    fn get(&self, id: &Identifier) -> Option<&(Option<Identifier>, &'a T)> {
+        self.items.get(id)
+    }
+
+    fn contains_key(&self, id: &Identifier) -> bool {
+        self.items.contains_key(id)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> SubGraphKeys<'a> {
+    fn new(graph: &'a RootGraph) -> Self {
+        let mut keys: HashMap<&'a Identifier, Identifier> = HashMap::default();
+        let mut anonymous: HashMap<Option<Identifier>, usize> = HashMap::default();
+        // Parents are always visited before the sub-graphs they contain.
+        for sub_graph in graph.all_sub_graphs() {
+            let item = sub_graph.item();
+            let key = match item {
+                SubGraphKind::Graph(g) if g.is_anonymous() => {
+                    let parent = (sub_graph.depth() > 0).then(|| keys[sub_graph.scope()].clone());
+                    let count = anonymous.entry(parent.clone()).or_default();
+                    *count += 1;
+                    Identifier::new_unchecked(&format!(
+                        "{}{{{}}}",
+                        parent.as_ref().map(Identifier::as_ref).unwrap_or_default(),
+                        count
+                    ))
+                }
+                _ => item.id().clone(),
+            };
+            let _ = keys.insert(item.id(), key);
+        }
+        let ids = keys.iter().map(|(id, key)| (key.clone(), *id)).collect();
+        Self { keys, ids }
+    }
+
+    fn key(&self, id: &Identifier) -> Identifier {
+        self.keys.get(id).cloned().unwrap_or_else(|| id.clone())
+    }
+
+    fn id(&self, key: &Identifier) -> Option<&'a Identifier> {
+        self.ids.get(key).copied()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for DiffStyle {
+    fn default() -> Self {
+        Self {
+            added: Color::Name(String::from("green")),
+            removed: Color::Name(String::from("red")),
+            changed: Color::Name(String::from("orange")),
+            removed_node_styles: vec![NodeStyles::Dashed],
+            removed_edge_styles: vec![EdgeStyles::Dashed],
+        }
+    }
+}
+
+impl DiffStyle {
+    pub fn added(self, color: Color) -> Self {
+        let mut self_mut = self;
+        self_mut.added = color;
+        self_mut
+    }
+
+    pub fn removed(self, color: Color) -> Self {
+        let mut self_mut = self;
+        self_mut.removed = color;
+        self_mut
+    }
+
+    pub fn changed(self, color: Color) -> Self {
+        let mut self_mut = self;
+        self_mut.changed = color;
+        self_mut
+    }
+
+    pub fn removed_node_styles(self, styles: Vec<NodeStyles>) -> Self {
+        let mut self_mut = self;
+        self_mut.removed_node_styles = styles;
+        self_mut
+    }
+
+    pub fn removed_edge_styles(self, styles: Vec<EdgeStyles>) -> Self {
+        let mut self_mut = self;
+        self_mut.removed_edge_styles = styles;
+        self_mut
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn scoped<'a, T, F>(
+    items: impl Iterator<Item = InScope<'a, T>>,
+    keys: &SubGraphKeys<'_>,
+    key: F,
+) -> Scoped<'a, T>
+where
+    T: 'a,
+    F: Fn(&T) -> Identifier,
+{
+    let mut scoped = Scoped {
+        order: Vec::default(),
+        items: HashMap::default(),
+    };
+    for item in items {
+        let id = key(item.item());
+        if !scoped.items.contains_key(&id) {
+            let scope = (item.depth() > 0).then(|| keys.key(item.scope()));
+            scoped.order.push(id.clone());
+            let _ = scoped.items.insert(id, (scope, item.item()));
+        }
+    }
+    scoped
+}
+
+#[inline]
+fn is_directed(from: &RootGraph, to: &RootGraph) -> bool {
+    from.is_directed() && to.is_directed()
+}
+
+///
+/// Returns pairs of edge indices; `(Some, None)` for a removed edge, `(None, Some)` for an
+/// added edge, and `(Some, Some)` for an edge present in both graphs.
+///
+fn match_edges(
+    from: &[&Edge],
+    to: &[&Edge],
+    directed: bool,
+) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut unmatched: HashMap<(&Identifier, &Identifier), Vec<usize>> = HashMap::default();
+    for (index, edge) in to.iter().enumerate().rev() {
+        unmatched
+            .entry(edge_key(edge, directed))
+            .or_default()
+            .push(index);
+    }
+    let mut pairs: Vec<(Option<usize>, Option<usize>)> = from
+        .iter()
+        .enumerate()
+        .map(|(index, edge)| {
+            let matched = unmatched
+                .get_mut(&edge_key(edge, directed))
+                .and_then(|indices| indices.pop());
+            (Some(index), matched)
+        })
+        .collect();
+    let mut added: Vec<usize> = unmatched.into_values().flatten().collect();
+    added.sort_unstable();
+    pairs.extend(added.into_iter().map(|index| (None, Some(index))));
+    pairs
+}
+
+///
+/// The ends of an edge, ordered when the graph is undirected so that `a -- b` and `b -- a`
+/// have the same key.
+///
+fn edge_key(edge: &Edge, directed: bool) -> (&Identifier, &Identifier) {
+    if directed || edge.head() <= edge.tail() {
+        (edge.head(), edge.tail())
+    } else {
+        (edge.tail(), edge.head())
+    }
+}
+
+fn attribute_changes(
+    from: Option<&Vec<Attribute>>,
+    to: Option<&Vec<Attribute>>,
+) -> Vec<AttributeChange> {
+    let from = by_name(from);
+    let to = by_name(to);
+    let mut changes: Vec<AttributeChange> = Vec::default();
+    for (name, from_attribute) in from.iter() {
+        match to.iter().find(|(other, _)| other == name) {
+            None => changes.push(AttributeChange::Removed((*from_attribute).clone())),
+            Some((_, to_attribute)) if from_attribute != to_attribute => {
+                changes.push(AttributeChange::Changed {
+                    from: (*from_attribute).clone(),
+                    to: (*to_attribute).clone(),
+                })
+            }
+            _ => {}
+        }
+    }
+    for (name, to_attribute) in to.iter() {
+        if !from.iter().any(|(other, _)| other == name) {
+            changes.push(AttributeChange::Added((*to_attribute).clone()));
+        }
+    }
+    changes
+}
+
+///
+/// Where an attribute is repeated the last value wins, as it does for Graphviz.
+///
+fn by_name(attributes: Option<&Vec<Attribute>>) -> Vec<(&'static str, &Attribute)> {
+    let mut named: Vec<(&'static str, &Attribute)> = Vec::default();
+    for attribute in attributes.into_iter().flatten() {
+        let name = attribute.name();
+        match named.iter_mut().find(|(other, _)| *other == name) {
+            Some(existing) => existing.1 = attribute,
+            None => named.push((name, attribute)),
+        }
+    }
+    named
+}
+
+fn sub_graph_attributes(sub_graph: &SubGraphKind) -> Option<&Vec<Attribute>> {
+    match sub_graph {
+        SubGraphKind::Graph(v) => v.attributes().map(AsRef::as_ref),
+        SubGraphKind::Cluster(v) => v.attributes().map(AsRef::as_ref),
+    }
+}
+
+fn insert_in_scope(graph: &mut RootGraph, scope: Option<&Identifier>, sub_graph: SubGraphKind) {
+    match scope {
+        Some(scope) if graph.find_sub_graph(scope).is_some() => graph
+            .sub_graph_mut(scope)
+            .unwrap()
+            .insert_sub_graph(sub_graph),
+        _ => graph.insert_sub_graph(sub_graph),
+    }
+}
+
+fn set_overlay(attributes: &mut Vec<Attribute>, color: &Color, styles: Vec<Style>) {
+    attributes.retain(|a| {
+        !matches!(a, Attribute::Color(_))
+            && (styles.is_empty() || !matches!(a, Attribute::Style(_)))
+    });
+    attributes.push(Attribute::Color(ColorOrList::Color(color.clone())));
+    if !styles.is_empty() {
+        attributes.push(Attribute::Style(styles));
+    }
+}
+
+fn set_node_overlay(node: &mut Node, color: &Color, styles: &[NodeStyles]) {
+    let attributes: &mut NodeAttributes = node.attributes_or_default_mut();
+    set_overlay(
+        attributes.as_mut(),
+        color,
+        styles.iter().map(|s| Style::Node(*s)).collect(),
+    );
+}
+
+fn set_edge_overlay(edge: &mut Edge, color: &Color, styles: &[EdgeStyles]) {
+    let attributes: &mut EdgeAttributes = edge.attributes_or_default_mut();
+    set_overlay(
+        attributes.as_mut(),
+        color,
+        styles.iter().map(|s| Style::Edge(*s)).collect(),
+    );
+}
+
+fn set_cluster_overlay(cluster: Cluster, color: &Color) -> Cluster {
+    let mut attributes: ClusterAttributes = cluster.attributes().cloned().unwrap_or_default();
+    set_overlay(attributes.as_mut(), color, Vec::default());
+    cluster.set_attributes(attributes)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
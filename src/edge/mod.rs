@@ -11,6 +11,7 @@ use crate::{
     attributes::{EdgeAttributes, Styled},
     Identified, Identifier, Node,
 };
+use std::collections::HashMap;
 use std::fmt::Display;
 
 // ------------------------------------------------------------------------------------------------
@@ -63,6 +64,10 @@ impl Styled<EdgeAttributes> for Edge {
         self.attributes.as_ref()
     }
 
+    fn attributes_mut(&mut self) -> Option<&mut EdgeAttributes> {
+        self.attributes.as_mut()
+    }
+
     fn set_attributes(self, attributes: EdgeAttributes) -> Self
     where
         Self: Sized,
@@ -126,6 +131,29 @@ impl Edge {
         self.directed
     }
 
+    ///
+    /// Swap the head and tail of this edge, along with any attributes specific to either end.
+    ///
+    pub fn reverse(self) -> Self {
+        let mut self_mut = self;
+        std::mem::swap(&mut self_mut.head, &mut self_mut.tail);
+        self_mut.attributes = self_mut.attributes.map(EdgeAttributes::reversed);
+        self_mut
+    }
+
+    pub(crate) fn rename_nodes(&mut self, renames: &HashMap<Identifier, Identifier>) {
+        if let Some(head) = renames.get(&self.head) {
+            self.head = head.clone();
+        }
+        if let Some(tail) = renames.get(&self.tail) {
+            self.tail = tail.clone();
+        }
+    }
+
+    pub(crate) fn attributes_or_default_mut(&mut self) -> &mut EdgeAttributes {
+        self.attributes.get_or_insert_with(Default::default)
+    }
+
     pub(crate) fn set_directed(self, directed: bool) -> Self {
         let mut self_mut = self;
         self_mut.directed = directed;
@@ -23,12 +23,24 @@ pub enum Error {
         type_name: String,
         value_as_string: String,
     },
+    InvalidState {
+        type_name: String,
+        message: String,
+    },
     IoError {
         source: std::io::Error,
     },
     Utf8Error {
         source: std::string::FromUtf8Error,
     },
+    #[cfg(feature = "toml")]
+    TomlError {
+        source: toml::de::Error,
+    },
+    #[cfg(feature = "json")]
+    JsonError {
+        source: serde_json::Error,
+    },
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -43,6 +55,14 @@ pub fn invalid_value(type_name: &str, value: &impl Debug) -> Error {
     }
 }
 
+#[inline]
+pub fn invalid_state(type_name: &str, message: &str) -> Error {
+    Error::InvalidState {
+        type_name: type_name.to_string(),
+        message: message.to_string(),
+    }
+}
+
 #[inline]
 pub fn io_error(source: std::io::Error) -> Error {
     Error::IoError { source }
@@ -53,6 +73,18 @@ pub fn utf8_error(source: std::string::FromUtf8Error) -> Error {
     Error::Utf8Error { source }
 }
 
+#[cfg(feature = "toml")]
+#[inline]
+pub fn toml_error(source: toml::de::Error) -> Error {
+    Error::TomlError { source }
+}
+
+#[cfg(feature = "json")]
+#[inline]
+pub fn json_error(source: serde_json::Error) -> Error {
+    Error::JsonError { source }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Types
 // ------------------------------------------------------------------------------------------------
@@ -71,12 +103,20 @@ impl Display for Error {
                     type_name,
                     value_as_string,
                 } => format!("Invalid value `{}` for type {}", value_as_string, type_name),
+                Error::InvalidState { type_name, message } =>
+                    format!("Invalid state in {}; {}", type_name, message),
                 Error::IoError { source } =>
                     format!("An I/O error occurred; source: `{:?}`", source),
                 Error::Utf8Error { source } => format!(
                     "An error occurred converting to UTF-8 text; source: `{:?}`",
                     source
                 ),
+                #[cfg(feature = "toml")]
+                Error::TomlError { source } =>
+                    format!("An error occurred parsing TOML; source: `{:?}`", source),
+                #[cfg(feature = "json")]
+                Error::JsonError { source } =>
+                    format!("An error occurred parsing JSON; source: `{:?}`", source),
             }
         )
     }
@@ -87,6 +127,10 @@ impl std::error::Error for Error {
         match self {
             Error::IoError { source } => Some(source),
             Error::Utf8Error { source } => Some(source),
+            #[cfg(feature = "toml")]
+            Error::TomlError { source } => Some(source),
+            #[cfg(feature = "json")]
+            Error::JsonError { source } => Some(source),
             _ => None,
         }
     }
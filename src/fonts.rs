@@ -0,0 +1,646 @@
+/*!
+Discovers locally installed font families and checks the fonts a graph uses against them.
+
+A `FontCatalog` holds the families found in the standard fontconfig directories on Linux, or
+any others given, by reading the family names from TrueType, OpenType and Type 1 font files.
+Only the table directory and `name` table of TrueType and OpenType files, and the clear text
+header of Type 1 files, are read.
+Families are matched the way fontconfig matches them, ignoring case and spaces.
+
+The standard PostScript families `Times-Roman`, `Helvetica` and `Courier` are also satisfied by
+the installed fonts that share their metrics, such as *Liberation Serif*, so that text takes up
+the same space wherever the graph is rendered.
+
+* `FontCatalog::validate` checks that the first family of a `FontName` is available.
+* `FontCatalog::fallbacks` builds a list of the available families to try in order.
+* `FontCatalog::font_names` chooses the `fontnames` mapping that keeps SVG output the same on
+  every machine.
+
+# Example
+
+```rust
+use simple_dot::attributes::{FontName, FontNameMapping};
+use simple_dot::fonts::FontCatalog;
+
+let catalog = FontCatalog::default()
+    .add_family("DejaVu Sans")
+    .add_family("Liberation Sans");
+
+assert!(catalog.validate(&FontName::helvetica()).is_ok());
+assert!(catalog.validate(&FontName::family("Futura").unwrap()).is_err());
+assert_eq!(
+    catalog
+        .fallbacks(&FontName::family("Futura").unwrap().bold())
+        .to_string(),
+    "\"Liberation Sans bold,DejaVu Sans bold,sans-serif bold\""
+);
+assert_eq!(
+    catalog.font_names(&[FontName::helvetica()]),
+    FontNameMapping::Svg
+);
+```
+
+ */
+
+use crate::attributes::{Attribute, FontName, FontNameMapping, Styled};
+use crate::error::{invalid_value, Error};
+use crate::graph::Graph;
+use crate::RootGraph;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The font families available for rendering.
+///
+#[derive(Clone, Debug, Default)]
+pub struct FontCatalog {
+    families: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GenericFamily {
+    Serif,
+    SansSerif,
+    Monospace,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The directories fontconfig searches by default on Linux, whether or not they exist.
+///
+pub fn standard_font_directories() -> Vec<PathBuf> {
+    let mut directories = vec![
+        PathBuf::from("/usr/share/fonts"),
+        PathBuf::from("/usr/local/share/fonts"),
+    ];
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    match std::env::var_os("XDG_DATA_HOME") {
+        Some(data_home) => directories.push(PathBuf::from(data_home).join("fonts")),
+        None => {
+            if let Some(home) = &home {
+                directories.push(home.join(".local/share/fonts"));
+            }
+        }
+    }
+    if let Some(home) = &home {
+        directories.push(home.join(".fonts"));
+    }
+    directories
+}
+
+///
+/// Every font name set on `graph`, its defaults, and the nodes, edges and clusters within it.
+///
+pub fn used_fonts(graph: &RootGraph) -> Vec<FontName> {
+    let mut attributes: Vec<&[Attribute]> = Vec::default();
+    attributes.extend(graph.attributes().map(|a| a.as_ref().as_slice()));
+    attributes.extend(
+        graph
+            .default_graph_attributes()
+            .map(|a| a.as_ref().as_slice()),
+    );
+    attributes.extend(
+        graph
+            .default_node_attributes()
+            .map(|a| a.as_ref().as_slice()),
+    );
+    attributes.extend(
+        graph
+            .default_edge_attributes()
+            .map(|a| a.as_ref().as_slice()),
+    );
+    attributes.extend(
+        graph
+            .all_clusters()
+            .filter_map(|c| c.item().attributes().map(|a| a.as_ref().as_slice())),
+    );
+    attributes.extend(
+        graph
+            .all_nodes()
+            .filter_map(|n| n.item().attributes().map(|a| a.as_ref().as_slice())),
+    );
+    attributes.extend(
+        graph
+            .all_edges()
+            .filter_map(|e| e.item().attributes().map(|a| a.as_ref().as_slice())),
+    );
+
+    let mut fonts: Vec<FontName> = Vec::default();
+    for attribute in attributes.into_iter().flatten() {
+        if let Attribute::FontName(font) = attribute {
+            if !fonts.contains(font) {
+                fonts.push(font.clone());
+            }
+        }
+    }
+    fonts
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl FontCatalog {
+    ///
+    /// The families installed in the standard fontconfig directories.
+    ///
+    pub fn discover() -> Result<Self, Error> {
+        Self::discover_in(&standard_font_directories())
+    }
+
+    ///
+    /// The families of the font files found anywhere below `directories`, following symbolic
+    /// links; directories that do not exist or cannot be read, and files that cannot be read as
+    /// fonts, are skipped.
+    ///
+    pub fn discover_in<P>(directories: &[P]) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let mut catalog = Self::default();
+        let mut visited: HashSet<PathBuf> = HashSet::default();
+        for directory in directories {
+            catalog.scan_directory(directory.as_ref(), &mut visited);
+        }
+        Ok(catalog)
+    }
+
+    pub fn add_family<S>(self, family: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let mut self_mut = self;
+        self_mut.insert(family.into());
+        self_mut
+    }
+
+    pub fn families(&self) -> impl Iterator<Item = &str> {
+        self.families.values().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.families.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.families.len()
+    }
+
+    pub fn contains(&self, family: &str) -> bool {
+        self.families.contains_key(&family_key(family))
+    }
+
+    ///
+    /// The installed family used for `family`; either the family itself or, for the standard
+    /// PostScript families, an installed family with the same metrics.
+    ///
+    pub fn resolve(&self, family: &str) -> Option<&str> {
+        self.families
+            .get(&family_key(family))
+            .or_else(|| {
+                metric_equivalents(family)
+                    .iter()
+                    .find_map(|f| self.families.get(&family_key(f)))
+            })
+            .map(String::as_str)
+    }
+
+    ///
+    /// Checks that the first family named by `font` is available, as that is the one Graphviz
+    /// will use.
+    ///
+    pub fn validate(&self, font: &FontName) -> Result<(), Error> {
+        match font.families().first() {
+            Some(family) if self.resolve(family).is_some() => Ok(()),
+            Some(family) => Err(invalid_value(FONT_NAME_TYPE_NAME, family)),
+            None => Err(invalid_value(FONT_NAME_TYPE_NAME, &font.to_string())),
+        }
+    }
+
+    ///
+    /// Checks every font used in `graph`, returning the first that is not available.
+    ///
+    pub fn validate_graph(&self, graph: &RootGraph) -> Result<(), Error> {
+        used_fonts(graph)
+            .iter()
+            .try_for_each(|font| self.validate(font))
+    }
+
+    ///
+    /// A list of the available families to use for `font`: the families it names, then the
+    /// installed families of the same kind, and finally the generic family, each with the
+    /// slant and weight of `font`. Families that are not valid font names are left out.
+    ///
+    pub fn fallbacks(&self, font: &FontName) -> FontName {
+        let requested = font.families();
+        let generic = requested
+            .first()
+            .map(|f| GenericFamily::of(f))
+            .unwrap_or(GenericFamily::SansSerif);
+
+        let candidates = requested
+            .iter()
+            .filter_map(|family| self.resolve(family))
+            .chain(
+                generic
+                    .families()
+                    .iter()
+                    .filter_map(|family| self.families.get(&family_key(family)))
+                    .map(String::as_str),
+            )
+            .chain(std::iter::once(generic.name()));
+        let mut families: Vec<&str> = Vec::default();
+        for family in candidates {
+            if !families.iter().any(|f| family_key(f) == family_key(family)) {
+                families.push(family);
+            }
+        }
+
+        FontName::list(
+            families
+                .into_iter()
+                .filter_map(|family| FontName::family(family).ok())
+                .map(|fallback| {
+                    let fallback = match font.font_slant() {
+                        Some(slant) => fallback.slant(slant),
+                        None => fallback,
+                    };
+                    match font.font_weight() {
+                        Some(weight) => fallback.weight(weight),
+                        None => fallback,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    ///
+    /// The `fontnames` mapping that renders `fonts` the same everywhere. Graphviz knows SVG
+    /// names for the standard PostScript families, fonts found in this catalog are named as
+    /// fontconfig resolves them, and otherwise the names are written as given.
+    ///
+    pub fn font_names(&self, fonts: &[FontName]) -> FontNameMapping {
+        let primary: Vec<&str> = fonts
+            .iter()
+            .filter_map(|f| f.families().first().copied())
+            .collect();
+        if primary.iter().all(|f| is_postscript_family(f)) {
+            FontNameMapping::Svg
+        } else if primary.iter().all(|f| self.contains(f)) {
+            FontNameMapping::FontConfig
+        } else {
+            FontNameMapping::Postscript
+        }
+    }
+
+    ///
+    /// Directories are recorded in `visited` by their canonical path so that symbolic links
+    /// back up the tree, or to a directory already scanned, are not followed again.
+    ///
+    fn scan_directory(&mut self, directory: &Path, visited: &mut HashSet<PathBuf>) {
+        match directory.canonicalize() {
+            Ok(canonical) if !visited.contains(&canonical) => {
+                let _ = visited.insert(canonical);
+            }
+            _ => return,
+        }
+        let entries = match std::fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match std::fs::metadata(&path) {
+                Ok(metadata) if metadata.is_dir() => self.scan_directory(&path, visited),
+                Ok(metadata) if metadata.is_file() => {
+                    if let Some(format) = FontFormat::of(&path) {
+                        if let Ok(mut file) = File::open(&path) {
+                            for family in format.families(&mut file) {
+                                self.insert(family);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn insert(&mut self, family: String) {
+        self.families.entry(family_key(&family)).or_insert(family);
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl GenericFamily {
+    ///
+    /// Guesses the kind of `family` from the well-known families and then from its name.
+    ///
+    pub fn of(family: &str) -> Self {
+        let key = family_key(family);
+        [Self::Serif, Self::SansSerif, Self::Monospace]
+            .into_iter()
+            .find(|generic| generic.families().iter().any(|f| family_key(f) == key))
+            .unwrap_or_else(|| {
+                if key.contains("mono") || key.contains("courier") || key.contains("code") {
+                    Self::Monospace
+                } else if key.contains("sans") {
+                    Self::SansSerif
+                } else if key.contains("serif") || key.contains("times") || key.contains("roman") {
+                    Self::Serif
+                } else {
+                    Self::SansSerif
+                }
+            })
+    }
+
+    ///
+    /// The fontconfig alias for this kind of family.
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Serif => "serif",
+            Self::SansSerif => "sans-serif",
+            Self::Monospace => "monospace",
+        }
+    }
+
+    ///
+    /// Common families of this kind, metric-compatible families first.
+    ///
+    pub fn families(&self) -> &'static [&'static str] {
+        match self {
+            Self::Serif => SERIF_FAMILIES,
+            Self::SansSerif => SANS_SERIF_FAMILIES,
+            Self::Monospace => MONOSPACE_FAMILIES,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FontFormat {
+    OpenType,
+    Collection,
+    Type1,
+}
+
+impl FontFormat {
+    fn of(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ttf" | "otf" => Some(Self::OpenType),
+            "ttc" | "otc" => Some(Self::Collection),
+            "pfa" | "pfb" | "t1" => Some(Self::Type1),
+            _ => None,
+        }
+    }
+
+    fn families<R>(&self, reader: &mut R) -> Vec<String>
+    where
+        R: Read + Seek,
+    {
+        match self {
+            Self::OpenType => sfnt_families(reader, 0).unwrap_or_default(),
+            Self::Collection => collection_families(reader).unwrap_or_default(),
+            Self::Type1 => read_at(reader, 0, TYPE1_HEADER_LENGTH)
+                .and_then(|bytes| type1_family(&bytes))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const FONT_NAME_TYPE_NAME: &str = "FontName";
+
+const SERIF_FAMILIES: &[&str] = &[
+    "Times",
+    "Times New Roman",
+    "Nimbus Roman",
+    "Nimbus Roman No9 L",
+    "Liberation Serif",
+    "TeX Gyre Termes",
+    "DejaVu Serif",
+    "Noto Serif",
+    "FreeSerif",
+    "Georgia",
+];
+
+const SANS_SERIF_FAMILIES: &[&str] = &[
+    "Helvetica",
+    "Arial",
+    "Nimbus Sans",
+    "Nimbus Sans L",
+    "Liberation Sans",
+    "TeX Gyre Heros",
+    "DejaVu Sans",
+    "Noto Sans",
+    "FreeSans",
+    "Verdana",
+];
+
+const MONOSPACE_FAMILIES: &[&str] = &[
+    "Courier",
+    "Courier New",
+    "Nimbus Mono PS",
+    "Nimbus Mono L",
+    "Liberation Mono",
+    "TeX Gyre Cursor",
+    "DejaVu Sans Mono",
+    "Noto Sans Mono",
+    "FreeMono",
+];
+
+/// The number of families at the start of each list above that share metrics.
+const METRIC_EQUIVALENTS: usize = 6;
+
+/// The standard PostScript families for which Graphviz has SVG names.
+const POSTSCRIPT_FAMILIES: &[&str] = &[
+    "AvantGarde",
+    "Bookman",
+    "Courier",
+    "Helvetica",
+    "NewCenturySchlbk",
+    "Palatino",
+    "Symbol",
+    "Times",
+    "ZapfChancery",
+    "ZapfDingbats",
+];
+
+const NAME_ID_FAMILY: u16 = 1;
+const NAME_ID_TYPOGRAPHIC_FAMILY: u16 = 16;
+const PLATFORM_UNICODE: u16 = 0;
+const PLATFORM_MACINTOSH: u16 = 1;
+const PLATFORM_WINDOWS: u16 = 3;
+const LANGUAGE_MACINTOSH_ENGLISH: u16 = 0;
+const LANGUAGE_WINDOWS_ENGLISH_US: u16 = 0x0409;
+
+/// The most read from the start of a Type 1 font when looking for its family name, which is
+/// in the font dictionary near the start of the clear text part.
+const TYPE1_HEADER_LENGTH: u64 = 64 * 1024;
+
+///
+/// Fontconfig compares family names ignoring case and spaces.
+///
+fn family_key(family: &str) -> String {
+    family
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+///
+/// The PostScript style suffix, as in `Times-Roman` or `Helvetica-Bold`, is not part of the
+/// family.
+///
+fn postscript_base(family: &str) -> &str {
+    family.split('-').next().unwrap_or(family)
+}
+
+fn is_postscript_family(family: &str) -> bool {
+    POSTSCRIPT_FAMILIES.contains(&postscript_base(family))
+}
+
+fn metric_equivalents(family: &str) -> &'static [&'static str] {
+    let key = family_key(postscript_base(family));
+    [SERIF_FAMILIES, SANS_SERIF_FAMILIES, MONOSPACE_FAMILIES]
+        .into_iter()
+        .map(|families| &families[..METRIC_EQUIVALENTS])
+        .find(|families| families.iter().any(|f| family_key(f) == key))
+        .unwrap_or_default()
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+///
+/// Reads up to `length` bytes from `offset`; fewer are returned only at the end of the file.
+///
+fn read_at<R>(reader: &mut R, offset: u64, length: u64) -> Option<Vec<u8>>
+where
+    R: Read + Seek,
+{
+    let _ = reader.seek(SeekFrom::Start(offset)).ok()?;
+    let mut bytes: Vec<u8> = Vec::default();
+    let _ = reader.take(length).read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+fn collection_families<R>(reader: &mut R) -> Option<Vec<String>>
+where
+    R: Read + Seek,
+{
+    let header = read_at(reader, 0, 12)?;
+    if header.get(0..4)? != b"ttcf" {
+        return None;
+    }
+    let count = read_u32(&header, 8)? as u64;
+    let offsets = read_at(reader, 12, 4 * count)?;
+    let mut families = Vec::default();
+    for i in 0..count as usize {
+        let offset = read_u32(&offsets, 4 * i)? as u64;
+        families.extend(sfnt_families(reader, offset).unwrap_or_default());
+    }
+    Some(families)
+}
+
+///
+/// Reads the English family and typographic family names from the `name` table of the font
+/// starting at `start`, reading only the table directory and the `name` table itself.
+///
+fn sfnt_families<R>(reader: &mut R, start: u64) -> Option<Vec<String>>
+where
+    R: Read + Seek,
+{
+    let header = read_at(reader, start, 12)?;
+    let table_count = read_u16(&header, 4)? as u64;
+    let directory = read_at(reader, start + 12, 16 * table_count)?;
+    let record = (0..table_count as usize)
+        .map(|i| 16 * i)
+        .find(|record| directory.get(*record..*record + 4) == Some(b"name"))?;
+    let offset = read_u32(&directory, record + 8)? as u64;
+    let length = read_u32(&directory, record + 12)? as u64;
+    name_table_families(&read_at(reader, offset, length)?)
+}
+
+fn name_table_families(table: &[u8]) -> Option<Vec<String>> {
+    let count = read_u16(table, 2)? as usize;
+    let storage = read_u16(table, 4)? as usize;
+    let mut families: Vec<String> = Vec::default();
+    for i in 0..count {
+        let record = 6 + 12 * i;
+        let platform = read_u16(table, record)?;
+        let encoding = read_u16(table, record + 2)?;
+        let language = read_u16(table, record + 4)?;
+        let name_id = read_u16(table, record + 6)?;
+        let length = read_u16(table, record + 8)? as usize;
+        let offset = storage + read_u16(table, record + 10)? as usize;
+        if name_id != NAME_ID_FAMILY && name_id != NAME_ID_TYPOGRAPHIC_FAMILY {
+            continue;
+        }
+        let raw = table.get(offset..offset + length)?;
+        let name = match (platform, encoding, language) {
+            (PLATFORM_UNICODE, _, _) | (PLATFORM_WINDOWS, _, LANGUAGE_WINDOWS_ENGLISH_US) => {
+                String::from_utf16(
+                    &raw.chunks_exact(2)
+                        .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                        .collect::<Vec<u16>>(),
+                )
+                .ok()
+            }
+            (PLATFORM_MACINTOSH, 0, LANGUAGE_MACINTOSH_ENGLISH) => {
+                Some(raw.iter().map(|b| *b as char).collect())
+            }
+            _ => None,
+        };
+        if let Some(name) = name.filter(|n| !n.trim().is_empty()) {
+            if !families.contains(&name) {
+                families.push(name);
+            }
+        }
+    }
+    Some(families)
+}
+
+///
+/// Reads `/FamilyName (...)` from the clear text part of a Type 1 font.
+///
+fn type1_family(bytes: &[u8]) -> Option<String> {
+    const KEY: &[u8] = b"/FamilyName";
+    let start = bytes.windows(KEY.len()).position(|w| w == KEY)? + KEY.len();
+    let rest = &bytes[start..];
+    let open = rest.iter().position(|b| *b == b'(')? + 1;
+    let close = open + rest[open..].iter().position(|b| *b == b')')?;
+    String::from_utf8(rest[open..close].to_vec()).ok()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -0,0 +1,253 @@
+/*!
+An adjacency index over a graph and all of its nested sub-graphs and clusters.
+
+Edges may be declared in any scope, and refer to nodes declared in any other scope, so the
+index is built from a deep traversal of the graph. For undirected graphs every edge is treated
+as connecting its two ends in both directions.
+
+# Example
+
+ */
+
+use crate::attributes::Attributes;
+use crate::graph::Graph;
+use crate::{Edge, Identified, Identifier};
+use std::collections::HashMap;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+pub struct Adjacency<'a> {
+    directed: bool,
+    nodes: Vec<&'a Identifier>,
+    index: HashMap<&'a Identifier, usize>,
+    edges: Vec<&'a Edge>,
+    incident: Vec<Vec<usize>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Adjacency<'a> {
+    pub fn new<A>(graph: &'a impl Graph<A>) -> Self
+    where
+        A: Attributes,
+    {
+        let mut self_mut = Self {
+            directed: graph.is_directed(),
+            nodes: Default::default(),
+            index: Default::default(),
+            edges: Default::default(),
+            incident: Default::default(),
+        };
+        for node in graph.all_nodes() {
+            let _ = self_mut.add_node(node.item().id());
+        }
+        for edge in graph.all_edges() {
+            let edge = edge.item();
+            let edge_index = self_mut.edges.len();
+            self_mut.edges.push(edge);
+            let head = self_mut.add_node(edge.head());
+            let tail = self_mut.add_node(edge.tail());
+            self_mut.incident[head].push(edge_index);
+            if tail != head {
+                self_mut.incident[tail].push(edge_index);
+            }
+        }
+        self_mut
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &'a Identifier> + '_ {
+        self.nodes.iter().copied()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn contains_node(&self, id: &Identifier) -> bool {
+        self.index.contains_key(id)
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = &'a Edge> + '_ {
+        self.edges.iter().copied()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn incident_edges(&self, id: &Identifier) -> Vec<&'a Edge> {
+        self.incident_indices(id)
+            .iter()
+            .map(|i| self.edges[*i])
+            .collect()
+    }
+
+    pub fn out_edges(&self, id: &Identifier) -> Vec<&'a Edge> {
+        self.incident_edges(id)
+            .into_iter()
+            .filter(|e| !self.directed || e.head() == id)
+            .collect()
+    }
+
+    pub fn in_edges(&self, id: &Identifier) -> Vec<&'a Edge> {
+        self.incident_edges(id)
+            .into_iter()
+            .filter(|e| !self.directed || e.tail() == id)
+            .collect()
+    }
+
+    pub fn successors(&self, id: &Identifier) -> Vec<&'a Identifier> {
+        unique(
+            self.out_edges(id)
+                .into_iter()
+                .map(|e| other_end(e, id))
+                .collect(),
+        )
+    }
+
+    pub fn predecessors(&self, id: &Identifier) -> Vec<&'a Identifier> {
+        unique(
+            self.in_edges(id)
+                .into_iter()
+                .map(|e| {
+                    if self.directed {
+                        e.head()
+                    } else {
+                        other_end(e, id)
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    pub fn neighbors(&self, id: &Identifier) -> Vec<&'a Identifier> {
+        unique(
+            self.incident_edges(id)
+                .into_iter()
+                .map(|e| other_end(e, id))
+                .collect(),
+        )
+    }
+
+    pub fn degree(&self, id: &Identifier) -> usize {
+        self.incident_edges(id)
+            .into_iter()
+            .map(|e| usize::from(e.head() == id) + usize::from(e.tail() == id))
+            .sum()
+    }
+
+    pub fn in_degree(&self, id: &Identifier) -> usize {
+        self.in_edges(id).len()
+    }
+
+    pub fn out_degree(&self, id: &Identifier) -> usize {
+        self.out_edges(id).len()
+    }
+
+    pub(crate) fn index_of(&self, id: &Identifier) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    pub(crate) fn node_at(&self, index: usize) -> &'a Identifier {
+        self.nodes[index]
+    }
+
+    pub(crate) fn edge_ends(&self, index: usize) -> (usize, usize) {
+        let edge = self.edges[index];
+        (self.index[edge.head()], self.index[edge.tail()])
+    }
+
+    pub(crate) fn successor_lists(&self) -> Vec<Vec<usize>> {
+        self.neighbor_lists(true)
+    }
+
+    pub(crate) fn predecessor_lists(&self) -> Vec<Vec<usize>> {
+        self.neighbor_lists(false)
+    }
+
+    fn neighbor_lists(&self, outgoing: bool) -> Vec<Vec<usize>> {
+        let mut lists: Vec<Vec<usize>> = vec![Vec::default(); self.nodes.len()];
+        for edge in self.edges.iter() {
+            let head = self.index[edge.head()];
+            let tail = self.index[edge.tail()];
+            if !self.directed {
+                lists[head].push(tail);
+                if head != tail {
+                    lists[tail].push(head);
+                }
+            } else if outgoing {
+                lists[head].push(tail);
+            } else {
+                lists[tail].push(head);
+            }
+        }
+        lists
+    }
+
+    fn incident_indices(&self, id: &Identifier) -> &[usize] {
+        match self.index.get(id) {
+            Some(index) => &self.incident[*index],
+            None => &[],
+        }
+    }
+
+    fn add_node(&mut self, id: &'a Identifier) -> usize {
+        if let Some(index) = self.index.get(id) {
+            *index
+        } else {
+            let index = self.nodes.len();
+            self.nodes.push(id);
+            let _ = self.index.insert(id, index);
+            self.incident.push(Vec::default());
+            index
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+#[inline]
+fn other_end<'a>(edge: &'a Edge, id: &Identifier) -> &'a Identifier {
+    if edge.head() == id {
+        edge.tail()
+    } else {
+        edge.head()
+    }
+}
+
+pub(crate) fn unique(ids: Vec<&Identifier>) -> Vec<&Identifier> {
+    let mut result: Vec<&Identifier> = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !result.contains(&id) {
+            result.push(id);
+        }
+    }
+    result
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -14,6 +14,7 @@ use crate::graph::{
     display_graph_common, Edges, Graph, GraphImpl, GraphImplKind, Nodes, SubGraphKind, SubGraphs,
 };
 use crate::{Edge, Identified, Identifier, Node};
+use std::collections::HashMap;
 use std::fmt::Display;
 
 // ------------------------------------------------------------------------------------------------
@@ -67,6 +68,42 @@ impl Cluster {
     pub fn new(id: Identifier) -> Self {
         Self(GraphImpl::new(GraphImplKind::Cluster, id))
     }
+
+    pub fn edge_mut(&mut self, from: &Identifier, to: &Identifier) -> Option<&mut Edge> {
+        self.0.edge_mut(from, to)
+    }
+
+    pub fn sub_graph_mut(&mut self, id: &Identifier) -> Option<&mut SubGraphKind> {
+        self.0.sub_graph_mut(id)
+    }
+
+    #[inline]
+    fn push_edge(&mut self, edge: Edge) {
+        self.0.edges.push(edge);
+    }
+
+    #[inline]
+    fn push_sub_graph(&mut self, sub_graph: SubGraphKind) {
+        self.0.sub_graphs.push(sub_graph);
+    }
+
+    #[inline]
+    fn edges_changed(&mut self) {}
+
+    #[inline]
+    fn edges_removed(&mut self) {}
+
+    pub(super) fn from_inner(inner: GraphImpl<ClusterAttributes>) -> Self {
+        Self(inner)
+    }
+
+    pub(super) fn inner(&self) -> &GraphImpl<ClusterAttributes> {
+        &self.0
+    }
+
+    pub(super) fn inner_mut(&mut self) -> &mut GraphImpl<ClusterAttributes> {
+        &mut self.0
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
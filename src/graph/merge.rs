@@ -0,0 +1,298 @@
+/*!
+Merges graphs produced separately into a single graph.
+
+Each source graph is merged, in order, into the target graph. Where a node identifier is used in
+more than one graph the `NodeCollision` strategy determines whether the nodes are unified into a
+single node, whether the later node is renamed with the identifier of its source graph as a
+prefix, or whether the merge fails. Renaming a node rewrites every edge that refers to it.
+Attributes of the root graphs, and of unified nodes, are combined according to the
+`AttributeConflict` policy.
+
+# Example
+
+ */
+
+use crate::attributes::{Attribute, Styled};
+use crate::error::{invalid_state, Error};
+use crate::graph::{Cluster, Graph, RootGraph};
+use crate::{Identified, Identifier};
+use std::collections::{HashMap, HashSet};
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NodeCollision {
+    /// Nodes with the same identifier are the same node.
+    #[default]
+    Unify,
+    /// Nodes are renamed to `{graph}_{node}`, where `graph` is the source graph identifier; the
+    /// merge fails if a new name is already used in either graph.
+    Prefix,
+    /// The merge fails if a node is declared in both graphs.
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AttributeConflict {
+    FirstWins,
+    #[default]
+    LastWins,
+    /// The merge fails if the same attribute has different values.
+    Error,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MergeOptions {
+    nodes: NodeCollision,
+    attributes: AttributeConflict,
+    wrap_in_clusters: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl MergeOptions {
+    pub fn node_collision(self, nodes: NodeCollision) -> Self {
+        let mut self_mut = self;
+        self_mut.nodes = nodes;
+        self_mut
+    }
+
+    pub fn attribute_conflict(self, attributes: AttributeConflict) -> Self {
+        let mut self_mut = self;
+        self_mut.attributes = attributes;
+        self_mut
+    }
+
+    ///
+    /// Place the content of each source graph in a cluster with the source graph identifier,
+    /// along with its default graph, node and edge attributes.
+    ///
+    pub fn wrap_in_clusters(self) -> Self {
+        let mut self_mut = self;
+        self_mut.wrap_in_clusters = true;
+        self_mut
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl RootGraph {
+    ///
+    /// Merge `other` into this graph.
+    ///
+    pub fn merge(self, other: RootGraph, options: &MergeOptions) -> Result<Self, Error> {
+        let mut self_mut = self;
+        merge_into(&mut self_mut, other, options)?;
+        Ok(self_mut)
+    }
+
+    ///
+    /// Merge all `graphs`, in order, into a new graph which takes its strictness and direction
+    /// from the first graph.
+    ///
+    pub fn merge_all(
+        id: Identifier,
+        graphs: Vec<RootGraph>,
+        options: &MergeOptions,
+    ) -> Result<Self, Error> {
+        let (strict, directed) = graphs
+            .first()
+            .map(|g| (g.is_strict(), g.is_directed()))
+            .unwrap_or_default();
+        let mut merged = RootGraph::new(id, strict, directed);
+        for graph in graphs {
+            merge_into(&mut merged, graph, options)?;
+        }
+        Ok(merged)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn merge_into(
+    target: &mut RootGraph,
+    source: RootGraph,
+    options: &MergeOptions,
+) -> Result<(), Error> {
+    if target.is_directed() != source.is_directed() {
+        return Err(invalid_state(
+            MERGE_TYPE_NAME,
+            "cannot merge directed and undirected graphs",
+        ));
+    }
+
+    let mut source = source;
+    let existing: HashSet<Identifier> = target.adjacency().nodes().cloned().collect();
+    let colliding: Vec<Identifier> = source
+        .adjacency()
+        .nodes()
+        .filter(|id| existing.contains(id))
+        .cloned()
+        .collect();
+
+    match options.nodes {
+        NodeCollision::Unify => {
+            for id in colliding {
+                if target.contains_node(&id) {
+                    while let Some(node) = source.remove_node(&id, false) {
+                        if let Some(attributes) = node.attributes() {
+                            let existing = target.node_mut(&id).unwrap();
+                            merge_attributes(
+                                existing.attributes_or_default_mut().as_mut(),
+                                attributes.as_ref(),
+                                options.attributes,
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+        NodeCollision::Prefix => {
+            let prefix = format!("{}_", source.id().unquoted());
+            let renames: HashMap<Identifier, Identifier> = colliding
+                .into_iter()
+                .map(|id| {
+                    let renamed = id.derived(&prefix, "");
+                    (id, renamed)
+                })
+                .collect();
+            let source_ids: HashSet<&Identifier> = source.adjacency().nodes().collect();
+            if let Some(renamed) = renames
+                .values()
+                .find(|id| existing.contains(*id) || source_ids.contains(id))
+            {
+                return Err(invalid_state(
+                    MERGE_TYPE_NAME,
+                    &format!("renamed node {} is already used", renamed),
+                ));
+            }
+            source.rename_nodes(&renames);
+        }
+        NodeCollision::Error => {
+            let declared: HashSet<&Identifier> =
+                target.all_nodes().map(|n| n.item().id()).collect();
+            if let Some(id) = source
+                .all_nodes()
+                .map(|n| n.item().id())
+                .find(|id| declared.contains(id))
+            {
+                return Err(invalid_state(
+                    MERGE_TYPE_NAME,
+                    &format!("node {} is declared in more than one graph", id),
+                ));
+            }
+        }
+    }
+
+    if let Some(attributes) = source.attributes() {
+        let mut merged = target.attributes().cloned().unwrap_or_default();
+        merge_attributes(merged.as_mut(), attributes.as_ref(), options.attributes)?;
+        *target = std::mem::take(target).set_attributes(merged);
+    }
+
+    if options.wrap_in_clusters {
+        let mut cluster =
+            Cluster::new(source.id().clone()).add_nodes(source.nodes().cloned().collect());
+        if let Some(attributes) = source.default_graph_attributes() {
+            cluster = cluster.set_default_graph_attributes(attributes.clone());
+        }
+        if let Some(attributes) = source.default_node_attributes() {
+            cluster = cluster.set_default_node_attributes(attributes.clone());
+        }
+        if let Some(attributes) = source.default_edge_attributes() {
+            cluster = cluster.set_default_edge_attributes(attributes.clone());
+        }
+        for edge in source.edges() {
+            cluster = cluster.add_edge(edge.clone());
+        }
+        target.insert_sub_graph(cluster.add_sub_graphs(source.sub_graphs().cloned().collect()));
+    } else {
+        if let Some(attributes) = source.default_graph_attributes() {
+            let mut merged = target
+                .default_graph_attributes()
+                .cloned()
+                .unwrap_or_default();
+            merge_attributes(merged.as_mut(), attributes.as_ref(), options.attributes)?;
+            *target = std::mem::take(target).set_default_graph_attributes(merged);
+        }
+        if let Some(attributes) = source.default_node_attributes() {
+            let mut merged = target
+                .default_node_attributes()
+                .cloned()
+                .unwrap_or_default();
+            merge_attributes(merged.as_mut(), attributes.as_ref(), options.attributes)?;
+            *target = std::mem::take(target).set_default_node_attributes(merged);
+        }
+        if let Some(attributes) = source.default_edge_attributes() {
+            let mut merged = target
+                .default_edge_attributes()
+                .cloned()
+                .unwrap_or_default();
+            merge_attributes(merged.as_mut(), attributes.as_ref(), options.attributes)?;
+            *target = std::mem::take(target).set_default_edge_attributes(merged);
+        }
+        for node in source.nodes() {
+            target.insert_node(node.clone());
+        }
+        for edge in source.edges() {
+            target.insert_edge(edge.clone());
+        }
+        for sub_graph in source.sub_graphs() {
+            target.insert_sub_graph(sub_graph.clone());
+        }
+    }
+    Ok(())
+}
+
+fn merge_attributes(
+    existing: &mut Vec<Attribute>,
+    incoming: &[Attribute],
+    policy: AttributeConflict,
+) -> Result<(), Error> {
+    for attribute in incoming {
+        let name = attribute.name();
+        match existing.iter().rposition(|a| a.name() == name) {
+            None => existing.push(attribute.clone()),
+            Some(index) => match policy {
+                AttributeConflict::FirstWins => {}
+                AttributeConflict::LastWins => {
+                    existing.retain(|a| a.name() != name);
+                    existing.push(attribute.clone());
+                }
+                AttributeConflict::Error => {
+                    if &existing[index] != attribute {
+                        return Err(invalid_state(
+                            MERGE_TYPE_NAME,
+                            &format!("conflicting values for attribute {}", name),
+                        ));
+                    }
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+const MERGE_TYPE_NAME: &str = "RootGraph";
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -7,9 +7,14 @@ More detailed description, with
 
  */
 
-use crate::attributes::{Attributes, EdgeAttributes, GraphAttributes, NodeAttributes, Styled};
+use crate::attributes::{
+    Attribute, Attributes, EdgeAttributes, GraphAttributes, NodeAttributes, RankType, Styled,
+};
+use crate::error::{invalid_state, invalid_value, Error};
 use crate::{Edge, Identified, Identifier, Node};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
+use std::rc::Rc;
 
 // ------------------------------------------------------------------------------------------------
 // Public Macros
@@ -78,6 +83,199 @@ where
 
     fn chain(self, nodes: Vec<Node>) -> Self;
     fn circular_chain(self, nodes: Vec<Node>) -> Self;
+
+    ///
+    /// Add an anonymous sub-graph, `{ rank=...; a; b; }`, which constrains the rank of the
+    /// nodes `ids` in the layout.
+    ///
+    fn add_rank(self, rank: RankType, ids: Vec<Identifier>) -> Self
+    where
+        Self: Sized,
+    {
+        self.add_sub_graph(
+            SubGraph::anonymous()
+                .set_attributes(GraphAttributes::default().rank(rank))
+                .add_nodes(ids.into_iter().map(Node::new).collect()),
+        )
+    }
+
+    ///
+    /// All nodes `ids` are placed on the same rank.
+    ///
+    fn same_rank(self, ids: Vec<Identifier>) -> Self
+    where
+        Self: Sized,
+    {
+        self.add_rank(RankType::Same, ids)
+    }
+
+    ///
+    /// All nodes `ids` are placed on the minimum rank.
+    ///
+    fn min_rank(self, ids: Vec<Identifier>) -> Self
+    where
+        Self: Sized,
+    {
+        self.add_rank(RankType::Min, ids)
+    }
+
+    ///
+    /// All nodes `ids` are placed on the maximum rank.
+    ///
+    fn max_rank(self, ids: Vec<Identifier>) -> Self
+    where
+        Self: Sized,
+    {
+        self.add_rank(RankType::Max, ids)
+    }
+
+    ///
+    /// All nodes `ids` are placed on the minimum rank, which contains no other nodes.
+    ///
+    fn source_rank(self, ids: Vec<Identifier>) -> Self
+    where
+        Self: Sized,
+    {
+        self.add_rank(RankType::Source, ids)
+    }
+
+    ///
+    /// All nodes `ids` are placed on the maximum rank, which contains no other nodes.
+    ///
+    fn sink_rank(self, ids: Vec<Identifier>) -> Self
+    where
+        Self: Sized,
+    {
+        self.add_rank(RankType::Sink, ids)
+    }
+
+    fn all_nodes(&self) -> AllNodes<'_>
+    where
+        Self: Sized,
+    {
+        AllNodes {
+            scopes: Scopes::new(self),
+            current: None,
+        }
+    }
+
+    fn all_edges(&self) -> AllEdges<'_>
+    where
+        Self: Sized,
+    {
+        AllEdges {
+            scopes: Scopes::new(self),
+            current: None,
+        }
+    }
+
+    fn all_sub_graphs(&self) -> AllSubGraphs<'_>
+    where
+        Self: Sized,
+    {
+        AllSubGraphs {
+            scopes: Scopes::new(self),
+        }
+    }
+
+    fn all_clusters(&self) -> AllClusters<'_>
+    where
+        Self: Sized,
+    {
+        AllClusters {
+            iter: self.all_sub_graphs(),
+        }
+    }
+
+    ///
+    /// An index over every node and edge in this graph, for answering many adjacency queries.
+    ///
+    fn adjacency(&self) -> Adjacency<'_>
+    where
+        Self: Sized,
+    {
+        Adjacency::new(self)
+    }
+
+    ///
+    /// The nodes reached by an edge from `id`. Each call scans every edge in the graph, so
+    /// build an `Adjacency` once for repeated queries.
+    ///
+    fn successors(&self, id: &Identifier) -> Vec<&Identifier>
+    where
+        Self: Sized,
+    {
+        let directed = self.is_directed();
+        adjacency::unique(
+            self.all_edges()
+                .filter_map(|e| {
+                    let edge = e.item();
+                    if edge.head() == id {
+                        Some(edge.tail())
+                    } else if !directed && edge.tail() == id {
+                        Some(edge.head())
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    ///
+    /// The nodes with an edge to `id`. Each call scans every edge in the graph, so build an
+    /// `Adjacency` once for repeated queries.
+    ///
+    fn predecessors(&self, id: &Identifier) -> Vec<&Identifier>
+    where
+        Self: Sized,
+    {
+        let directed = self.is_directed();
+        adjacency::unique(
+            self.all_edges()
+                .filter_map(|e| {
+                    let edge = e.item();
+                    if edge.tail() == id {
+                        Some(edge.head())
+                    } else if !directed && edge.head() == id {
+                        Some(edge.tail())
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    ///
+    /// The edges, in any scope, that start or end at `id`. Each call scans every edge in the
+    /// graph, so build an `Adjacency` once for repeated queries.
+    ///
+    fn incident_edges(&self, id: &Identifier) -> Vec<&Edge>
+    where
+        Self: Sized,
+    {
+        self.all_edges()
+            .map(|e| e.item())
+            .filter(|e| e.head() == id || e.tail() == id)
+            .collect()
+    }
+
+    ///
+    /// The number of edge ends at `id`, counting a self-loop twice. Each call scans every edge
+    /// in the graph, so build an `Adjacency` once for repeated queries.
+    ///
+    fn degree(&self, id: &Identifier) -> usize
+    where
+        Self: Sized,
+    {
+        self.all_edges()
+            .map(|e| {
+                let edge = e.item();
+                usize::from(edge.head() == id) + usize::from(edge.tail() == id)
+            })
+            .sum()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -101,6 +299,38 @@ pub struct SubGraphs<'a> {
     iter: std::slice::Iter<'a, SubGraphKind>,
 }
 
+///
+/// An item found during a deep traversal of a graph, along with the path of graph identifiers
+/// from the outermost graph traversed down to the graph that directly contains the item.
+///
+#[derive(Clone, Debug)]
+pub struct InScope<'a, T> {
+    path: ScopePath<'a>,
+    item: &'a T,
+}
+
+#[derive(Debug)]
+pub struct AllNodes<'a> {
+    scopes: Scopes<'a>,
+    current: Option<(ScopePath<'a>, Nodes<'a>)>,
+}
+
+#[derive(Debug)]
+pub struct AllEdges<'a> {
+    scopes: Scopes<'a>,
+    current: Option<(ScopePath<'a>, Edges<'a>)>,
+}
+
+#[derive(Debug)]
+pub struct AllSubGraphs<'a> {
+    scopes: Scopes<'a>,
+}
+
+#[derive(Debug)]
+pub struct AllClusters<'a> {
+    iter: AllSubGraphs<'a>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
@@ -128,9 +358,31 @@ where
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum GraphImplKind {
-    Root(bool),
+    Root(bool, SelfLoops),
     Cluster,
-    Graph,
+    Graph(bool),
+}
+
+///
+/// The identifiers from the outermost graph down to one scope, shared by every item in it.
+///
+type ScopePath<'a> = Rc<[&'a Identifier]>;
+
+///
+/// A lazy, depth-first walk over a graph and each sub-graph nested within it.
+///
+#[derive(Debug)]
+struct Scopes<'a> {
+    first: Option<Scope<'a>>,
+    stack: Vec<(ScopePath<'a>, SubGraphs<'a>)>,
+}
+
+#[derive(Debug)]
+struct Scope<'a> {
+    path: ScopePath<'a>,
+    nodes: Nodes<'a>,
+    edges: Edges<'a>,
+    sub_graph: Option<(ScopePath<'a>, &'a SubGraphKind)>,
 }
 // ------------------------------------------------------------------------------------------------
 // Implementation Macros
@@ -149,6 +401,10 @@ macro_rules! impl_graph_trait_for {
                 self.0.attributes.as_ref()
             }
 
+            fn attributes_mut(&mut self) -> Option<&mut $attr_type> {
+                self.0.attributes.as_mut()
+            }
+
             fn set_attributes(self, attributes: $attr_type) -> Self
             where
                 Self: Sized,
@@ -247,7 +503,7 @@ macro_rules! impl_graph_trait_for {
             {
                 let edge = edge.set_directed(self.is_directed());
                 let mut self_mut = self;
-                self_mut.0.edges.push(edge);
+                self_mut.push_edge(edge);
                 self_mut
             }
 
@@ -256,12 +512,10 @@ macro_rules! impl_graph_trait_for {
                 Self: Sized,
             {
                 let directed = self.0.directed;
-                println!("dir={};", directed);
                 let mut self_mut = self;
-                self_mut
-                    .0
-                    .edges
-                    .extend(edges.into_iter().map(|e| e.set_directed(directed)));
+                for edge in edges {
+                    self_mut.push_edge(edge.set_directed(directed));
+                }
                 self_mut
             }
 
@@ -282,7 +536,7 @@ macro_rules! impl_graph_trait_for {
             {
                 let sub_graph = sub_graph.into().set_directed(self.is_directed());
                 let mut self_mut = self;
-                self_mut.0.sub_graphs.push(sub_graph);
+                self_mut.push_sub_graph(sub_graph);
                 self_mut
             }
 
@@ -293,11 +547,9 @@ macro_rules! impl_graph_trait_for {
             {
                 let directed = self.0.directed;
                 let mut self_mut = self;
-                self_mut.0.sub_graphs.extend(
-                    sub_graphs
-                        .into_iter()
-                        .map(|s| s.into().set_directed(directed)),
-                );
+                for sub_graph in sub_graphs {
+                    self_mut.push_sub_graph(sub_graph.into().set_directed(directed));
+                }
                 self_mut
             }
 
@@ -317,6 +569,125 @@ macro_rules! impl_graph_trait_for {
         }
 
         impl $type {
+            pub fn find_node(&self, id: &Identifier) -> Option<&Node> {
+                self.0.find_node(id)
+            }
+
+            pub fn node_mut(&mut self, id: &Identifier) -> Option<&mut Node> {
+                self.0.node_mut(id)
+            }
+
+            pub fn contains_node(&self, id: &Identifier) -> bool {
+                self.find_node(id).is_some()
+            }
+
+            pub fn insert_node(&mut self, node: Node) {
+                self.0.nodes.push(node);
+            }
+
+            pub fn remove_node(&mut self, id: &Identifier, cascade: bool) -> Option<Node> {
+                let removed = self.0.remove_node(id, cascade);
+                self.edges_removed();
+                removed
+            }
+
+            pub fn retain_nodes<F>(&mut self, predicate: F, cascade: bool)
+            where
+                F: FnMut(&Node) -> bool,
+            {
+                let mut predicate = predicate;
+                self.0.retain_nodes(&mut predicate, cascade);
+                self.edges_removed();
+            }
+
+            pub fn move_node(
+                &mut self,
+                id: &Identifier,
+                to_graph: &Identifier,
+            ) -> Result<(), crate::error::Error> {
+                self.0.move_node(id, to_graph)
+            }
+
+            pub fn find_edge(&self, from: &Identifier, to: &Identifier) -> Option<&Edge> {
+                self.0.find_edge(from, to)
+            }
+
+            pub fn insert_edge(&mut self, edge: Edge) {
+                let edge = edge.set_directed(self.0.directed);
+                self.push_edge(edge);
+            }
+
+            pub fn remove_edge(&mut self, from: &Identifier, to: &Identifier) -> Option<Edge> {
+                let removed = self.0.remove_edge(from, to);
+                self.edges_removed();
+                removed
+            }
+
+            pub fn retain_edges<F>(&mut self, predicate: F)
+            where
+                F: FnMut(&Edge) -> bool,
+            {
+                let mut predicate = predicate;
+                self.0.retain_edges(&mut predicate);
+                self.edges_removed();
+            }
+
+            ///
+            /// Renames each node in `renames`, wherever it is declared, and rewrites all
+            /// edges that refer to it.
+            ///
+            pub fn rename_nodes(&mut self, renames: &HashMap<Identifier, Identifier>) {
+                self.0.rename_nodes(renames);
+                self.edges_changed();
+            }
+
+            pub fn for_each_edge_mut<F>(&mut self, f: F)
+            where
+                F: FnMut(&mut Edge),
+            {
+                let mut f = f;
+                self.0.for_each_edge_mut(&mut f);
+                self.edges_changed();
+            }
+
+            pub fn for_each_node_mut<F>(&mut self, f: F)
+            where
+                F: FnMut(&mut Node),
+            {
+                let mut f = f;
+                self.0.for_each_node_mut(&mut f)
+            }
+
+            ///
+            /// Calls `f` with each cluster nested, at any depth, within this graph.
+            ///
+            pub fn for_each_cluster_mut<F>(&mut self, f: F)
+            where
+                F: FnMut(&mut crate::graph::Cluster),
+            {
+                let mut f = f;
+                self.0.for_each_cluster_mut(&mut f);
+                self.edges_changed();
+            }
+
+            pub fn find_sub_graph(&self, id: &Identifier) -> Option<&SubGraphKind> {
+                self.0.find_sub_graph(id)
+            }
+
+            pub fn insert_sub_graph<G>(&mut self, sub_graph: G)
+            where
+                G: Into<SubGraphKind>,
+            {
+                let sub_graph = sub_graph.into().set_directed(self.0.directed);
+                self.push_sub_graph(sub_graph);
+            }
+
+            pub fn remove_sub_graph(&mut self, id: &Identifier) -> Option<SubGraphKind> {
+                let removed = self.0.remove_sub_graph(id);
+                self.edges_removed();
+                removed
+            }
+
             pub(crate) fn set_directed(self, directed: bool) -> Self {
                 let mut self_mut = self;
                 self_mut.0.sub_graphs = self_mut
@@ -355,6 +726,15 @@ impl Display for SubGraphKind {
     }
 }
 
+impl Identified for SubGraphKind {
+    fn id(&self) -> &Identifier {
+        match self {
+            Self::Graph(v) => v.id(),
+            Self::Cluster(v) => v.id(),
+        }
+    }
+}
+
 impl From<SubGraph> for SubGraphKind {
     fn from(v: SubGraph) -> Self {
         Self::Graph(v)
@@ -378,12 +758,193 @@ impl SubGraphKind {
         matches!(self, Self::Cluster(_))
     }
 
+    pub fn find_node(&self, id: &Identifier) -> Option<&Node> {
+        match self {
+            Self::Graph(v) => v.find_node(id),
+            Self::Cluster(v) => v.find_node(id),
+        }
+    }
+
+    pub fn node_mut(&mut self, id: &Identifier) -> Option<&mut Node> {
+        match self {
+            Self::Graph(v) => v.node_mut(id),
+            Self::Cluster(v) => v.node_mut(id),
+        }
+    }
+
+    pub fn insert_node(&mut self, node: Node) {
+        match self {
+            Self::Graph(v) => v.insert_node(node),
+            Self::Cluster(v) => v.insert_node(node),
+        }
+    }
+
+    pub fn remove_node(&mut self, id: &Identifier, cascade: bool) -> Option<Node> {
+        match self {
+            Self::Graph(v) => v.remove_node(id, cascade),
+            Self::Cluster(v) => v.remove_node(id, cascade),
+        }
+    }
+
+    pub fn find_edge(&self, from: &Identifier, to: &Identifier) -> Option<&Edge> {
+        match self {
+            Self::Graph(v) => v.find_edge(from, to),
+            Self::Cluster(v) => v.find_edge(from, to),
+        }
+    }
+
+    pub fn edge_mut(&mut self, from: &Identifier, to: &Identifier) -> Option<&mut Edge> {
+        match self {
+            Self::Graph(v) => v.edge_mut(from, to),
+            Self::Cluster(v) => v.edge_mut(from, to),
+        }
+    }
+
+    pub fn insert_edge(&mut self, edge: Edge) {
+        match self {
+            Self::Graph(v) => v.insert_edge(edge),
+            Self::Cluster(v) => v.insert_edge(edge),
+        }
+    }
+
+    pub fn remove_edge(&mut self, from: &Identifier, to: &Identifier) -> Option<Edge> {
+        match self {
+            Self::Graph(v) => v.remove_edge(from, to),
+            Self::Cluster(v) => v.remove_edge(from, to),
+        }
+    }
+
+    pub fn find_sub_graph(&self, id: &Identifier) -> Option<&SubGraphKind> {
+        match self {
+            Self::Graph(v) => v.find_sub_graph(id),
+            Self::Cluster(v) => v.find_sub_graph(id),
+        }
+    }
+
+    pub fn sub_graph_mut(&mut self, id: &Identifier) -> Option<&mut SubGraphKind> {
+        match self {
+            Self::Graph(v) => v.sub_graph_mut(id),
+            Self::Cluster(v) => v.sub_graph_mut(id),
+        }
+    }
+
+    pub fn insert_sub_graph<G>(&mut self, sub_graph: G)
+    where
+        G: Into<SubGraphKind>,
+    {
+        match self {
+            Self::Graph(v) => v.insert_sub_graph(sub_graph),
+            Self::Cluster(v) => v.insert_sub_graph(sub_graph),
+        }
+    }
+
+    pub fn remove_sub_graph(&mut self, id: &Identifier) -> Option<SubGraphKind> {
+        match self {
+            Self::Graph(v) => v.remove_sub_graph(id),
+            Self::Cluster(v) => v.remove_sub_graph(id),
+        }
+    }
+
     pub(crate) fn set_directed(self, directed: bool) -> Self {
         match self {
             Self::Graph(v) => Self::Graph(v.set_directed(directed)),
             Self::Cluster(v) => Self::Cluster(v.set_directed(directed)),
         }
     }
+
+    pub(crate) fn filtered(
+        &self,
+        node_predicate: &dyn Fn(&Identifier) -> bool,
+        edge_predicate: &dyn Fn(&Edge) -> bool,
+    ) -> Option<Self> {
+        match self {
+            Self::Graph(v) => {
+                let inner = v.inner().filtered(node_predicate, edge_predicate);
+                (!inner.is_empty()).then(|| Self::Graph(SubGraph::from_inner(inner)))
+            }
+            Self::Cluster(v) => {
+                let inner = v.inner().filtered(node_predicate, edge_predicate);
+                (!inner.is_empty()).then(|| Self::Cluster(Cluster::from_inner(inner)))
+            }
+        }
+    }
+
+    fn strict_merge(&mut self, self_loops: SelfLoops) -> Result<(), Error> {
+        match self {
+            Self::Graph(v) => v.inner_mut().strict_merge(self_loops),
+            Self::Cluster(v) => v.inner_mut().strict_merge(self_loops),
+        }
+    }
+
+    fn retain_nodes_inner(
+        &mut self,
+        predicate: &mut dyn FnMut(&Node) -> bool,
+        removed: &mut HashSet<Identifier>,
+    ) {
+        match self {
+            Self::Graph(v) => v.inner_mut().retain_nodes_inner(predicate, removed),
+            Self::Cluster(v) => v.inner_mut().retain_nodes_inner(predicate, removed),
+        }
+    }
+
+    fn retain_edges(&mut self, predicate: &mut dyn FnMut(&Edge) -> bool) {
+        match self {
+            Self::Graph(v) => v.retain_edges(predicate),
+            Self::Cluster(v) => v.retain_edges(predicate),
+        }
+    }
+
+    fn for_each_edge_list_mut(&mut self, f: &mut dyn FnMut(&mut Vec<Edge>)) {
+        match self {
+            Self::Graph(v) => v.inner_mut().for_each_edge_list_mut(f),
+            Self::Cluster(v) => v.inner_mut().for_each_edge_list_mut(f),
+        }
+    }
+
+    fn rename_nodes(&mut self, renames: &HashMap<Identifier, Identifier>) {
+        match self {
+            Self::Graph(v) => v.inner_mut().rename_nodes(renames),
+            Self::Cluster(v) => v.inner_mut().rename_nodes(renames),
+        }
+    }
+
+    fn nth_edge_mut(&mut self, n: &mut usize) -> Option<&mut Edge> {
+        match self {
+            Self::Graph(v) => v.inner_mut().nth_edge_mut(n),
+            Self::Cluster(v) => v.inner_mut().nth_edge_mut(n),
+        }
+    }
+
+    fn for_each_edge_mut(&mut self, f: &mut dyn FnMut(&mut Edge)) {
+        match self {
+            Self::Graph(v) => v.inner_mut().for_each_edge_mut(f),
+            Self::Cluster(v) => v.inner_mut().for_each_edge_mut(f),
+        }
+    }
+
+    fn for_each_node_mut(&mut self, f: &mut dyn FnMut(&mut Node)) {
+        match self {
+            Self::Graph(v) => v.inner_mut().for_each_node_mut(f),
+            Self::Cluster(v) => v.inner_mut().for_each_node_mut(f),
+        }
+    }
+
+    fn for_each_cluster_mut(&mut self, f: &mut dyn FnMut(&mut Cluster)) {
+        match self {
+            Self::Graph(v) => v.inner_mut().for_each_cluster_mut(f),
+            Self::Cluster(v) => {
+                f(v);
+                v.inner_mut().for_each_cluster_mut(f)
+            }
+        }
+    }
+
+    fn take_node(&mut self, id: &Identifier) -> Option<Node> {
+        match self {
+            Self::Graph(v) => v.inner_mut().take_node(id),
+            Self::Cluster(v) => v.inner_mut().take_node(id),
+        }
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -418,9 +979,151 @@ impl<'a> Iterator for SubGraphs<'a> {
 
 // ------------------------------------------------------------------------------------------------
 
+impl<'a, T> InScope<'a, T> {
+    fn new(path: ScopePath<'a>, item: &'a T) -> Self {
+        Self { path, item }
+    }
+
+    pub fn item(&self) -> &'a T {
+        self.item
+    }
+
+    pub fn path(&self) -> &[&'a Identifier] {
+        &self.path
+    }
+
+    pub fn scope(&self) -> &'a Identifier {
+        self.path.last().unwrap()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.path.len() - 1
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Iterator for AllNodes<'a> {
+    type Item = InScope<'a, Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((path, nodes)) = &mut self.current {
+                if let Some(node) = nodes.next() {
+                    return Some(InScope::new(path.clone(), node));
+                }
+            }
+            let scope = self.scopes.next()?;
+            self.current = Some((scope.path, scope.nodes));
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Iterator for AllEdges<'a> {
+    type Item = InScope<'a, Edge>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((path, edges)) = &mut self.current {
+                if let Some(edge) = edges.next() {
+                    return Some(InScope::new(path.clone(), edge));
+                }
+            }
+            let scope = self.scopes.next()?;
+            self.current = Some((scope.path, scope.edges));
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Iterator for AllSubGraphs<'a> {
+    type Item = InScope<'a, SubGraphKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.scopes
+            .by_ref()
+            .find_map(|scope| scope.sub_graph)
+            .map(|(path, sub_graph)| InScope::new(path, sub_graph))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Iterator for AllClusters<'a> {
+    type Item = InScope<'a, Cluster>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for scoped in self.iter.by_ref() {
+            if let SubGraphKind::Cluster(cluster) = scoped.item() {
+                return Some(InScope::new(scoped.path, cluster));
+            }
+        }
+        None
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<'a> Scopes<'a> {
+    fn new<A>(graph: &'a impl Graph<A>) -> Self
+    where
+        A: Attributes,
+    {
+        let path: ScopePath<'a> = Rc::from(vec![graph.id()]);
+        Self {
+            first: Some(Scope {
+                path: path.clone(),
+                nodes: graph.nodes(),
+                edges: graph.edges(),
+                sub_graph: None,
+            }),
+            stack: vec![(path, graph.sub_graphs())],
+        }
+    }
+}
+
+impl<'a> Iterator for Scopes<'a> {
+    type Item = Scope<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first) = self.first.take() {
+            return Some(first);
+        }
+        loop {
+            let (parent, sub_graphs) = self.stack.last_mut()?;
+            match sub_graphs.next() {
+                None => {
+                    let _ = self.stack.pop();
+                }
+                Some(sub_graph) => {
+                    let parent = parent.clone();
+                    let path: ScopePath<'a> =
+                        parent.iter().copied().chain([sub_graph.id()]).collect();
+                    let (nodes, edges, sub_graphs) = match sub_graph {
+                        SubGraphKind::Graph(g) => (g.nodes(), g.edges(), g.sub_graphs()),
+                        SubGraphKind::Cluster(c) => (c.nodes(), c.edges(), c.sub_graphs()),
+                    };
+                    self.stack.push((path.clone(), sub_graphs));
+                    return Some(Scope {
+                        path,
+                        nodes,
+                        edges,
+                        sub_graph: Some((parent, sub_graph)),
+                    });
+                }
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
 impl Default for GraphImplKind {
     fn default() -> Self {
-        Self::Graph
+        Self::Graph(false)
     }
 }
 
@@ -464,12 +1167,346 @@ where
             ..Default::default()
         }
     }
+
+    fn find_node(&self, id: &Identifier) -> Option<&Node> {
+        self.nodes
+            .iter()
+            .find(|n| n.id() == id)
+            .or_else(|| self.sub_graphs.iter().find_map(|s| s.find_node(id)))
+    }
+
+    fn node_mut(&mut self, id: &Identifier) -> Option<&mut Node> {
+        if let Some(index) = self.nodes.iter().position(|n| n.id() == id) {
+            Some(&mut self.nodes[index])
+        } else {
+            self.sub_graphs.iter_mut().find_map(|s| s.node_mut(id))
+        }
+    }
+
+    fn take_node(&mut self, id: &Identifier) -> Option<Node> {
+        if let Some(index) = self.nodes.iter().position(|n| n.id() == id) {
+            Some(self.nodes.remove(index))
+        } else {
+            self.sub_graphs.iter_mut().find_map(|s| s.take_node(id))
+        }
+    }
+
+    fn remove_node(&mut self, id: &Identifier, cascade: bool) -> Option<Node> {
+        let removed = self.take_node(id);
+        if cascade {
+            self.retain_edges(&mut |e: &Edge| e.head() != id && e.tail() != id);
+        }
+        removed
+    }
+
+    fn retain_nodes(&mut self, predicate: &mut dyn FnMut(&Node) -> bool, cascade: bool) {
+        let mut removed = HashSet::default();
+        self.retain_nodes_inner(predicate, &mut removed);
+        if cascade && !removed.is_empty() {
+            self.retain_edges(&mut |e: &Edge| {
+                !removed.contains(e.head()) && !removed.contains(e.tail())
+            });
+        }
+    }
+
+    fn retain_nodes_inner(
+        &mut self,
+        predicate: &mut dyn FnMut(&Node) -> bool,
+        removed: &mut HashSet<Identifier>,
+    ) {
+        self.nodes.retain(|n| {
+            let keep = predicate(n);
+            if !keep {
+                let _ = removed.insert(n.id().clone());
+            }
+            keep
+        });
+        for sub_graph in self.sub_graphs.iter_mut() {
+            sub_graph.retain_nodes_inner(predicate, removed);
+        }
+    }
+
+    fn move_node(&mut self, id: &Identifier, to_graph: &Identifier) -> Result<(), Error> {
+        if &self.id != to_graph && self.find_sub_graph(to_graph).is_none() {
+            return Err(invalid_value("Identifier", to_graph));
+        }
+        let node = self
+            .take_node(id)
+            .ok_or_else(|| invalid_value("Identifier", id))?;
+        if &self.id == to_graph {
+            self.nodes.push(node);
+        } else {
+            self.sub_graph_mut(to_graph).unwrap().insert_node(node);
+        }
+        Ok(())
+    }
+
+    fn find_edge(&self, from: &Identifier, to: &Identifier) -> Option<&Edge> {
+        self.edges
+            .iter()
+            .find(|e| connects(e, from, to, self.directed))
+            .or_else(|| self.sub_graphs.iter().find_map(|s| s.find_edge(from, to)))
+    }
+
+    fn edge_mut(&mut self, from: &Identifier, to: &Identifier) -> Option<&mut Edge> {
+        let directed = self.directed;
+        if let Some(index) = self
+            .edges
+            .iter()
+            .position(|e| connects(e, from, to, directed))
+        {
+            Some(&mut self.edges[index])
+        } else {
+            self.sub_graphs
+                .iter_mut()
+                .find_map(|s| s.edge_mut(from, to))
+        }
+    }
+
+    fn nth_edge_mut(&mut self, n: &mut usize) -> Option<&mut Edge> {
+        if *n < self.edges.len() {
+            return self.edges.get_mut(*n);
+        }
+        *n -= self.edges.len();
+        self.sub_graphs.iter_mut().find_map(|s| s.nth_edge_mut(n))
+    }
+
+    fn remove_edge(&mut self, from: &Identifier, to: &Identifier) -> Option<Edge> {
+        let directed = self.directed;
+        if let Some(index) = self
+            .edges
+            .iter()
+            .position(|e| connects(e, from, to, directed))
+        {
+            Some(self.edges.remove(index))
+        } else {
+            self.sub_graphs
+                .iter_mut()
+                .find_map(|s| s.remove_edge(from, to))
+        }
+    }
+
+    fn retain_edges(&mut self, predicate: &mut dyn FnMut(&Edge) -> bool) {
+        self.edges.retain(|e| predicate(e));
+        for sub_graph in self.sub_graphs.iter_mut() {
+            sub_graph.retain_edges(predicate);
+        }
+    }
+
+    fn for_each_edge_list_mut(&mut self, f: &mut dyn FnMut(&mut Vec<Edge>)) {
+        f(&mut self.edges);
+        for sub_graph in self.sub_graphs.iter_mut() {
+            sub_graph.for_each_edge_list_mut(f);
+        }
+    }
+
+    fn rename_nodes(&mut self, renames: &HashMap<Identifier, Identifier>) {
+        for node in self.nodes.iter_mut() {
+            if let Some(id) = renames.get(node.id()) {
+                node.set_id(id.clone());
+            }
+        }
+        for edge in self.edges.iter_mut() {
+            edge.rename_nodes(renames);
+        }
+        for sub_graph in self.sub_graphs.iter_mut() {
+            sub_graph.rename_nodes(renames);
+        }
+    }
+
+    ///
+    /// Merge duplicate edges, and handle self-loops, as Graphviz does for strict graphs. The
+    /// first of a set of duplicate edges is kept, and the attributes of later edges are merged
+    /// into it with the last value of each attribute winning. Only the attributes of duplicates
+    /// are copied.
+    ///
+    fn strict_merge(&mut self, self_loops: SelfLoops) -> Result<(), Error> {
+        let directed = self.directed;
+        let mut first: HashMap<(Identifier, Identifier), usize> = HashMap::default();
+        let mut keep: Vec<bool> = Vec::default();
+        let mut merged: HashMap<usize, Vec<Attribute>> = HashMap::default();
+        let mut self_loop: Option<Identifier> = None;
+        self.for_each_edge_mut(&mut |edge: &mut Edge| {
+            let index = keep.len();
+            let key = edge_key(edge, directed);
+            if key.0 == key.1 {
+                match self_loops {
+                    SelfLoops::Allow => {}
+                    SelfLoops::Drop => {
+                        keep.push(false);
+                        return;
+                    }
+                    SelfLoops::Reject => {
+                        let _ = self_loop.get_or_insert_with(|| key.0.clone());
+                    }
+                }
+            }
+            match first.get(&key) {
+                None => {
+                    let _ = first.insert(key, index);
+                    keep.push(true);
+                }
+                Some(kept) => {
+                    keep.push(false);
+                    if let Some(incoming) = edge.attributes() {
+                        let existing = merged.entry(*kept).or_default();
+                        merge_attributes(existing, incoming.as_ref());
+                    }
+                }
+            }
+        });
+        if let Some(id) = self_loop {
+            return Err(self_loop_error(&id));
+        }
+
+        if keep.iter().all(|keep| *keep) {
+            return Ok(());
+        }
+        let mut index = 0;
+        self.for_each_edge_mut(&mut |edge: &mut Edge| {
+            if let Some(incoming) = merged.remove(&index) {
+                merge_attributes(edge.attributes_or_default_mut().as_mut(), &incoming);
+            }
+            index += 1;
+        });
+        let mut keep = keep.into_iter();
+        self.retain_edges(&mut |_: &Edge| keep.next().unwrap_or(true));
+        Ok(())
+    }
+
+    fn for_each_edge_mut(&mut self, f: &mut dyn FnMut(&mut Edge)) {
+        for edge in self.edges.iter_mut() {
+            f(edge);
+        }
+        for sub_graph in self.sub_graphs.iter_mut() {
+            sub_graph.for_each_edge_mut(f);
+        }
+    }
+
+    fn for_each_node_mut(&mut self, f: &mut dyn FnMut(&mut Node)) {
+        for node in self.nodes.iter_mut() {
+            f(node);
+        }
+        for sub_graph in self.sub_graphs.iter_mut() {
+            sub_graph.for_each_node_mut(f);
+        }
+    }
+
+    fn for_each_cluster_mut(&mut self, f: &mut dyn FnMut(&mut Cluster)) {
+        for sub_graph in self.sub_graphs.iter_mut() {
+            sub_graph.for_each_cluster_mut(f);
+        }
+    }
+
+    fn find_sub_graph(&self, id: &Identifier) -> Option<&SubGraphKind> {
+        self.sub_graphs
+            .iter()
+            .find(|s| s.id() == id)
+            .or_else(|| self.sub_graphs.iter().find_map(|s| s.find_sub_graph(id)))
+    }
+
+    fn sub_graph_mut(&mut self, id: &Identifier) -> Option<&mut SubGraphKind> {
+        if let Some(index) = self.sub_graphs.iter().position(|s| s.id() == id) {
+            Some(&mut self.sub_graphs[index])
+        } else {
+            self.sub_graphs.iter_mut().find_map(|s| s.sub_graph_mut(id))
+        }
+    }
+
+    fn remove_sub_graph(&mut self, id: &Identifier) -> Option<SubGraphKind> {
+        if let Some(index) = self.sub_graphs.iter().position(|s| s.id() == id) {
+            Some(self.sub_graphs.remove(index))
+        } else {
+            self.sub_graphs
+                .iter_mut()
+                .find_map(|s| s.remove_sub_graph(id))
+        }
+    }
+}
+
+impl<A> GraphImpl<A>
+where
+    A: Attributes + Clone,
+{
+    fn filtered(
+        &self,
+        node_predicate: &dyn Fn(&Identifier) -> bool,
+        edge_predicate: &dyn Fn(&Edge) -> bool,
+    ) -> Self {
+        Self {
+            kind: self.kind,
+            directed: self.directed,
+            id: self.id.clone(),
+            attributes: self.attributes.clone(),
+            default_graph_attributes: self.default_graph_attributes.clone(),
+            default_node_attributes: self.default_node_attributes.clone(),
+            default_edge_attributes: self.default_edge_attributes.clone(),
+            nodes: self
+                .nodes
+                .iter()
+                .filter(|n| node_predicate(n.id()))
+                .cloned()
+                .collect(),
+            edges: self
+                .edges
+                .iter()
+                .filter(|e| edge_predicate(e))
+                .cloned()
+                .collect(),
+            sub_graphs: self
+                .sub_graphs
+                .iter()
+                .filter_map(|s| s.filtered(node_predicate, edge_predicate))
+                .collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.edges.is_empty() && self.sub_graphs.is_empty()
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+///
+/// Returns `true` if `edge` joins `from` to `to`, in either order if the graph is undirected.
+///
+fn connects(edge: &Edge, from: &Identifier, to: &Identifier, directed: bool) -> bool {
+    (edge.head() == from && edge.tail() == to)
+        || (!directed && edge.head() == to && edge.tail() == from)
+}
+
+///
+/// The key under which a strict graph merges `edge`; both ends in order for directed graphs,
+/// otherwise the lesser end first.
+///
+fn edge_key(edge: &Edge, directed: bool) -> (Identifier, Identifier) {
+    if !directed && edge.tail() < edge.head() {
+        (edge.tail().clone(), edge.head().clone())
+    } else {
+        (edge.head().clone(), edge.tail().clone())
+    }
+}
+
+///
+/// Each of `incoming` replaces any attribute of the same name in `existing`.
+///
+fn merge_attributes(existing: &mut Vec<Attribute>, incoming: &[Attribute]) {
+    for attribute in incoming {
+        existing.retain(|a| a.name() != attribute.name());
+        existing.push(attribute.clone());
+    }
+}
+
+fn self_loop_error(id: &Identifier) -> Error {
+    invalid_state(
+        "RootGraph",
+        &format!("self-loop on {} is not allowed in a strict graph", id),
+    )
+}
+
 fn display_graph_common<A>(
     graph: &impl Graph<A>,
     f: &mut std::fmt::Formatter<'_>,
@@ -525,10 +1562,16 @@ where
 // ------------------------------------------------------------------------------------------------
 
 pub mod root;
-pub use root::RootGraph;
+pub use root::{EdgeMut, RootGraph, SelfLoops, SubGraphMut};
 
 pub mod sub_graph;
 pub use sub_graph::SubGraph;
 
 pub mod cluster;
 pub use cluster::Cluster;
+
+pub mod adjacency;
+pub use adjacency::Adjacency;
+
+pub mod merge;
+pub use merge::{AttributeConflict, MergeOptions, NodeCollision};
@@ -8,11 +8,15 @@ More detailed description, with
  */
 
 use crate::attributes::{EdgeAttributes, GraphAttributes, NodeAttributes, Styled};
+use crate::error::Error;
 use crate::graph::{
-    display_graph_common, Edges, Graph, GraphImpl, GraphImplKind, Nodes, SubGraphKind, SubGraphs,
+    connects, display_graph_common, edge_key, merge_attributes, self_loop_error, Cluster, Edges,
+    Graph, GraphImpl, GraphImplKind, Nodes, SubGraphKind, SubGraphs,
 };
 use crate::{Edge, Identified, Identifier, Node};
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::ops::{Deref, DerefMut};
 
 // ------------------------------------------------------------------------------------------------
 // Public Macros
@@ -23,7 +27,42 @@ use std::fmt::Display;
 // ------------------------------------------------------------------------------------------------
 
 #[derive(Clone, Debug)]
-pub struct RootGraph(GraphImpl<GraphAttributes>);
+pub struct RootGraph(GraphImpl<GraphAttributes>, Option<EdgeIndex>);
+
+///
+/// How a strict graph treats edges from a node to itself.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelfLoops {
+    /// Self-loops are kept, as Graphviz does.
+    #[default]
+    Allow,
+    /// Self-loops are silently removed.
+    Drop,
+    /// Self-loops are an error for `try_add_edge`, `try_insert_edge` and `into_strict`; other
+    /// ways of adding edges cannot fail and keep them, use `check_self_loops` to find them.
+    Reject,
+}
+
+///
+/// A sub-graph of a `RootGraph` borrowed for changes; when dropped, a strict graph merges any
+/// edges added to or changed in it with those elsewhere in the graph.
+///
+#[derive(Debug)]
+pub struct SubGraphMut<'a> {
+    root: &'a mut RootGraph,
+    id: Identifier,
+}
+
+///
+/// An edge of a `RootGraph` borrowed for changes; when dropped, a strict graph merges the
+/// edge with any it now duplicates.
+///
+#[derive(Debug)]
+pub struct EdgeMut<'a> {
+    root: &'a mut RootGraph,
+    position: usize,
+}
 
 // ------------------------------------------------------------------------------------------------
 // Public Functions
@@ -33,6 +72,13 @@ pub struct RootGraph(GraphImpl<GraphAttributes>);
 // Private Types
 // ------------------------------------------------------------------------------------------------
 
+///
+/// The edges of a merged strict graph by their merge key, with the position of those declared
+/// directly in the root graph so that duplicates of them are merged without a search.
+///
+#[derive(Clone, Debug, Default)]
+struct EdgeIndex(HashMap<(Identifier, Identifier), Option<usize>>);
+
 // ------------------------------------------------------------------------------------------------
 // Implementation Macros
 // ------------------------------------------------------------------------------------------------
@@ -43,8 +89,9 @@ pub struct RootGraph(GraphImpl<GraphAttributes>);
 
 impl Default for RootGraph {
     fn default() -> Self {
-        let inner: GraphImpl<GraphAttributes> = GraphImpl::anonymous(GraphImplKind::Root(false));
-        Self(inner)
+        let inner: GraphImpl<GraphAttributes> =
+            GraphImpl::anonymous(GraphImplKind::Root(false, SelfLoops::default()));
+        Self(inner, None)
     }
 }
 
@@ -66,23 +113,318 @@ impl_graph_trait_for!(RootGraph, GraphAttributes);
 
 impl RootGraph {
     pub fn anonymous(strict: bool, directed: bool) -> Self {
-        Self(GraphImpl::anonymous(GraphImplKind::Root(strict))).set_directed(directed)
+        Self(
+            GraphImpl::anonymous(GraphImplKind::Root(strict, SelfLoops::default())),
+            None,
+        )
+        .set_directed(directed)
     }
 
     pub fn new(id: Identifier, strict: bool, directed: bool) -> Self {
-        Self(GraphImpl::new(GraphImplKind::Root(strict), id)).set_directed(directed)
+        Self(
+            GraphImpl::new(GraphImplKind::Root(strict, SelfLoops::default()), id),
+            None,
+        )
+        .set_directed(directed)
     }
 
     pub fn is_strict(&self) -> bool {
         match self.0.kind {
-            GraphImplKind::Root(v) => v,
+            GraphImplKind::Root(v, _) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn self_loops(&self) -> SelfLoops {
+        match self.0.kind {
+            GraphImplKind::Root(_, v) => v,
             _ => unreachable!(),
         }
     }
 
+    ///
+    /// Set the policy for self-loops in a strict graph.
+    ///
+    pub fn set_self_loops(self, self_loops: SelfLoops) -> Self {
+        let mut self_mut = self;
+        self_mut.0.kind = GraphImplKind::Root(self_mut.is_strict(), self_loops);
+        self_mut.edges_changed();
+        self_mut
+    }
+
+    ///
+    /// Convert this graph into a strict graph, merging duplicate edges and handling self-loops
+    /// according to `self_loops`.
+    ///
+    pub fn into_strict(self) -> Result<Self, Error> {
+        let mut self_mut = self;
+        self_mut.0.strict_merge(self_mut.self_loops())?;
+        self_mut.0.kind = GraphImplKind::Root(true, self_mut.self_loops());
+        self_mut.1 = None;
+        Ok(self_mut)
+    }
+
+    ///
+    /// Add `edge`, returning an error if this is a strict graph that rejects self-loops and
+    /// `edge` is a self-loop.
+    ///
+    pub fn try_add_edge(self, edge: Edge) -> Result<Self, Error> {
+        self.check_self_loop(&edge)?;
+        Ok(self.add_edge(edge))
+    }
+
+    ///
+    /// Insert `edge`, returning an error if this is a strict graph that rejects self-loops
+    /// and `edge` is a self-loop.
+    ///
+    pub fn try_insert_edge(&mut self, edge: Edge) -> Result<(), Error> {
+        self.check_self_loop(&edge)?;
+        self.insert_edge(edge);
+        Ok(())
+    }
+
+    ///
+    /// Checks that a strict graph that rejects self-loops does not contain any.
+    ///
+    pub fn check_self_loops(&self) -> Result<(), Error> {
+        if self.rejects_self_loops() {
+            if let Some(edge) = self
+                .all_edges()
+                .find(|e| e.item().head() == e.item().tail())
+            {
+                return Err(self_loop_error(edge.item().head()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn edge_mut(&mut self, from: &Identifier, to: &Identifier) -> Option<EdgeMut<'_>> {
+        let directed = self.0.directed;
+        let position = self
+            .all_edges()
+            .position(|e| connects(e.item(), from, to, directed))?;
+        Some(EdgeMut {
+            root: self,
+            position,
+        })
+    }
+
+    pub fn sub_graph_mut(&mut self, id: &Identifier) -> Option<SubGraphMut<'_>> {
+        let _ = self.0.find_sub_graph(id)?;
+        Some(SubGraphMut {
+            root: self,
+            id: id.clone(),
+        })
+    }
+
     pub fn is_directed(&self) -> bool {
         self.0.directed
     }
+
+    ///
+    /// Change the direction of this graph and every edge within it; a strict graph merges any
+    /// edges that become duplicates.
+    ///
+    pub(crate) fn convert_direction(self, directed: bool) -> Self {
+        let mut self_mut = self.set_directed(directed);
+        self_mut.edges_changed();
+        self_mut
+    }
+
+    pub(crate) fn filtered(
+        &self,
+        id: Identifier,
+        node_predicate: &dyn Fn(&Identifier) -> bool,
+        edge_predicate: &dyn Fn(&Edge) -> bool,
+    ) -> Self {
+        let mut inner = self.0.filtered(node_predicate, edge_predicate);
+        inner.id = id;
+        Self(inner, None)
+    }
+
+    ///
+    /// Calls `f` with the edge list of this graph and of each nested sub-graph in turn; a
+    /// strict graph then merges any edges that `f` added or changed.
+    ///
+    pub(crate) fn for_each_edge_list_mut(&mut self, f: &mut dyn FnMut(&mut Vec<Edge>)) {
+        self.0.for_each_edge_list_mut(f);
+        self.edges_changed();
+    }
+
+    fn rejects_self_loops(&self) -> bool {
+        self.is_strict() && self.self_loops() == SelfLoops::Reject
+    }
+
+    fn check_self_loop(&self, edge: &Edge) -> Result<(), Error> {
+        if self.rejects_self_loops() && edge.head() == edge.tail() {
+            Err(self_loop_error(edge.head()))
+        } else {
+            Ok(())
+        }
+    }
+
+    ///
+    /// The self-loop policy for changes that cannot fail, which keep rejected self-loops.
+    ///
+    fn merge_self_loops(&self) -> SelfLoops {
+        match self.self_loops() {
+            SelfLoops::Reject => SelfLoops::Allow,
+            self_loops => self_loops,
+        }
+    }
+
+    ///
+    /// Called after any change that may add or rewrite edges anywhere in the graph; a strict
+    /// graph merges all duplicate edges.
+    ///
+    fn edges_changed(&mut self) {
+        if self.is_strict() {
+            let _ = self.0.strict_merge(self.merge_self_loops());
+            self.1 = None;
+        }
+    }
+
+    ///
+    /// Called after edges are removed, which moves those that follow them.
+    ///
+    fn edges_removed(&mut self) {
+        self.1 = None;
+    }
+
+    fn push_edge(&mut self, edge: Edge) {
+        if !self.is_strict() {
+            self.0.edges.push(edge);
+        } else if let Some(edge) = self.admit_edge(edge, Some(self.0.edges.len())) {
+            self.0.edges.push(edge);
+        }
+    }
+
+    fn push_sub_graph(&mut self, sub_graph: SubGraphKind) {
+        let mut sub_graph = sub_graph;
+        if self.is_strict() {
+            let _ = sub_graph.strict_merge(self.merge_self_loops());
+            sub_graph.for_each_edge_list_mut(&mut |edges: &mut Vec<Edge>| {
+                let incoming = std::mem::take(edges);
+                edges.extend(
+                    incoming
+                        .into_iter()
+                        .filter_map(|edge| self.admit_edge(edge, None)),
+                );
+            });
+        }
+        self.0.sub_graphs.push(sub_graph);
+    }
+
+    ///
+    /// Returns `edge` if it is new to this strict graph, recording it at `position` in the root
+    /// graph's own edges, or `None` if it was merged into an existing edge or dropped.
+    ///
+    fn admit_edge(&mut self, edge: Edge, position: Option<usize>) -> Option<Edge> {
+        let directed = self.0.directed;
+        let key = edge_key(&edge, directed);
+        if key.0 == key.1 && self.self_loops() == SelfLoops::Drop {
+            return None;
+        }
+        let location = self.edge_index().0.get(&key).copied();
+        let existing = match location {
+            None => None,
+            Some(Some(position)) => self.0.edges.get_mut(position),
+            Some(None) => self.0.edge_mut(&key.0, &key.1),
+        };
+        match existing {
+            Some(existing) => {
+                if let Some(incoming) = edge.attributes() {
+                    merge_attributes(
+                        existing.attributes_or_default_mut().as_mut(),
+                        incoming.as_ref(),
+                    );
+                }
+                None
+            }
+            None => {
+                let _ = self.edge_index().0.insert(key, position);
+                Some(edge)
+            }
+        }
+    }
+
+    fn edge_index(&mut self) -> &mut EdgeIndex {
+        if self.1.is_none() {
+            let directed = self.0.directed;
+            let own = self.0.edges.len();
+            let mut index = EdgeIndex::default();
+            let mut position = 0;
+            self.0.for_each_edge_mut(&mut |edge: &mut Edge| {
+                let _ = index.0.insert(
+                    edge_key(edge, directed),
+                    (position < own).then_some(position),
+                );
+                position += 1;
+            });
+            self.1 = Some(index);
+        }
+        self.1.as_mut().unwrap()
+    }
+
+    pub(crate) fn into_cluster(self, id: Identifier) -> Cluster {
+        Cluster::from_inner(GraphImpl {
+            kind: GraphImplKind::Cluster,
+            directed: self.0.directed,
+            id,
+            attributes: None,
+            default_graph_attributes: None,
+            default_node_attributes: None,
+            default_edge_attributes: None,
+            nodes: self.0.nodes,
+            edges: self.0.edges,
+            sub_graphs: self.0.sub_graphs,
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Deref for SubGraphMut<'_> {
+    type Target = SubGraphKind;
+
+    fn deref(&self) -> &Self::Target {
+        self.root.0.find_sub_graph(&self.id).unwrap()
+    }
+}
+
+impl DerefMut for SubGraphMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.root.0.sub_graph_mut(&self.id).unwrap()
+    }
+}
+
+impl Drop for SubGraphMut<'_> {
+    fn drop(&mut self) {
+        self.root.edges_changed();
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Deref for EdgeMut<'_> {
+    type Target = Edge;
+
+    fn deref(&self) -> &Self::Target {
+        self.root.all_edges().nth(self.position).unwrap().item()
+    }
+}
+
+impl DerefMut for EdgeMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let mut position = self.position;
+        self.root.0.nth_edge_mut(&mut position).unwrap()
+    }
+}
+
+impl Drop for EdgeMut<'_> {
+    fn drop(&mut self) {
+        self.root.edges_changed();
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
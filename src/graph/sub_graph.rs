@@ -12,6 +12,7 @@ use crate::graph::{
     display_graph_common, Edges, Graph, GraphImpl, GraphImplKind, Nodes, SubGraphKind, SubGraphs,
 };
 use crate::{Edge, Identified, Identifier, Node};
+use std::collections::HashMap;
 use std::fmt::Display;
 
 // ------------------------------------------------------------------------------------------------
@@ -49,7 +50,11 @@ impl Default for SubGraph {
 
 impl Display for SubGraph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "subgraph {} {{", self.id())?;
+        if self.is_anonymous() {
+            writeln!(f, "{{")?;
+        } else {
+            writeln!(f, "subgraph {} {{", self.id())?;
+        }
         display_graph_common(self, f)?;
         writeln!(f, "}}")
     }
@@ -59,11 +64,55 @@ impl_graph_trait_for!(SubGraph, GraphAttributes);
 
 impl SubGraph {
     pub fn anonymous() -> Self {
-        Self(GraphImpl::anonymous(GraphImplKind::Graph))
+        Self(GraphImpl::anonymous(GraphImplKind::Graph(true)))
     }
 
     pub fn new(id: Identifier) -> Self {
-        Self(GraphImpl::new(GraphImplKind::Graph, id))
+        Self(GraphImpl::new(GraphImplKind::Graph(false), id))
+    }
+
+    ///
+    /// An anonymous sub-graph is written as a bare `{ ... }` block; its identifier is generated
+    /// and only used to find it within the graph.
+    ///
+    pub fn is_anonymous(&self) -> bool {
+        matches!(self.0.kind, GraphImplKind::Graph(true))
+    }
+
+    pub fn edge_mut(&mut self, from: &Identifier, to: &Identifier) -> Option<&mut Edge> {
+        self.0.edge_mut(from, to)
+    }
+
+    pub fn sub_graph_mut(&mut self, id: &Identifier) -> Option<&mut SubGraphKind> {
+        self.0.sub_graph_mut(id)
+    }
+
+    #[inline]
+    fn push_edge(&mut self, edge: Edge) {
+        self.0.edges.push(edge);
+    }
+
+    #[inline]
+    fn push_sub_graph(&mut self, sub_graph: SubGraphKind) {
+        self.0.sub_graphs.push(sub_graph);
+    }
+
+    #[inline]
+    fn edges_changed(&mut self) {}
+
+    #[inline]
+    fn edges_removed(&mut self) {}
+
+    pub(super) fn from_inner(inner: GraphImpl<GraphAttributes>) -> Self {
+        Self(inner)
+    }
+
+    pub(super) fn inner(&self) -> &GraphImpl<GraphAttributes> {
+        &self.0
+    }
+
+    pub(super) fn inner_mut(&mut self) -> &mut GraphImpl<GraphAttributes> {
+        &mut self.0
     }
 }
 
@@ -0,0 +1,519 @@
+/*!
+Generates a legend describing the styles used in a graph.
+
+A `LegendBuilder` holds human descriptions of individual styles, a node shape, a fill color, an
+edge style or an arrow type, or of whole classes of elements. Building a legend for a graph
+collects the distinct styles set on its nodes and edges, either directly or through the root
+graph's default node and edge attributes, and pairs each with its description. An element with
+a described class is explained by that class, so its individual styles need no description of
+their own; any other style without a description is reported, so a legend never silently omits
+something.
+
+The legend is either a cluster containing one sample node or edge per entry, or a single node
+with an HTML table label. Either way its nodes are placed using a `rank` constraint, at the
+bottom of the graph by default.
+
+# Example
+
+ */
+
+use crate::attributes::{
+    ArrowType, Attribute, ClusterAttributes, Color, ColorOrList, Direction, EdgeAttributes,
+    EdgeStyles, LabelString, NodeAttributes, NodeStyles, RankType, Shape, Style, Styled,
+};
+use crate::error::{invalid_state, Error};
+use crate::graph::{Cluster, Graph};
+use crate::stylesheet::ElementKind;
+use crate::{Edge, Identifier, Node, RootGraph};
+use std::fmt::Display;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A style that may appear in a legend.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum LegendKey {
+    Shape(Shape),
+    FillColor(ColorOrList),
+    EdgeStyle(EdgeStyles),
+    Arrow(ArrowType),
+    Class(String),
+}
+
+///
+/// Whether a legend is drawn as a cluster of sample elements or as a single HTML-table node.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LegendForm {
+    #[default]
+    Cluster,
+    Table,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegendBuilder {
+    id: Identifier,
+    title: Option<String>,
+    form: LegendForm,
+    placement: RankType,
+    descriptions: Vec<(LegendKey, String)>,
+}
+
+///
+/// The legend built for a particular graph; its entries are in the order the styles are first
+/// used in the graph.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Legend {
+    id: Identifier,
+    title: Option<String>,
+    form: LegendForm,
+    placement: RankType,
+    entries: Vec<Entry>,
+    undescribed: Vec<LegendKey>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    key: LegendKey,
+    description: String,
+    kind: ElementKind,
+    sample: Vec<Attribute>,
+}
+
+#[derive(Clone, Debug)]
+struct Usage {
+    key: LegendKey,
+    kind: ElementKind,
+    sample: Vec<Attribute>,
+    unexplained: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for LegendKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shape(v) => write!(f, "shape={}", v),
+            Self::FillColor(v) => write!(f, "fillcolor={}", v),
+            Self::EdgeStyle(v) => write!(f, "style={}", v),
+            Self::Arrow(v) => write!(f, "arrow={}", v),
+            Self::Class(v) => write!(f, "class={}", v),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LegendBuilder {
+    pub fn new(id: Identifier) -> Self {
+        Self {
+            id,
+            title: None,
+            form: LegendForm::default(),
+            placement: RankType::Sink,
+            descriptions: Vec::default(),
+        }
+    }
+
+    pub fn title(self, title: &str) -> Self {
+        let mut self_mut = self;
+        self_mut.title = Some(title.to_string());
+        self_mut
+    }
+
+    pub fn form(self, form: LegendForm) -> Self {
+        let mut self_mut = self;
+        self_mut.form = form;
+        self_mut
+    }
+
+    ///
+    /// Sets the rank constraint used to place the legend, `sink` by default.
+    ///
+    pub fn placement(self, placement: RankType) -> Self {
+        let mut self_mut = self;
+        self_mut.placement = placement;
+        self_mut
+    }
+
+    ///
+    /// Describes `key`, replacing any previous description.
+    ///
+    pub fn describe(self, key: LegendKey, description: &str) -> Self {
+        let mut self_mut = self;
+        self_mut.descriptions.retain(|(k, _)| *k != key);
+        self_mut.descriptions.push((key, description.to_string()));
+        self_mut
+    }
+
+    pub fn describe_class(self, class: &str, description: &str) -> Self {
+        self.describe(LegendKey::Class(class.to_string()), description)
+    }
+
+    pub fn description(&self, key: &LegendKey) -> Option<&str> {
+        self.descriptions
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, d)| d.as_str())
+    }
+
+    ///
+    /// Builds the legend for `graph`, recording any styles used without a description.
+    ///
+    pub fn build(&self, graph: &RootGraph) -> Legend {
+        let mut usages: Vec<Usage> = Vec::default();
+        let node_defaults = graph
+            .default_node_attributes()
+            .map_or(&[][..], |a| a.as_ref());
+        for node in graph.all_nodes() {
+            let attributes = effective_attributes(
+                node_defaults,
+                node.item().attributes().map_or(&[], |a| a.as_ref()),
+            );
+            self.record(&mut usages, ElementKind::Node, attributes);
+        }
+        let edge_defaults = graph
+            .default_edge_attributes()
+            .map_or(&[][..], |a| a.as_ref());
+        for edge in graph.all_edges() {
+            let attributes = effective_attributes(
+                edge_defaults,
+                edge.item().attributes().map_or(&[], |a| a.as_ref()),
+            );
+            self.record(&mut usages, ElementKind::Edge, attributes);
+        }
+
+        let mut entries: Vec<Entry> = Vec::default();
+        let mut undescribed: Vec<LegendKey> = Vec::default();
+        for usage in usages {
+            match self.description(&usage.key) {
+                Some(description) => entries.push(Entry {
+                    // Only a class is drawn with the other styles of the element using it.
+                    sample: match usage.key {
+                        LegendKey::Class(_) => usage.sample,
+                        _ => Vec::default(),
+                    },
+                    key: usage.key,
+                    description: description.to_string(),
+                    kind: usage.kind,
+                }),
+                None if usage.unexplained && !matches!(usage.key, LegendKey::Class(_)) => {
+                    undescribed.push(usage.key)
+                }
+                None => {}
+            }
+        }
+        Legend {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            form: self.form,
+            placement: self.placement,
+            entries,
+            undescribed,
+        }
+    }
+
+    ///
+    /// Builds the legend for `graph` and adds it to the graph, failing if any style used in the
+    /// graph has no description.
+    ///
+    pub fn apply(&self, graph: RootGraph) -> Result<RootGraph, Error> {
+        let legend = self.build(&graph);
+        if legend.is_complete() {
+            Ok(legend.add_to(graph))
+        } else {
+            Err(invalid_state(
+                LEGEND_TYPE_NAME,
+                &format!(
+                    "no description for {}",
+                    legend
+                        .undescribed
+                        .iter()
+                        .map(LegendKey::to_string)
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            ))
+        }
+    }
+
+    fn record(&self, usages: &mut Vec<Usage>, kind: ElementKind, attributes: Vec<Attribute>) {
+        let classes: Vec<String> = attributes
+            .iter()
+            .filter_map(|a| match a {
+                Attribute::Class(classes) => Some(classes.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        let explained = classes
+            .iter()
+            .any(|c| self.description(&LegendKey::Class(c.clone())).is_some());
+        let keys = classes
+            .into_iter()
+            .map(LegendKey::Class)
+            .chain(attributes.iter().flat_map(|a| style_keys(kind, a)));
+        for key in keys {
+            match usages.iter_mut().find(|u| u.key == key) {
+                Some(usage) => usage.unexplained |= !explained,
+                None => usages.push(Usage {
+                    key,
+                    kind,
+                    sample: attributes
+                        .iter()
+                        .filter(|a| is_sample_attribute(a))
+                        .cloned()
+                        .collect(),
+                    unexplained: !explained,
+                }),
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Legend {
+    pub fn entries(&self) -> impl Iterator<Item = (&LegendKey, &str)> {
+        self.entries
+            .iter()
+            .map(|e| (&e.key, e.description.as_str()))
+    }
+
+    ///
+    /// Returns the styles used in the graph that have no description and are not explained by
+    /// a described class.
+    ///
+    pub fn undescribed(&self) -> &[LegendKey] {
+        &self.undescribed
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.undescribed.is_empty()
+    }
+
+    ///
+    /// Returns the legend as a cluster holding a sample node, or a pair of nodes joined by a
+    /// sample edge, for each entry.
+    ///
+    pub fn to_cluster(&self) -> Cluster {
+        let mut cluster = Cluster::new(self.id.clone());
+        if let Some(title) = &self.title {
+            cluster = cluster
+                .set_attributes(ClusterAttributes::default().label(LabelString::plain_text(title)));
+        }
+        let mut ids: Vec<Identifier> = Vec::default();
+        for (i, entry) in self.entries.iter().enumerate() {
+            let id = self.entry_id(i, "");
+            let label = LabelString::plain_text(&entry.description);
+            if entry.kind == ElementKind::Edge {
+                let to = self.entry_id(i, "_to");
+                let mut attributes = EdgeAttributes::default();
+                *attributes.as_mut() = entry.sample.clone();
+                match &entry.key {
+                    LegendKey::EdgeStyle(style) => {
+                        set_attribute(
+                            attributes.as_mut(),
+                            Attribute::Style(vec![Style::Edge(*style)]),
+                        );
+                    }
+                    LegendKey::Arrow(arrow) => {
+                        set_attribute(attributes.as_mut(), Attribute::ArrowHead(arrow.clone()));
+                        set_attribute(
+                            attributes.as_mut(),
+                            Attribute::Direction(Direction::Forward),
+                        );
+                    }
+                    _ => {}
+                }
+                cluster = cluster
+                    .add_node(
+                        Node::new(id.clone()).set_attributes(
+                            NodeAttributes::default()
+                                .shape(Shape::PlainText)
+                                .label(label),
+                        ),
+                    )
+                    .add_node(
+                        Node::new(to.clone())
+                            .set_attributes(NodeAttributes::default().shape(Shape::Point)),
+                    )
+                    .add_edge(Edge::new(id.clone(), to.clone()).set_attributes(attributes));
+                ids.push(id);
+                ids.push(to);
+            } else {
+                let mut attributes = NodeAttributes::default();
+                *attributes.as_mut() = entry.sample.clone();
+                match &entry.key {
+                    LegendKey::Shape(shape) => {
+                        shape.apply_to(attributes.as_mut());
+                    }
+                    LegendKey::FillColor(color) => {
+                        set_attribute(attributes.as_mut(), Attribute::FillColor(color.clone()));
+                        set_attribute(
+                            attributes.as_mut(),
+                            Attribute::Style(vec![Style::Node(NodeStyles::Filled)]),
+                        );
+                        if let ColorOrList::Color(color) = color {
+                            if let Ok(font_color) = color.contrasting() {
+                                set_attribute(
+                                    attributes.as_mut(),
+                                    Attribute::FontColor(font_color),
+                                );
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                set_attribute(attributes.as_mut(), Attribute::Label(label));
+                cluster = cluster.add_node(Node::new(id.clone()).set_attributes(attributes));
+                ids.push(id);
+            }
+        }
+        cluster.add_rank(self.placement, ids)
+    }
+
+    ///
+    /// Returns the legend as a single plain-text node labeled with an HTML table, with one row
+    /// per entry.
+    ///
+    pub fn to_table_node(&self) -> Node {
+        let mut table = String::from(
+            "<TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\" CELLPADDING=\"4\">",
+        );
+        if let Some(title) = &self.title {
+            table.push_str(&format!(
+                "<TR><TD COLSPAN=\"2\"><B>{}</B></TD></TR>",
+                escape_html(title)
+            ));
+        }
+        for entry in &self.entries {
+            let swatch = match &entry.key {
+                LegendKey::FillColor(color) => format!(
+                    "<TD BGCOLOR=\"{}\"> </TD>",
+                    escape_html(&first_color(color).to_string())
+                ),
+                LegendKey::Shape(v) => format!("<TD>{}</TD>", escape_html(v.as_ref())),
+                LegendKey::EdgeStyle(v) => format!("<TD>{}</TD>", v),
+                LegendKey::Arrow(v) => format!("<TD>{}</TD>", v),
+                LegendKey::Class(v) => format!("<TD>{}</TD>", escape_html(v)),
+            };
+            table.push_str(&format!(
+                "<TR>{}<TD ALIGN=\"LEFT\">{}</TD></TR>",
+                swatch,
+                escape_html(&entry.description)
+            ));
+        }
+        table.push_str("</TABLE>");
+        Node::new(self.id.clone()).set_attributes(
+            NodeAttributes::default()
+                .shape(Shape::PlainText)
+                .label(LabelString::html(&table)),
+        )
+    }
+
+    ///
+    /// Adds the legend to `graph`, placed with this legend's rank constraint. A cluster legend
+    /// also turns on `newrank` so that the constraint applies within the cluster.
+    ///
+    pub fn add_to(&self, graph: RootGraph) -> RootGraph {
+        match self.form {
+            LegendForm::Cluster => {
+                let mut attributes = graph.attributes().cloned().unwrap_or_default();
+                if !attributes.as_ref().iter().any(|a| a.name() == "newrank") {
+                    attributes = attributes.use_new_ranking();
+                }
+                graph
+                    .set_attributes(attributes)
+                    .add_sub_graph(self.to_cluster())
+            }
+            LegendForm::Table => graph
+                .add_node(self.to_table_node())
+                .add_rank(self.placement, vec![self.id.clone()]),
+        }
+    }
+
+    fn entry_id(&self, index: usize, suffix: &str) -> Identifier {
+        self.id.derived("", &format!("_{}{}", index, suffix))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const LEGEND_TYPE_NAME: &str = "Legend";
+
+fn effective_attributes(defaults: &[Attribute], explicit: &[Attribute]) -> Vec<Attribute> {
+    let mut attributes: Vec<Attribute> = Vec::default();
+    for attribute in defaults.iter().chain(explicit) {
+        set_attribute(&mut attributes, attribute.clone());
+    }
+    attributes
+}
+
+fn style_keys(kind: ElementKind, attribute: &Attribute) -> Vec<LegendKey> {
+    match (kind, attribute) {
+        (ElementKind::Node, Attribute::Shape(v)) => vec![LegendKey::Shape(v.clone())],
+        (ElementKind::Node, Attribute::FillColor(v)) => vec![LegendKey::FillColor(v.clone())],
+        (ElementKind::Edge, Attribute::Style(styles)) => styles
+            .iter()
+            .filter_map(|s| match s {
+                Style::Edge(s) => Some(LegendKey::EdgeStyle(*s)),
+                _ => None,
+            })
+            .collect(),
+        (ElementKind::Edge, Attribute::ArrowHead(v) | Attribute::ArrowTail(v)) => {
+            vec![LegendKey::Arrow(v.clone())]
+        }
+        _ => Vec::default(),
+    }
+}
+
+fn is_sample_attribute(attribute: &Attribute) -> bool {
+    matches!(
+        attribute,
+        Attribute::Class(_)
+            | Attribute::Color(_)
+            | Attribute::FillColor(_)
+            | Attribute::FontColor(_)
+            | Attribute::PenWidth(_)
+            | Attribute::Shape(_)
+            | Attribute::Style(_)
+            | Attribute::ArrowHead(_)
+            | Attribute::ArrowTail(_)
+            | Attribute::Direction(_)
+    )
+}
+
+fn set_attribute(attributes: &mut Vec<Attribute>, attribute: Attribute) {
+    attributes.retain(|a| a.name() != attribute.name());
+    attributes.push(attribute);
+}
+
+fn first_color(color: &ColorOrList) -> Color {
+    match color {
+        ColorOrList::Color(v) => v.clone(),
+        ColorOrList::List(vs) => vs
+            .first()
+            .map(|v| v.color().clone())
+            .unwrap_or_else(|| Color::Name("black".to_string())),
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
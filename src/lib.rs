@@ -7,6 +7,9 @@ More detailed description, with
 
 # Features
 
+* `json`; enables loading themes from JSON, see [`theme::Theme::from_json_str`].
+* `toml`; enables loading themes from TOML, see [`theme::Theme::from_toml_str`].
+
  */
 
 #![warn(
@@ -63,7 +66,7 @@ pub trait Identified {
     fn id(&self) -> &Identifier;
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Identifier(String);
 
 // ------------------------------------------------------------------------------------------------
@@ -118,6 +121,38 @@ impl Identifier {
     pub fn prefix(self, prefix: Identifier) -> Self {
         Self(format!("{}{}", prefix, self))
     }
+
+    pub fn suffix(self, suffix: Identifier) -> Self {
+        Self(format!("{}{}", self, suffix))
+    }
+
+    ///
+    /// The text of this identifier without any surrounding quotes or angle brackets.
+    ///
+    pub fn unquoted(&self) -> &str {
+        let s = self.0.as_str();
+        if s.len() > 1
+            && ((s.starts_with('"') && s.ends_with('"'))
+                || (s.starts_with('<') && s.ends_with('>')))
+        {
+            &s[1..s.len() - 1]
+        } else {
+            s
+        }
+    }
+
+    ///
+    /// A new identifier from `prefix`, the unquoted text of this identifier, and `suffix`; it is
+    /// quoted if the combined text is not itself a valid identifier.
+    ///
+    pub fn derived(&self, prefix: &str, suffix: &str) -> Self {
+        let text = format!("{}{}{}", prefix, self.unquoted(), suffix);
+        if is_valid_id_string(&text) {
+            Self(text)
+        } else {
+            Self(quote_id_string(&text))
+        }
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -144,6 +179,21 @@ fn is_valid_id_string(s: &str) -> bool {
     }
 }
 
+fn quote_id_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    let mut escaped = false;
+    for c in s.chars() {
+        if c == '"' && !escaped {
+            quoted.push('\\');
+        }
+        escaped = c == '\\' && !escaped;
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
 // ------------------------------------------------------------------------------------------------
 // Modules
 // ------------------------------------------------------------------------------------------------
@@ -161,6 +211,22 @@ pub use edge::Edge;
 
 pub mod attributes;
 
+pub mod algo;
+
+pub mod diff;
+
+pub mod fonts;
+
+pub mod legend;
+
+pub mod scale;
+
+pub mod stylesheet;
+
+pub mod theme;
+
+pub mod transform;
+
 pub mod visitor;
 
 pub mod writer;
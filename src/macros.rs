@@ -90,6 +90,12 @@ macro_rules! styled_common_impl {
             }
         }
 
+        impl AsMut<Vec<Attribute>> for $type {
+            fn as_mut(&mut self) -> &mut Vec<Attribute> {
+                &mut self.0
+            }
+        }
+
         impl Attributes for $type {}
 
         impl $type {
@@ -118,6 +124,14 @@ macro_rules! attribute_setter {
     };
 }
 
+macro_rules! length_attribute_setter {
+    ($name:ident, $variant:ident, $convert:ident) => {
+        pub fn $name(self, $name: Length) -> Self {
+            self.push(Attribute::$variant($name.$convert()))
+        }
+    };
+}
+
 macro_rules! style_attribute_setter {
     ($style_variant:ident, $style_type:ty) => {
         pub fn style(self, styles: Vec<$style_type>) -> Self {
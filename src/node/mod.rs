@@ -90,6 +90,10 @@ impl Styled<NodeAttributes> for Node {
         self.attributes.as_ref()
     }
 
+    fn attributes_mut(&mut self) -> Option<&mut NodeAttributes> {
+        self.attributes.as_mut()
+    }
+
     fn set_attributes(self, attributes: NodeAttributes) -> Self
     where
         Self: Sized,
@@ -140,6 +144,14 @@ impl Node {
             self.id().to_string()
         }
     }
+
+    pub(crate) fn set_id(&mut self, id: Identifier) {
+        self.id = id;
+    }
+
+    pub(crate) fn attributes_or_default_mut(&mut self) -> &mut NodeAttributes {
+        self.attributes.get_or_insert_with(Default::default)
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -0,0 +1,579 @@
+/*!
+Maps data values onto visual attributes.
+
+A `ContinuousScale` maps numbers from a domain, linearly or logarithmically, onto either a range
+of numbers or a multi-stop color gradient; a `CategoricalScale` maps category names onto fixed
+values. A `ScaleMapping` binds a scale to the `Channel`, the attribute, it drives and applies it
+to every node or edge in a graph for which a value is provided. Values outside the domain of a
+continuous scale are clamped to it.
+
+A mapping may also produce a legend, a cluster with one sample node per category or per tick of
+the domain, each styled with the mapped value and labeled with the value it stands for.
+
+# Example
+
+ */
+
+use crate::attributes::{
+    Attribute, ClusterAttributes, Color, ColorOrList, ColorScheme, Double, LabelString,
+    NodeAttributes, NodeStyles, Positive, Shape, Style, Styled,
+};
+use crate::error::{invalid_state, invalid_value, Error};
+use crate::graph::{Cluster, Graph};
+use crate::{Identified, Identifier, Node, RootGraph};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The attribute driven by a scale.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Channel {
+    Color,
+    FillColor,
+    FontColor,
+    PenWidth,
+    Width,
+    Height,
+    FontSize,
+}
+
+///
+/// A value produced by a scale, either a color or a number depending on the scale's range.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScaleValue {
+    Color(Color),
+    Number(Double),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    Log,
+}
+
+///
+/// A scale over a set of values, implemented by `ContinuousScale` over numbers and by
+/// `CategoricalScale` over category names.
+///
+pub trait ValueScale {
+    type Input: ?Sized;
+
+    ///
+    /// Returns the value that `input` maps to.
+    ///
+    fn map(&self, input: &Self::Input) -> Result<ScaleValue, Error>;
+
+    ///
+    /// Returns the labeled values a legend for this scale should show.
+    ///
+    fn legend_entries(&self) -> Result<Vec<(String, ScaleValue)>, Error>;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContinuousScale {
+    domain: (Double, Double),
+    interpolation: Interpolation,
+    range: Range,
+    ticks: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CategoricalScale {
+    categories: Vec<(String, ScaleValue)>,
+    default: Option<ScaleValue>,
+}
+
+///
+/// Binds a scale to the channel it drives, and optionally to a legend.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleMapping<S> {
+    scale: S,
+    channel: Channel,
+    legend: Option<(Identifier, String)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Range {
+    Numbers(Double, Double),
+    Colors(Vec<(Double, Color)>),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<Color> for ScaleValue {
+    fn from(v: Color) -> Self {
+        Self::Color(v)
+    }
+}
+
+impl From<Double> for ScaleValue {
+    fn from(v: Double) -> Self {
+        Self::Number(v)
+    }
+}
+
+impl ScaleValue {
+    fn to_attribute(&self, channel: Channel) -> Result<Attribute, Error> {
+        Ok(match (channel, self) {
+            (Channel::Color, Self::Color(v)) => Attribute::Color(ColorOrList::Color(v.clone())),
+            (Channel::FillColor, Self::Color(v)) => {
+                Attribute::FillColor(ColorOrList::Color(v.clone()))
+            }
+            (Channel::FontColor, Self::Color(v)) => Attribute::FontColor(v.clone()),
+            (Channel::PenWidth, Self::Number(v)) => Attribute::PenWidth(Positive::try_from(*v)?),
+            (Channel::Width, Self::Number(v)) => Attribute::Width(Positive::try_from(*v)?),
+            (Channel::Height, Self::Number(v)) => Attribute::Height(Positive::try_from(*v)?),
+            (Channel::FontSize, Self::Number(v)) => Attribute::FontSize(*v),
+            _ => return Err(invalid_value(SCALE_VALUE_TYPE_NAME, self)),
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ContinuousScale {
+    ///
+    /// A scale over `min..=max`, interpolated linearly, with the range `0.0..=1.0`.
+    ///
+    pub fn linear(min: Double, max: Double) -> Self {
+        Self {
+            domain: (min, max),
+            interpolation: Interpolation::Linear,
+            range: Range::Numbers(0.0, 1.0),
+            ticks: DEFAULT_TICKS,
+        }
+    }
+
+    ///
+    /// A scale over `min..=max`, interpolated logarithmically, with the range `0.0..=1.0`; both
+    /// bounds must be greater than zero.
+    ///
+    pub fn log(min: Double, max: Double) -> Self {
+        Self {
+            interpolation: Interpolation::Log,
+            ..Self::linear(min, max)
+        }
+    }
+
+    ///
+    /// Sets the domain to the smallest and largest of `values`.
+    ///
+    pub fn fit<I>(self, values: I) -> Self
+    where
+        I: IntoIterator<Item = Double>,
+    {
+        let mut self_mut = self;
+        let (min, max) = values
+            .into_iter()
+            .fold((Double::INFINITY, Double::NEG_INFINITY), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+        self_mut.domain = (min, max);
+        self_mut
+    }
+
+    ///
+    /// Maps the domain onto the numbers `min..=max`.
+    ///
+    pub fn numbers(self, min: Double, max: Double) -> Self {
+        let mut self_mut = self;
+        self_mut.range = Range::Numbers(min, max);
+        self_mut
+    }
+
+    ///
+    /// Maps the domain onto a gradient through `colors`, spaced evenly.
+    ///
+    pub fn colors(self, colors: Vec<Color>) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as Double;
+        self.color_stops(
+            colors
+                .into_iter()
+                .enumerate()
+                .map(|(i, color)| (i as Double / last, color))
+                .collect(),
+        )
+    }
+
+    ///
+    /// Maps the domain onto a gradient through `stops`, each a position in `0.0..=1.0` along the
+    /// domain and the color at that position.
+    ///
+    pub fn color_stops(self, stops: Vec<(Double, Color)>) -> Self {
+        let mut self_mut = self;
+        let mut stops = stops;
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        self_mut.range = Range::Colors(stops);
+        self_mut
+    }
+
+    ///
+    /// Sets the number of evenly spaced values shown in a legend, at least two.
+    ///
+    pub fn legend_ticks(self, ticks: usize) -> Self {
+        let mut self_mut = self;
+        self_mut.ticks = ticks.max(2);
+        self_mut
+    }
+
+    pub fn domain(&self) -> (Double, Double) {
+        self.domain
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    ///
+    /// Returns the position of `value` within the domain, clamped to `0.0..=1.0`.
+    ///
+    fn position(&self, value: Double) -> Result<Double, Error> {
+        let (min, max) = self.domain;
+        if !(min.is_finite() && max.is_finite() && min <= max) {
+            return Err(invalid_state(
+                CONTINUOUS_SCALE_TYPE_NAME,
+                "the domain must be finite with min <= max",
+            ));
+        }
+        if value.is_nan() {
+            return Err(invalid_value(CONTINUOUS_SCALE_TYPE_NAME, &value));
+        }
+        if min == max {
+            return Ok(0.0);
+        }
+        let position = match self.interpolation {
+            Interpolation::Linear => (value - min) / (max - min),
+            Interpolation::Log => {
+                if min <= 0.0 {
+                    return Err(invalid_state(
+                        CONTINUOUS_SCALE_TYPE_NAME,
+                        "a log scale must have a domain greater than zero",
+                    ));
+                }
+                (value.max(min).ln() - min.ln()) / (max.ln() - min.ln())
+            }
+        };
+        Ok(position.clamp(0.0, 1.0))
+    }
+
+    fn value_at(&self, position: Double) -> Double {
+        let (min, max) = self.domain;
+        match self.interpolation {
+            Interpolation::Linear => min + (max - min) * position,
+            Interpolation::Log => (min.ln() + (max.ln() - min.ln()) * position).exp(),
+        }
+    }
+}
+
+impl ValueScale for ContinuousScale {
+    type Input = Double;
+
+    fn map(&self, input: &Double) -> Result<ScaleValue, Error> {
+        let position = self.position(*input)?;
+        match &self.range {
+            Range::Numbers(min, max) => Ok(ScaleValue::Number(min + (max - min) * position)),
+            Range::Colors(stops) => interpolate_colors(stops, position).map(ScaleValue::Color),
+        }
+    }
+
+    fn legend_entries(&self) -> Result<Vec<(String, ScaleValue)>, Error> {
+        let last = (self.ticks - 1) as Double;
+        (0..self.ticks)
+            .map(|i| {
+                let value = self.value_at(i as Double / last);
+                Ok((format_tick(value), self.map(&value)?))
+            })
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl CategoricalScale {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Assigns the colors of `scheme`, in order, to `categories`; the scheme must have at least
+    /// as many colors as there are categories.
+    ///
+    pub fn from_scheme(scheme: &ColorScheme, categories: &[&str]) -> Result<Self, Error> {
+        categories
+            .iter()
+            .enumerate()
+            .try_fold(Self::default(), |scale, (i, category)| {
+                Ok(scale.category(category, Color::named_in(scheme, &(i + 1).to_string())?))
+            })
+    }
+
+    pub fn category<V>(self, name: &str, value: V) -> Self
+    where
+        V: Into<ScaleValue>,
+    {
+        let mut self_mut = self;
+        let value = value.into();
+        match self_mut.categories.iter_mut().find(|(n, _)| n == name) {
+            Some(existing) => existing.1 = value,
+            None => self_mut.categories.push((name.to_string(), value)),
+        }
+        self_mut
+    }
+
+    ///
+    /// Sets the value used for categories that have not been added to this scale.
+    ///
+    pub fn default_value<V>(self, value: V) -> Self
+    where
+        V: Into<ScaleValue>,
+    {
+        let mut self_mut = self;
+        self_mut.default = Some(value.into());
+        self_mut
+    }
+
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        self.categories.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+impl ValueScale for CategoricalScale {
+    type Input = str;
+
+    fn map(&self, input: &str) -> Result<ScaleValue, Error> {
+        self.categories
+            .iter()
+            .find(|(name, _)| name == input)
+            .map(|(_, value)| value)
+            .or(self.default.as_ref())
+            .cloned()
+            .ok_or_else(|| invalid_value(CATEGORICAL_SCALE_TYPE_NAME, &input))
+    }
+
+    fn legend_entries(&self) -> Result<Vec<(String, ScaleValue)>, Error> {
+        Ok(self.categories.clone())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<S> ScaleMapping<S>
+where
+    S: ValueScale,
+{
+    pub fn new(scale: S, channel: Channel) -> Self {
+        Self {
+            scale,
+            channel,
+            legend: None,
+        }
+    }
+
+    ///
+    /// When applied, also add a legend cluster `id` labeled `title` to the graph.
+    ///
+    pub fn with_legend(self, id: Identifier, title: &str) -> Self {
+        let mut self_mut = self;
+        self_mut.legend = Some((id, title.to_string()));
+        self_mut
+    }
+
+    pub fn scale(&self) -> &S {
+        &self.scale
+    }
+
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    ///
+    /// Sets the channel's attribute on every node with an entry in `values`, replacing any value
+    /// the node already has. Filling a node with color also makes it `filled`.
+    ///
+    pub fn apply_to_nodes<V>(
+        &self,
+        graph: RootGraph,
+        values: &HashMap<Identifier, V>,
+    ) -> Result<RootGraph, Error>
+    where
+        V: Borrow<S::Input>,
+    {
+        let mut graph = graph;
+        let mut result = Ok(());
+        graph.for_each_node_mut(|node| {
+            if result.is_err() {
+                return;
+            }
+            if let Some(value) = values.get(node.id()) {
+                result = self.attribute_for(value.borrow()).map(|attribute| {
+                    let attributes = node.attributes_or_default_mut().as_mut();
+                    if self.channel == Channel::FillColor {
+                        ensure_filled(attributes);
+                    }
+                    set_attribute(attributes, attribute);
+                });
+            }
+        });
+        result?;
+        self.add_legend(graph)
+    }
+
+    ///
+    /// Sets the channel's attribute on every edge with an entry in `values`, keyed by the edge's
+    /// head and tail; in undirected graphs an edge also matches the reversed pair.
+    ///
+    pub fn apply_to_edges<V>(
+        &self,
+        graph: RootGraph,
+        values: &HashMap<(Identifier, Identifier), V>,
+    ) -> Result<RootGraph, Error>
+    where
+        V: Borrow<S::Input>,
+    {
+        let mut graph = graph;
+        let directed = graph.is_directed();
+        let mut result = Ok(());
+        graph.for_each_edge_mut(|edge| {
+            if result.is_err() {
+                return;
+            }
+            let key = (edge.head().clone(), edge.tail().clone());
+            let value = values.get(&key).or_else(|| {
+                if directed {
+                    None
+                } else {
+                    values.get(&(key.1.clone(), key.0.clone()))
+                }
+            });
+            if let Some(value) = value {
+                result = self.attribute_for(value.borrow()).map(|attribute| {
+                    set_attribute(edge.attributes_or_default_mut().as_mut(), attribute)
+                });
+            }
+        });
+        result?;
+        self.add_legend(graph)
+    }
+
+    ///
+    /// Returns a legend cluster `id` labeled `title`, with one sample node per legend entry of
+    /// the scale, all on the same rank.
+    ///
+    pub fn legend(&self, id: Identifier, title: &str) -> Result<Cluster, Error> {
+        let mut nodes: Vec<Node> = Vec::default();
+        for (i, (label, value)) in self.scale.legend_entries()?.into_iter().enumerate() {
+            let node_id = id.derived("", &format!("_{}", i));
+            let mut attributes = NodeAttributes::default()
+                .label(LabelString::plain_text(&label))
+                .shape(if self.channel == Channel::FontSize {
+                    Shape::PlainText
+                } else {
+                    Shape::Box
+                });
+            match &value {
+                ScaleValue::Color(color) => {
+                    attributes = attributes
+                        .style(vec![NodeStyles::Filled])
+                        .fill_color(ColorOrList::Color(color.clone()))
+                        .font_color(color.contrasting()?);
+                }
+                ScaleValue::Number(_) => {
+                    set_attribute(attributes.as_mut(), value.to_attribute(self.channel)?);
+                }
+            }
+            nodes.push(Node::new(node_id).set_attributes(attributes));
+        }
+        let ids: Vec<Identifier> = nodes.iter().map(|n| n.id().clone()).collect();
+        Ok(Cluster::new(id)
+            .set_attributes(ClusterAttributes::default().label(LabelString::plain_text(title)))
+            .add_nodes(nodes)
+            .same_rank(ids))
+    }
+
+    fn attribute_for(&self, value: &S::Input) -> Result<Attribute, Error> {
+        self.scale.map(value)?.to_attribute(self.channel)
+    }
+
+    fn add_legend(&self, graph: RootGraph) -> Result<RootGraph, Error> {
+        match &self.legend {
+            Some((id, title)) => Ok(graph.add_sub_graph(self.legend(id.clone(), title)?)),
+            None => Ok(graph),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const SCALE_VALUE_TYPE_NAME: &str = "ScaleValue";
+
+const CONTINUOUS_SCALE_TYPE_NAME: &str = "ContinuousScale";
+
+const CATEGORICAL_SCALE_TYPE_NAME: &str = "CategoricalScale";
+
+const DEFAULT_TICKS: usize = 5;
+
+fn interpolate_colors(stops: &[(Double, Color)], position: Double) -> Result<Color, Error> {
+    let (first, last) = match (stops.first(), stops.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => {
+            return Err(invalid_state(
+                CONTINUOUS_SCALE_TYPE_NAME,
+                "a color range must have at least one color",
+            ))
+        }
+    };
+    if position <= first.0 {
+        return Ok(first.1.clone());
+    }
+    if position >= last.0 {
+        return Ok(last.1.clone());
+    }
+    let i = stops.iter().position(|(p, _)| *p > position).unwrap_or(0);
+    let ((p0, c0), (p1, c1)) = (&stops[i - 1], &stops[i]);
+    let t = (position - p0) / (p1 - p0);
+    let (c0, c1) = (c0.resolve(None)?, c1.resolve(None)?);
+    let mix = |i: usize| (c0[i] as Double + (c1[i] as Double - c0[i] as Double) * t).round() as u8;
+    if c0[3] == 0xff && c1[3] == 0xff {
+        Ok(Color::rgb(mix(0), mix(1), mix(2)))
+    } else {
+        Ok(Color::rgba(mix(0), mix(1), mix(2), mix(3)))
+    }
+}
+
+fn format_tick(value: Double) -> String {
+    let s = format!("{:.2}", value);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn set_attribute(attributes: &mut Vec<Attribute>, attribute: Attribute) {
+    attributes.retain(|a| a.name() != attribute.name());
+    attributes.push(attribute);
+}
+
+fn ensure_filled(attributes: &mut Vec<Attribute>) {
+    let filled = Style::Node(NodeStyles::Filled);
+    match attributes.iter_mut().find_map(|a| match a {
+        Attribute::Style(styles) => Some(styles),
+        _ => None,
+    }) {
+        Some(styles) if !styles.contains(&filled) => styles.push(filled),
+        Some(_) => {}
+        None => attributes.push(Attribute::Style(vec![filled])),
+    }
+}
@@ -0,0 +1,560 @@
+/*!
+Styles a graph declaratively with CSS-like rules.
+
+A `Stylesheet` is an ordered list of rules, each of which pairs a `Selector` with the attributes
+to apply to the nodes, edges or clusters it selects. Selectors match on identifier globs, class
+membership, the clusters an element is nested within, node degree, and existing attribute
+values.
+
+Where more than one rule sets the same attribute on an element the rule with the highest
+specificity wins, and between rules of equal specificity the rule added last wins. Attributes
+set explicitly on an element always take precedence over the stylesheet. A rule only applies the
+attributes that are used by the kind of element selected, so a rule selecting any element may set
+`shape` and it is applied to nodes but not to edges or clusters; the same is true of a `style`
+whose values are not all for that kind of element.
+
+# Example
+
+ */
+
+use crate::attributes::{Attribute, ClusterAttributes, Style, Styled};
+use crate::graph::Graph;
+use crate::{Identified, Identifier, RootGraph};
+use std::collections::HashSet;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The kind of element a selector applies to.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementKind {
+    Node,
+    Edge,
+    Cluster,
+}
+
+///
+/// Selects elements; an element is selected only if it matches every condition.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Selector {
+    kind: Option<ElementKind>,
+    conditions: Vec<Condition>,
+}
+
+///
+/// The specificity of a selector, compared in order of the number of exact identifier
+/// conditions, the number of other conditions, and whether the selector names an element kind.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Specificity(usize, usize, usize);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rule {
+    selector: Selector,
+    attributes: Vec<Attribute>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stylesheet {
+    rules: Vec<Rule>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Condition {
+    Id(String),
+    Head(String),
+    Tail(String),
+    Class(String),
+    Within(Identifier),
+    MinDegree(usize),
+    MaxDegree(usize),
+    HasAttribute(String),
+    Attribute(Attribute),
+}
+
+#[derive(Debug)]
+struct Element<'a> {
+    kind: ElementKind,
+    id: Option<&'a Identifier>,
+    ends: Option<(&'a Identifier, &'a Identifier)>,
+    path: &'a [&'a Identifier],
+    degree: usize,
+    attributes: &'a [Attribute],
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Selector {
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    pub fn nodes() -> Self {
+        Self {
+            kind: Some(ElementKind::Node),
+            ..Default::default()
+        }
+    }
+
+    pub fn edges() -> Self {
+        Self {
+            kind: Some(ElementKind::Edge),
+            ..Default::default()
+        }
+    }
+
+    pub fn clusters() -> Self {
+        Self {
+            kind: Some(ElementKind::Cluster),
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Match nodes and clusters whose identifier matches `pattern`, where `*` matches any
+    /// sequence of characters and `?` any single character. Edges have no identifier and never
+    /// match.
+    ///
+    pub fn id(self, pattern: &str) -> Self {
+        self.condition(Condition::Id(pattern.to_string()))
+    }
+
+    ///
+    /// Match edges whose head matches the glob `pattern`.
+    ///
+    pub fn head(self, pattern: &str) -> Self {
+        self.condition(Condition::Head(pattern.to_string()))
+    }
+
+    ///
+    /// Match edges whose tail matches the glob `pattern`.
+    ///
+    pub fn tail(self, pattern: &str) -> Self {
+        self.condition(Condition::Tail(pattern.to_string()))
+    }
+
+    ///
+    /// Match elements with `class` in their `class` attribute.
+    ///
+    pub fn class(self, class: &str) -> Self {
+        self.condition(Condition::Class(class.to_string()))
+    }
+
+    ///
+    /// Match elements declared, at any depth, within the cluster `id`.
+    ///
+    pub fn within(self, id: Identifier) -> Self {
+        self.condition(Condition::Within(id))
+    }
+
+    ///
+    /// Match nodes with at least `degree` incident edges.
+    ///
+    pub fn min_degree(self, degree: usize) -> Self {
+        self.condition(Condition::MinDegree(degree))
+    }
+
+    ///
+    /// Match nodes with at most `degree` incident edges.
+    ///
+    pub fn max_degree(self, degree: usize) -> Self {
+        self.condition(Condition::MaxDegree(degree))
+    }
+
+    ///
+    /// Match elements that explicitly set the attribute with the DOT name `name`.
+    ///
+    pub fn has_attribute(self, name: &str) -> Self {
+        self.condition(Condition::HasAttribute(name.to_string()))
+    }
+
+    ///
+    /// Match elements that explicitly set `attribute` to the same value.
+    ///
+    pub fn attribute(self, attribute: Attribute) -> Self {
+        self.condition(Condition::Attribute(attribute))
+    }
+
+    pub fn kind(&self) -> Option<ElementKind> {
+        self.kind
+    }
+
+    pub fn specificity(&self) -> Specificity {
+        let exact_ids = self
+            .conditions
+            .iter()
+            .filter(|c| matches!(c, Condition::Id(p) if !is_glob(p)))
+            .count();
+        Specificity(
+            exact_ids,
+            self.conditions.len() - exact_ids,
+            self.kind.is_some() as usize,
+        )
+    }
+
+    fn condition(self, condition: Condition) -> Self {
+        let mut self_mut = self;
+        self_mut.conditions.push(condition);
+        self_mut
+    }
+
+    fn matches(&self, element: &Element<'_>, clusters: &HashSet<&Identifier>) -> bool {
+        if self.kind.map(|k| k != element.kind).unwrap_or(false) {
+            return false;
+        }
+        self.conditions.iter().all(|condition| match condition {
+            Condition::Id(pattern) => element
+                .id
+                .map(|id| glob_matches(pattern, id.as_ref()))
+                .unwrap_or(false),
+            Condition::Head(pattern) => element
+                .ends
+                .map(|(head, _)| glob_matches(pattern, head.as_ref()))
+                .unwrap_or(false),
+            Condition::Tail(pattern) => element
+                .ends
+                .map(|(_, tail)| glob_matches(pattern, tail.as_ref()))
+                .unwrap_or(false),
+            Condition::Class(class) => element.attributes.iter().any(|a| match a {
+                Attribute::Class(classes) => classes.contains(class),
+                _ => false,
+            }),
+            Condition::Within(id) => element
+                .path
+                .iter()
+                .any(|scope| *scope == id && clusters.contains(scope)),
+            Condition::MinDegree(degree) => {
+                element.kind == ElementKind::Node && element.degree >= *degree
+            }
+            Condition::MaxDegree(degree) => {
+                element.kind == ElementKind::Node && element.degree <= *degree
+            }
+            Condition::HasAttribute(name) => element.attributes.iter().any(|a| a.name() == name),
+            Condition::Attribute(attribute) => element.attributes.contains(attribute),
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Rule {
+    pub fn new(selector: Selector, attributes: Vec<Attribute>) -> Self {
+        Self {
+            selector,
+            attributes,
+        }
+    }
+
+    pub fn selector(&self) -> &Selector {
+        &self.selector
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Stylesheet {
+    ///
+    /// Add a rule that applies `attributes` to the elements selected by `selector`.
+    ///
+    pub fn rule(self, selector: Selector, attributes: Vec<Attribute>) -> Self {
+        self.add_rule(Rule::new(selector, attributes))
+    }
+
+    pub fn add_rule(self, rule: Rule) -> Self {
+        let mut self_mut = self;
+        self_mut.rules.push(rule);
+        self_mut
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &Rule> {
+        self.rules.iter()
+    }
+
+    ///
+    /// Apply this stylesheet to every node, edge and cluster in `graph`.
+    ///
+    pub fn apply(&self, graph: RootGraph) -> RootGraph {
+        let (node_styles, edge_styles, cluster_styles) = {
+            let clusters: HashSet<&Identifier> =
+                graph.all_clusters().map(|c| c.item().id()).collect();
+            let adjacency = graph.adjacency();
+            let node_styles: Vec<Option<Vec<Attribute>>> = graph
+                .all_nodes()
+                .map(|n| {
+                    self.cascade(
+                        &Element {
+                            kind: ElementKind::Node,
+                            id: Some(n.item().id()),
+                            ends: None,
+                            path: n.path(),
+                            degree: adjacency.degree(n.item().id()),
+                            attributes: n.item().attributes().map_or(&[], |a| a.as_ref()),
+                        },
+                        &clusters,
+                    )
+                })
+                .collect();
+            let edge_styles: Vec<Option<Vec<Attribute>>> = graph
+                .all_edges()
+                .map(|e| {
+                    self.cascade(
+                        &Element {
+                            kind: ElementKind::Edge,
+                            id: None,
+                            ends: Some((e.item().head(), e.item().tail())),
+                            path: e.path(),
+                            degree: 0,
+                            attributes: e.item().attributes().map_or(&[], |a| a.as_ref()),
+                        },
+                        &clusters,
+                    )
+                })
+                .collect();
+            let cluster_styles: Vec<Option<Vec<Attribute>>> = graph
+                .all_clusters()
+                .map(|c| {
+                    self.cascade(
+                        &Element {
+                            kind: ElementKind::Cluster,
+                            id: Some(c.item().id()),
+                            ends: None,
+                            path: c.path(),
+                            degree: 0,
+                            attributes: c.item().attributes().map_or(&[], |a| a.as_ref()),
+                        },
+                        &clusters,
+                    )
+                })
+                .collect();
+            (node_styles, edge_styles, cluster_styles)
+        };
+
+        let mut graph = graph;
+        let mut styles = node_styles.into_iter();
+        graph.for_each_node_mut(|node| {
+            if let Some(Some(attributes)) = styles.next() {
+                *node.attributes_or_default_mut().as_mut() = attributes;
+            }
+        });
+        let mut styles = edge_styles.into_iter();
+        graph.for_each_edge_mut(|edge| {
+            if let Some(Some(attributes)) = styles.next() {
+                *edge.attributes_or_default_mut().as_mut() = attributes;
+            }
+        });
+        let mut styles = cluster_styles.into_iter();
+        graph.for_each_cluster_mut(|cluster| {
+            if let Some(Some(attributes)) = styles.next() {
+                let mut cluster_attributes = ClusterAttributes::default();
+                *cluster_attributes.as_mut() = attributes;
+                *cluster = std::mem::take(cluster).set_attributes(cluster_attributes);
+            }
+        });
+        graph
+    }
+
+    ///
+    /// Returns the attributes of `element` after applying all matching rules, or `None` if no
+    /// rules match.
+    ///
+    fn cascade(
+        &self,
+        element: &Element<'_>,
+        clusters: &HashSet<&Identifier>,
+    ) -> Option<Vec<Attribute>> {
+        let mut matching: Vec<&Rule> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.selector.matches(element, clusters))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        matching.sort_by_key(|rule| rule.selector.specificity());
+
+        let mut attributes: Vec<Attribute> = Vec::default();
+        for attribute in matching
+            .into_iter()
+            .flat_map(|rule| rule.attributes.iter())
+            .filter(|attribute| is_used_by(attribute, element.kind))
+            .chain(element.attributes.iter())
+        {
+            attributes.retain(|a| a.name() != attribute.name());
+            attributes.push(attribute.clone());
+        }
+        Some(attributes)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns `true` if `attribute` is used by elements of `kind`, following the "Used By" column
+/// of the Graphviz attribute documentation.
+///
+fn is_used_by(attribute: &Attribute, kind: ElementKind) -> bool {
+    match attribute {
+        Attribute::Class(_)
+        | Attribute::Color(_)
+        | Attribute::ColorScheme(_)
+        | Attribute::FillColor(_)
+        | Attribute::FontColor(_)
+        | Attribute::FontName(_)
+        | Attribute::FontSize(_)
+        | Attribute::Id(_)
+        | Attribute::Label(_)
+        | Attribute::Layer(_)
+        | Attribute::NoJustification(_)
+        | Attribute::PenWidth(_)
+        | Attribute::Target(_)
+        | Attribute::Tooltip(_)
+        | Attribute::Url(_) => true,
+        Attribute::Style(styles) => styles.iter().all(|style| {
+            matches!(
+                (style, kind),
+                (Style::Node(_), ElementKind::Node)
+                    | (Style::Edge(_), ElementKind::Edge)
+                    | (Style::Cluster(_), ElementKind::Cluster)
+            )
+        }),
+        Attribute::Area(_)
+        | Attribute::GradientAngle(_)
+        | Attribute::LabelLocation(_)
+        | Attribute::Margin(_)
+        | Attribute::Peripheries(_)
+        | Attribute::SortValue(_) => kind != ElementKind::Edge,
+        Attribute::Comment(_)
+        | Attribute::ExternalLabel(_)
+        | Attribute::ExternalLabelPosition(_)
+        | Attribute::Position(_)
+        | Attribute::ShowBoxes(_) => kind != ElementKind::Cluster,
+        Attribute::LabelPosition(_) => kind != ElementKind::Node,
+        Attribute::BackgroundColor(_)
+        | Attribute::LabelHeight(_)
+        | Attribute::LabelJustification(_)
+        | Attribute::LabelWidth(_)
+        | Attribute::PenColor(_)
+        | Attribute::Rank(_)
+        | Attribute::SpringConstant(_) => kind == ElementKind::Cluster,
+        Attribute::Distortion(_)
+        | Attribute::FixedSize(_)
+        | Attribute::Group(_)
+        | Attribute::Height(_)
+        | Attribute::Image(_)
+        | Attribute::ImagePosition(_)
+        | Attribute::ImageScale(_)
+        | Attribute::Ordering(_)
+        | Attribute::Orientation(_)
+        | Attribute::Pin(_)
+        | Attribute::RecordRectangles(_)
+        | Attribute::Regular(_)
+        | Attribute::Root(_)
+        | Attribute::SamplePoints(_)
+        | Attribute::Shape(_)
+        | Attribute::ShapeFile(_)
+        | Attribute::Sides(_)
+        | Attribute::Skew(_)
+        | Attribute::Vertices(_)
+        | Attribute::Width(_) => kind == ElementKind::Node,
+        Attribute::ArrowHead(_)
+        | Attribute::ArrowSize(_)
+        | Attribute::ArrowTail(_)
+        | Attribute::Constraint(_)
+        | Attribute::Decorate(_)
+        | Attribute::Direction(_)
+        | Attribute::EdgeTarget(_)
+        | Attribute::EdgeTooltip(_)
+        | Attribute::EdgeUrl(_)
+        | Attribute::HeadClip(_)
+        | Attribute::HeadLabel(_)
+        | Attribute::HeadLabelPosition(_)
+        | Attribute::HeadPort(_)
+        | Attribute::HeadTarget(_)
+        | Attribute::HeadTooltip(_)
+        | Attribute::HeadUrl(_)
+        | Attribute::LabelAngle(_)
+        | Attribute::LabelDistance(_)
+        | Attribute::LabelFloat(_)
+        | Attribute::LabelFontColor(_)
+        | Attribute::LabelFontName(_)
+        | Attribute::LabelFontSize(_)
+        | Attribute::LabelTarget(_)
+        | Attribute::LabelTooltip(_)
+        | Attribute::LabelUrl(_)
+        | Attribute::Length(_)
+        | Attribute::LogicalHead(_)
+        | Attribute::LogicalTail(_)
+        | Attribute::MinLength(_)
+        | Attribute::SameHead(_)
+        | Attribute::SameTail(_)
+        | Attribute::TailClip(_)
+        | Attribute::TailLabel(_)
+        | Attribute::TailLabelPosition(_)
+        | Attribute::TailPort(_)
+        | Attribute::TailTarget(_)
+        | Attribute::TailTooltip(_)
+        | Attribute::TailUrl(_)
+        | Attribute::Weight(_) => kind == ElementKind::Edge,
+        _ => false,
+    }
+}
+
+#[inline]
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+///
+/// Matches `text` against `pattern`, where `*` matches any sequence of characters and `?` any
+/// single character.
+///
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, t));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
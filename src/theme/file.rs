@@ -0,0 +1,296 @@
+/*!
+Loads themes from TOML and JSON documents.
+
+Both formats are first converted into a common tree of tables and text values; numbers and
+booleans are converted to text and arrays are joined with commas, so that `["a", "b"]` and
+`"a,b"` are equivalent.
+
+ */
+
+use crate::attributes::{
+    ArrowType, Attribute, Attributes, ClusterStyles, Color, ColorOrList, ColorScheme, EdgeStyles,
+    FontName, GraphStyles, NodeStyles, Positive, Shape, Style,
+};
+use crate::error::{invalid_value, Error};
+use crate::theme::{Theme, ThemeRole};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "toml")]
+pub(super) fn parse_toml(s: &str) -> Result<Theme, Error> {
+    let table: toml::Table = s.parse().map_err(crate::error::toml_error)?;
+    theme_from_value(from_toml(toml::Value::Table(table)))
+}
+
+#[cfg(feature = "json")]
+pub(super) fn parse_json(s: &str) -> Result<Theme, Error> {
+    let value: serde_json::Value = serde_json::from_str(s).map_err(crate::error::json_error)?;
+    theme_from_value(from_json(value))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+enum Value {
+    Text(String),
+    Table(Vec<(String, Value)>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Element {
+    Graph,
+    Node,
+    Edge,
+    Cluster,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Value {
+    fn into_table(self) -> Result<Vec<(String, Value)>, Error> {
+        match self {
+            Value::Table(v) => Ok(v),
+            Value::Text(v) => Err(invalid_value(THEME_TYPE_NAME, &v)),
+        }
+    }
+
+    fn into_text(self) -> Result<String, Error> {
+        match self {
+            Value::Text(v) => Ok(v),
+            Value::Table(v) => Err(invalid_value(
+                THEME_TYPE_NAME,
+                &v.into_iter().map(|(k, _)| k).collect::<Vec<String>>(),
+            )),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const THEME_TYPE_NAME: &str = "Theme";
+
+fn theme_from_value(value: Value) -> Result<Theme, Error> {
+    let mut theme = Theme::default();
+    for (key, value) in value.into_table()? {
+        match key.as_str() {
+            "name" => theme.name = value.into_text()?,
+            "graph" => theme.graph = parse_attributes(Element::Graph, value)?,
+            "node" => theme.node = parse_attributes(Element::Node, value)?,
+            "edge" => theme.edge = parse_attributes(Element::Edge, value)?,
+            "cluster" => theme.cluster = parse_attributes(Element::Cluster, value)?,
+            "roles" => {
+                for (name, value) in value.into_table()? {
+                    theme = theme.add_role(&name, role_from_value(value)?);
+                }
+            }
+            _ => return Err(invalid_value(THEME_TYPE_NAME, &key)),
+        }
+    }
+    Ok(theme)
+}
+
+fn role_from_value(value: Value) -> Result<ThemeRole, Error> {
+    let mut role = ThemeRole::default();
+    for (key, value) in value.into_table()? {
+        match key.as_str() {
+            "node" => role.node = Some(parse_attributes(Element::Node, value)?),
+            "edge" => role.edge = Some(parse_attributes(Element::Edge, value)?),
+            "cluster" => role.cluster = Some(parse_attributes(Element::Cluster, value)?),
+            _ => return Err(invalid_value(THEME_TYPE_NAME, &key)),
+        }
+    }
+    Ok(role)
+}
+
+fn parse_attributes<A>(element: Element, value: Value) -> Result<A, Error>
+where
+    A: Attributes,
+{
+    let mut attributes = A::default();
+    for (name, value) in value.into_table()? {
+        let value = value.into_text()?;
+        attributes
+            .as_mut()
+            .push(parse_attribute(element, &name, &value)?);
+    }
+    Ok(attributes)
+}
+
+///
+/// Parses the styling attributes listed in the documentation of the `theme` module; see there
+/// for the attributes used by each element.
+///
+fn parse_attribute(element: Element, name: &str, value: &str) -> Result<Attribute, Error> {
+    use Element::{Cluster, Edge, Graph, Node};
+    let positive = |value: &str| {
+        value
+            .parse::<f64>()
+            .map_err(|_| invalid_value("Positive", &value))
+            .and_then(Positive::try_from)
+    };
+    let number = |type_name: &str, value: &str| {
+        value
+            .parse::<f64>()
+            .map_err(|_| invalid_value(type_name, &value))
+    };
+    Ok(match (name, element) {
+        ("arrowhead", Edge) => Attribute::ArrowHead(ArrowType::from_str(value)?),
+        ("arrowsize", Edge) => Attribute::ArrowSize(positive(value)?),
+        ("arrowtail", Edge) => Attribute::ArrowTail(ArrowType::from_str(value)?),
+        ("bgcolor", Graph | Cluster) => {
+            Attribute::BackgroundColor(ColorOrList::Color(Color::from_str(value)?))
+        }
+        ("color", _) => Attribute::Color(ColorOrList::Color(Color::from_str(value)?)),
+        ("colorscheme", _) => Attribute::ColorScheme(ColorScheme::from_str(value)?),
+        ("fillcolor", Node | Edge | Cluster) => {
+            Attribute::FillColor(ColorOrList::Color(Color::from_str(value)?))
+        }
+        ("fontcolor", _) => Attribute::FontColor(Color::from_str(value)?),
+        ("fontname", _) => Attribute::FontName(parse_font_name(value)?),
+        ("fontsize", _) => Attribute::FontSize(number("FontSize", value)?),
+        ("gradientangle", Graph | Node | Cluster) => Attribute::GradientAngle(
+            value
+                .parse()
+                .map_err(|_| invalid_value("GradientAngle", &value))?,
+        ),
+        ("labelfontcolor", Edge) => Attribute::LabelFontColor(Color::from_str(value)?),
+        ("labelfontname", Edge) => Attribute::LabelFontName(parse_font_name(value)?),
+        ("labelfontsize", Edge) => Attribute::LabelFontSize(number("FontSize", value)?),
+        ("pencolor", Cluster) => Attribute::PenColor(Color::from_str(value)?),
+        ("penwidth", Node | Edge | Cluster) => Attribute::PenWidth(positive(value)?),
+        ("peripheries", Node | Cluster) => Attribute::Peripheries(
+            value
+                .parse()
+                .map_err(|_| invalid_value("Peripheries", &value))?,
+        ),
+        ("shape", Node) => Attribute::Shape(Shape::from_str(value)?),
+        ("style", _) => Attribute::Style(
+            value
+                .split(',')
+                .map(|s| parse_style(element, s.trim()))
+                .collect::<Result<_, _>>()?,
+        ),
+        _ => return Err(invalid_value("Attribute", &name)),
+    })
+}
+
+fn parse_font_name(value: &str) -> Result<FontName, Error> {
+    let families: Vec<FontName> = value
+        .split(',')
+        .map(|s| FontName::family(s.trim()))
+        .collect::<Result<_, _>>()?;
+    if families.len() == 1 {
+        Ok(families.into_iter().next().unwrap())
+    } else {
+        Ok(FontName::list(families))
+    }
+}
+
+fn parse_style(element: Element, value: &str) -> Result<Style, Error> {
+    let found = match element {
+        Element::Graph => [GraphStyles::Filled, GraphStyles::Radial]
+            .into_iter()
+            .find(|s| s.to_string() == value)
+            .map(Style::Graph),
+        Element::Node => [
+            NodeStyles::Dashed,
+            NodeStyles::Dotted,
+            NodeStyles::Solid,
+            NodeStyles::Invisible,
+            NodeStyles::Bold,
+            NodeStyles::Filled,
+            NodeStyles::Striped,
+            NodeStyles::Wedged,
+            NodeStyles::Diagonals,
+            NodeStyles::Rounded,
+            NodeStyles::Radial,
+        ]
+        .into_iter()
+        .find(|s| s.to_string() == value)
+        .map(Style::Node),
+        Element::Edge => [
+            EdgeStyles::Dashed,
+            EdgeStyles::Dotted,
+            EdgeStyles::Solid,
+            EdgeStyles::Invisible,
+            EdgeStyles::Bold,
+            EdgeStyles::Tapered,
+        ]
+        .into_iter()
+        .find(|s| s.to_string() == value)
+        .map(Style::Edge),
+        Element::Cluster => [
+            ClusterStyles::Filled,
+            ClusterStyles::Striped,
+            ClusterStyles::Rounded,
+            ClusterStyles::Radial,
+        ]
+        .into_iter()
+        .find(|s| s.to_string() == value)
+        .map(Style::Cluster),
+    };
+    found.ok_or_else(|| invalid_value("Style", &value))
+}
+
+#[cfg(feature = "toml")]
+fn from_toml(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(v) => Value::Text(v),
+        toml::Value::Array(vs) => Value::Text(
+            vs.into_iter()
+                .map(|v| {
+                    v.as_str()
+                        .map(String::from)
+                        .unwrap_or_else(|| v.to_string())
+                })
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+        toml::Value::Table(vs) => {
+            Value::Table(vs.into_iter().map(|(k, v)| (k, from_toml(v))).collect())
+        }
+        v => Value::Text(v.to_string()),
+    }
+}
+
+#[cfg(feature = "json")]
+fn from_json(value: serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::String(v) => Value::Text(v),
+        serde_json::Value::Array(vs) => Value::Text(
+            vs.into_iter()
+                .map(|v| {
+                    v.as_str()
+                        .map(String::from)
+                        .unwrap_or_else(|| v.to_string())
+                })
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+        serde_json::Value::Object(vs) => {
+            Value::Table(vs.into_iter().map(|(k, v)| (k, from_json(v))).collect())
+        }
+        v => Value::Text(v.to_string()),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -0,0 +1,489 @@
+/*!
+Reusable, named sets of styles that can be applied to a graph in one call.
+
+A `Theme` bundles the attributes of the root graph, the default node and edge attributes, the
+attributes of every cluster, and a set of named roles. A role, such as "database" or
+"deprecated", maps to attributes for nodes, edges and clusters and is applied to any element
+whose `class` attribute includes the role name. Attributes set explicitly on a graph or element
+always take precedence over those from the theme.
+
+Themes may be loaded from TOML, with the `toml` feature, or JSON, with the `json` feature. Each
+attribute is given by its DOT name, with values as strings, numbers or booleans and lists as
+arrays. Only the following styling attributes are supported in a file, any other name, or an
+attribute given for an element that does not use it, is an error:
+
+| Attribute                                        | Graph | Node | Edge | Cluster |
+|--------------------------------------------------|:-----:|:----:|:----:|:-------:|
+| `color`, `fontcolor`, `fontname`, `fontsize`, `style`, `colorscheme` | ✓ | ✓ | ✓ | ✓ |
+| `bgcolor`                                        | ✓     |      |      | ✓       |
+| `fillcolor`                                      |       | ✓    | ✓    | ✓       |
+| `gradientangle`                                  | ✓     | ✓    |      | ✓       |
+| `penwidth`                                       |       | ✓    | ✓    | ✓       |
+| `peripheries`                                    |       | ✓    |      | ✓       |
+| `pencolor`                                       |       |      |      | ✓       |
+| `shape`                                          |       | ✓    |      |         |
+| `arrowhead`, `arrowtail`, `arrowsize`            |       |      | ✓    |         |
+| `labelfontcolor`, `labelfontname`, `labelfontsize` |     |      | ✓    |         |
+
+Other attributes may still be set on a `Theme` built in code.
+
+```toml
+name = "corporate"
+
+[graph]
+fontname = ["Helvetica", "Arial", "sans-serif"]
+
+[node]
+shape = "box"
+style = ["rounded", "filled"]
+fillcolor = "#f5f5f5"
+
+[roles.database.node]
+shape = "cylinder"
+
+[roles.deprecated.edge]
+style = "dashed"
+```
+
+# Example
+
+ */
+
+use crate::attributes::{
+    Attribute, Attributes, ClusterAttributes, ClusterStyles, Color, EdgeAttributes, EdgeStyles,
+    FontName, GraphAttributes, NodeAttributes, NodeStyles, Shape, Styled,
+};
+#[cfg(any(feature = "toml", feature = "json"))]
+use crate::error::Error;
+use crate::graph::Graph;
+use crate::RootGraph;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default)]
+pub struct Theme {
+    name: String,
+    graph: GraphAttributes,
+    node: NodeAttributes,
+    edge: EdgeAttributes,
+    cluster: ClusterAttributes,
+    roles: Vec<(String, ThemeRole)>,
+}
+
+///
+/// The attributes applied to nodes, edges and clusters that have a role's name as a class.
+///
+#[derive(Clone, Debug, Default)]
+pub struct ThemeRole {
+    node: Option<NodeAttributes>,
+    edge: Option<EdgeAttributes>,
+    cluster: Option<ClusterAttributes>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Theme {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Black on white, with light fills.
+    ///
+    pub fn light() -> Self {
+        Self::built_in("light", "black", "white", "#f5f5f5", "#fafafa", "#9e9e9e")
+    }
+
+    ///
+    /// Light text and lines on a dark background.
+    ///
+    pub fn dark() -> Self {
+        Self::built_in(
+            "dark", "#e0e0e0", "#1e1e1e", "#2d2d2d", "#252526", "#808080",
+        )
+    }
+
+    ///
+    /// Black on white with no fills, suitable for printing in monochrome.
+    ///
+    pub fn print() -> Self {
+        let fonts = FontName::list(vec![
+            FontName::times_roman(),
+            FontName::family("serif").unwrap(),
+        ]);
+        let black: Color = Color::named("black").unwrap();
+        Self::new("print")
+            .set_graph_attributes(
+                GraphAttributes::default()
+                    .font_name(fonts.clone())
+                    .background_color(Color::named("white").unwrap().into()),
+            )
+            .set_node_attributes(
+                NodeAttributes::default()
+                    .font_name(fonts.clone())
+                    .shape(Shape::Box)
+                    .color(black.clone().into()),
+            )
+            .set_edge_attributes(
+                EdgeAttributes::default()
+                    .font_name(fonts.clone())
+                    .color(black.clone().into()),
+            )
+            .set_cluster_attributes(
+                ClusterAttributes::default()
+                    .font_name(fonts)
+                    .color(black.into()),
+            )
+            .add_role(
+                "database",
+                ThemeRole::default()
+                    .set_node_attributes(NodeAttributes::default().shape(Shape::Cylinder)),
+            )
+            .add_role(
+                "external",
+                ThemeRole::default()
+                    .set_node_attributes(NodeAttributes::default().peripheries(2))
+                    .set_cluster_attributes(ClusterAttributes::default().peripheries(2)),
+            )
+            .add_role(
+                "deprecated",
+                ThemeRole::default()
+                    .set_node_attributes(NodeAttributes::default().style(vec![NodeStyles::Dotted]))
+                    .set_edge_attributes(EdgeAttributes::default().style(vec![EdgeStyles::Dotted])),
+            )
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn graph_attributes(&self) -> &GraphAttributes {
+        &self.graph
+    }
+
+    pub fn set_graph_attributes(self, attributes: GraphAttributes) -> Self {
+        let mut self_mut = self;
+        self_mut.graph = attributes;
+        self_mut
+    }
+
+    pub fn node_attributes(&self) -> &NodeAttributes {
+        &self.node
+    }
+
+    pub fn set_node_attributes(self, attributes: NodeAttributes) -> Self {
+        let mut self_mut = self;
+        self_mut.node = attributes;
+        self_mut
+    }
+
+    pub fn edge_attributes(&self) -> &EdgeAttributes {
+        &self.edge
+    }
+
+    pub fn set_edge_attributes(self, attributes: EdgeAttributes) -> Self {
+        let mut self_mut = self;
+        self_mut.edge = attributes;
+        self_mut
+    }
+
+    pub fn cluster_attributes(&self) -> &ClusterAttributes {
+        &self.cluster
+    }
+
+    pub fn set_cluster_attributes(self, attributes: ClusterAttributes) -> Self {
+        let mut self_mut = self;
+        self_mut.cluster = attributes;
+        self_mut
+    }
+
+    pub fn roles(&self) -> impl Iterator<Item = (&str, &ThemeRole)> {
+        self.roles.iter().map(|(name, role)| (name.as_str(), role))
+    }
+
+    pub fn role(&self, name: &str) -> Option<&ThemeRole> {
+        self.roles
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, role)| role)
+    }
+
+    ///
+    /// Add the role `name`, replacing any existing role with the same name.
+    ///
+    pub fn add_role(self, name: &str, role: ThemeRole) -> Self {
+        let mut self_mut = self;
+        match self_mut.roles.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = role,
+            None => self_mut.roles.push((name.to_string(), role)),
+        }
+        self_mut
+    }
+
+    ///
+    /// Apply this theme to `graph`, and to every cluster, node and edge within it.
+    ///
+    pub fn apply(&self, graph: RootGraph) -> RootGraph {
+        let graph_attributes = underlay(&self.graph, graph.attributes());
+        let node_attributes = underlay(&self.node, graph.default_node_attributes());
+        let edge_attributes = underlay(&self.edge, graph.default_edge_attributes());
+        let mut graph = graph;
+        if !graph_attributes.as_ref().is_empty() {
+            graph = graph.set_attributes(graph_attributes);
+        }
+        if !node_attributes.as_ref().is_empty() {
+            graph = graph.set_default_node_attributes(node_attributes);
+        }
+        if !edge_attributes.as_ref().is_empty() {
+            graph = graph.set_default_edge_attributes(edge_attributes);
+        }
+
+        graph.for_each_cluster_mut(|cluster| {
+            let explicit: &[Attribute] = cluster.attributes().map(AsRef::as_ref).map_or(&[], |v| v);
+            let mut themed = self.cluster.clone();
+            for role in self.roles_for(explicit) {
+                if let Some(role_attributes) = &role.cluster {
+                    themed = underlay(&themed, Some(role_attributes));
+                }
+            }
+            let attributes = underlay(&themed, cluster.attributes());
+            if !attributes.as_ref().is_empty() {
+                *cluster = std::mem::take(cluster).set_attributes(attributes);
+            }
+        });
+        graph.for_each_node_mut(|node| {
+            if let Some(attributes) = node.attributes() {
+                let mut attributes = attributes.clone();
+                for role in self.roles_for(attributes.as_ref()) {
+                    if let Some(role_attributes) = &role.node {
+                        attributes = underlay(role_attributes, Some(&attributes));
+                    }
+                }
+                *node.attributes_or_default_mut() = attributes;
+            }
+        });
+        graph.for_each_edge_mut(|edge| {
+            if let Some(attributes) = edge.attributes() {
+                let mut attributes = attributes.clone();
+                for role in self.roles_for(attributes.as_ref()) {
+                    if let Some(role_attributes) = &role.edge {
+                        attributes = underlay(role_attributes, Some(&attributes));
+                    }
+                }
+                *edge.attributes_or_default_mut() = attributes;
+            }
+        });
+        graph
+    }
+
+    ///
+    /// Parse a theme from a TOML document.
+    ///
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(s: &str) -> Result<Self, Error> {
+        file::parse_toml(s)
+    }
+
+    ///
+    /// Load a theme from a TOML file.
+    ///
+    #[cfg(feature = "toml")]
+    pub fn from_toml_file(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+
+    ///
+    /// Parse a theme from a JSON document.
+    ///
+    #[cfg(feature = "json")]
+    pub fn from_json_str(s: &str) -> Result<Self, Error> {
+        file::parse_json(s)
+    }
+
+    ///
+    /// Load a theme from a JSON file.
+    ///
+    #[cfg(feature = "json")]
+    pub fn from_json_file(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        Self::from_json_str(&std::fs::read_to_string(path)?)
+    }
+
+    fn built_in(
+        name: &str,
+        foreground: &str,
+        background: &str,
+        fill: &str,
+        cluster_fill: &str,
+        muted: &str,
+    ) -> Self {
+        let fonts = FontName::list(vec![
+            FontName::helvetica(),
+            FontName::family("Arial").unwrap(),
+            FontName::family("sans-serif").unwrap(),
+        ]);
+        let foreground = Color::from_str(foreground).unwrap();
+        let fill = Color::from_str(fill).unwrap();
+        let muted = Color::from_str(muted).unwrap();
+        Self::new(name)
+            .set_graph_attributes(
+                GraphAttributes::default()
+                    .font_name(fonts.clone())
+                    .font_color(foreground.clone())
+                    .background_color(Color::from_str(background).unwrap().into()),
+            )
+            .set_node_attributes(
+                NodeAttributes::default()
+                    .font_name(fonts.clone())
+                    .font_color(foreground.clone())
+                    .color(foreground.clone().into())
+                    .fill_color(fill.clone().into())
+                    .shape(Shape::Box)
+                    .style(vec![NodeStyles::Rounded, NodeStyles::Filled]),
+            )
+            .set_edge_attributes(
+                EdgeAttributes::default()
+                    .font_name(fonts.clone())
+                    .font_color(foreground.clone())
+                    .color(foreground.clone().into()),
+            )
+            .set_cluster_attributes(
+                ClusterAttributes::default()
+                    .font_name(fonts)
+                    .font_color(foreground)
+                    .color(muted.clone().into())
+                    .fill_color(Color::from_str(cluster_fill).unwrap().into())
+                    .style(vec![ClusterStyles::Rounded, ClusterStyles::Filled]),
+            )
+            .add_role(
+                "database",
+                ThemeRole::default().set_node_attributes(
+                    NodeAttributes::default()
+                        .shape(Shape::Cylinder)
+                        .style(vec![NodeStyles::Filled]),
+                ),
+            )
+            .add_role(
+                "external",
+                ThemeRole::default()
+                    .set_node_attributes(
+                        NodeAttributes::default()
+                            .style(vec![NodeStyles::Rounded, NodeStyles::Dashed]),
+                    )
+                    .set_cluster_attributes(
+                        ClusterAttributes::default().style(vec![ClusterStyles::Rounded]),
+                    ),
+            )
+            .add_role(
+                "deprecated",
+                ThemeRole::default()
+                    .set_node_attributes(
+                        NodeAttributes::default()
+                            .font_color(muted.clone())
+                            .color(muted.clone().into()),
+                    )
+                    .set_edge_attributes(
+                        EdgeAttributes::default()
+                            .color(muted.into())
+                            .style(vec![EdgeStyles::Dashed]),
+                    ),
+            )
+    }
+
+    fn roles_for(&self, attributes: &[Attribute]) -> Vec<&ThemeRole> {
+        let classes: Vec<&String> = attributes
+            .iter()
+            .filter_map(|a| match a {
+                Attribute::Class(classes) => Some(classes),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        self.roles
+            .iter()
+            .filter(|(name, _)| classes.contains(&name))
+            .map(|(_, role)| role)
+            .collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ThemeRole {
+    pub fn node_attributes(&self) -> Option<&NodeAttributes> {
+        self.node.as_ref()
+    }
+
+    pub fn set_node_attributes(self, attributes: NodeAttributes) -> Self {
+        let mut self_mut = self;
+        self_mut.node = Some(attributes);
+        self_mut
+    }
+
+    pub fn edge_attributes(&self) -> Option<&EdgeAttributes> {
+        self.edge.as_ref()
+    }
+
+    pub fn set_edge_attributes(self, attributes: EdgeAttributes) -> Self {
+        let mut self_mut = self;
+        self_mut.edge = Some(attributes);
+        self_mut
+    }
+
+    pub fn cluster_attributes(&self) -> Option<&ClusterAttributes> {
+        self.cluster.as_ref()
+    }
+
+    pub fn set_cluster_attributes(self, attributes: ClusterAttributes) -> Self {
+        let mut self_mut = self;
+        self_mut.cluster = Some(attributes);
+        self_mut
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the attributes in `theme` overridden by any attribute of the same name in `explicit`.
+///
+fn underlay<A>(theme: &A, explicit: Option<&A>) -> A
+where
+    A: Attributes + Clone,
+{
+    let mut merged = theme.clone();
+    if let Some(explicit) = explicit {
+        for attribute in explicit.as_ref() {
+            merged.as_mut().retain(|a| a.name() != attribute.name());
+        }
+        merged.as_mut().extend(explicit.as_ref().iter().cloned());
+    }
+    merged
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(any(feature = "toml", feature = "json"))]
+mod file;
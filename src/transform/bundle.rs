@@ -0,0 +1,177 @@
+/*!
+Merges parallel edges into a single edge whose attributes reflect the number of edges merged.
+
+Edges are parallel if they have the same head and tail and are declared in the same graph,
+sub-graph or cluster; edges in different scopes are never merged. Optionally edges must also
+have the same attributes to be merged, and in undirected graphs `a -- b` and `b -- a` may be
+treated as the same pair.
+
+# Example
+
+ */
+
+use crate::attributes::{Attribute, LabelString, Positive, Styled, Unsigned};
+use crate::{Edge, Identifier, RootGraph};
+use std::collections::HashMap;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The attributes of a bundled edge that are set from the number of edges in the bundle.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BundleMetric {
+    /// The label is set to the count.
+    Label,
+    /// The pen width is set to `1 + log2(count)`, so that large bundles remain legible.
+    PenWidth,
+    /// The weight is set to the count.
+    Weight,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleOptions {
+    same_attributes: bool,
+    unordered: bool,
+    metrics: Vec<BundleMetric>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Merges parallel edges, keeping the first edge of each bundle and setting the attributes
+/// selected by the options' metrics. Returns the number of edges removed.
+///
+pub fn bundle_edges(graph: &mut RootGraph, options: &BundleOptions) -> usize {
+    let metrics = options.metrics.clone();
+    bundle_edges_with(graph, options, move |bundle| {
+        let count = bundle.len();
+        let mut edge = bundle.into_iter().next().unwrap();
+        if count > 1 {
+            let attributes = edge.attributes_or_default_mut().as_mut();
+            for metric in metrics.iter() {
+                let attribute = match metric {
+                    BundleMetric::Label => {
+                        Attribute::Label(LabelString::new_unchecked(&count.to_string()))
+                    }
+                    BundleMetric::PenWidth => Attribute::PenWidth(
+                        Positive::try_from(1.0 + (count as f64).log2()).unwrap(),
+                    ),
+                    BundleMetric::Weight => Attribute::Weight(count as Unsigned),
+                };
+                attributes.retain(|a| a.name() != attribute.name());
+                attributes.push(attribute);
+            }
+        }
+        edge
+    })
+}
+
+///
+/// Merges parallel edges, replacing each bundle, in declaration order, with the edge returned
+/// by `aggregate`. Returns the number of edges removed.
+///
+pub fn bundle_edges_with<F>(graph: &mut RootGraph, options: &BundleOptions, aggregate: F) -> usize
+where
+    F: FnMut(Vec<Edge>) -> Edge,
+{
+    let directed = graph.is_directed();
+    let unordered = options.unordered && !directed;
+    let same_attributes = options.same_attributes;
+    let mut aggregate = aggregate;
+    let mut removed = 0;
+    graph.for_each_edge_list_mut(&mut |edges| {
+        let before = edges.len();
+        let mut bundles: Vec<Vec<Edge>> = Vec::default();
+        let mut index: HashMap<(Identifier, Identifier), Vec<usize>> = HashMap::default();
+        for edge in edges.drain(..) {
+            let key = if unordered && edge.tail() < edge.head() {
+                (edge.tail().clone(), edge.head().clone())
+            } else {
+                (edge.head().clone(), edge.tail().clone())
+            };
+            let candidates = index.entry(key).or_default();
+            let found = candidates.iter().copied().find(|bundle| {
+                !same_attributes
+                    || bundles[*bundle][0].attributes().map(AsRef::as_ref)
+                        == edge.attributes().map(AsRef::as_ref)
+            });
+            match found {
+                Some(bundle) => bundles[bundle].push(edge),
+                None => {
+                    candidates.push(bundles.len());
+                    bundles.push(vec![edge]);
+                }
+            }
+        }
+        edges.extend(bundles.into_iter().map(|bundle| {
+            if bundle.len() == 1 {
+                bundle.into_iter().next().unwrap()
+            } else {
+                aggregate(bundle).set_directed(directed)
+            }
+        }));
+        removed += before - edges.len();
+    });
+    removed
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for BundleOptions {
+    fn default() -> Self {
+        Self {
+            same_attributes: false,
+            unordered: false,
+            metrics: vec![BundleMetric::Label],
+        }
+    }
+}
+
+impl BundleOptions {
+    ///
+    /// Only merge edges that also have the same attributes.
+    ///
+    pub fn same_attributes(self) -> Self {
+        let mut self_mut = self;
+        self_mut.same_attributes = true;
+        self_mut
+    }
+
+    ///
+    /// In undirected graphs, treat `a -- b` and `b -- a` as parallel edges.
+    ///
+    pub fn unordered(self) -> Self {
+        let mut self_mut = self;
+        self_mut.unordered = true;
+        self_mut
+    }
+
+    pub fn metrics(self, metrics: Vec<BundleMetric>) -> Self {
+        let mut self_mut = self;
+        self_mut.metrics = metrics;
+        self_mut
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -0,0 +1,104 @@
+/*!
+Splits a graph into its connected components, equivalent to the Graphviz `ccomps` tool.
+
+Edge direction is ignored when determining components. Each component keeps the attributes of
+the original root graph, and each node remains in the sub-graph or cluster in which it was
+declared; sub-graphs and clusters left empty by the split are dropped.
+
+# Example
+
+ */
+
+use crate::algo::weakly_connected_components;
+use crate::attributes::{Attribute, GraphAttributes, PackMode, Styled};
+use crate::graph::Graph;
+use crate::{Identified, Identifier, RootGraph};
+use std::collections::HashSet;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns one graph per connected component of `graph`, in the order the first node of each
+/// component was declared. Component graphs are named `{id}_{n}`, counting from zero, and
+/// carry over the strictness, direction, graph attributes and default attributes of `graph`.
+///
+pub fn connected_components(graph: &RootGraph) -> Vec<RootGraph> {
+    weakly_connected_components(graph)
+        .into_iter()
+        .enumerate()
+        .map(|(n, component)| {
+            let members: HashSet<Identifier> = component.into_iter().collect();
+            graph.filtered(
+                component_id(graph.id(), n),
+                &|id| members.contains(id),
+                &|edge| members.contains(edge.head()),
+            )
+        })
+        .collect()
+}
+
+///
+/// Returns a single graph containing each connected component of `graph` as a cluster, with
+/// the `pack` and `packmode` graph attributes set so that Graphviz lays out each component
+/// separately and then packs them together.
+///
+pub fn packed_connected_components(graph: &RootGraph, pack_mode: PackMode) -> RootGraph {
+    let mut attributes = graph.attributes().cloned().unwrap_or_default();
+    attributes
+        .as_mut()
+        .retain(|a| !matches!(a, Attribute::Pack(_) | Attribute::PackMode(_)));
+    let attributes: GraphAttributes = attributes.pack_graphs().pack_mode(pack_mode);
+
+    let mut packed = RootGraph::new(graph.id().clone(), graph.is_strict(), graph.is_directed())
+        .set_attributes(attributes);
+    if let Some(defaults) = graph.default_graph_attributes() {
+        packed = packed.set_default_graph_attributes(defaults.clone());
+    }
+    if let Some(defaults) = graph.default_node_attributes() {
+        packed = packed.set_default_node_attributes(defaults.clone());
+    }
+    if let Some(defaults) = graph.default_edge_attributes() {
+        packed = packed.set_default_edge_attributes(defaults.clone());
+    }
+
+    packed.add_sub_graphs(
+        connected_components(graph)
+            .into_iter()
+            .map(|component| {
+                let id = component.id().clone();
+                component.into_cluster(id)
+            })
+            .collect(),
+    )
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+#[inline]
+fn component_id(id: &Identifier, n: usize) -> Identifier {
+    id.derived("", &format!("_{}", n))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
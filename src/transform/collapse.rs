@@ -0,0 +1,342 @@
+/*!
+Collapses clusters into single summary nodes, and expands them again.
+
+When a cluster is collapsed it is removed from the graph, along with everything declared inside
+it, and replaced by a node with the same identifier in the cluster's parent scope. Nodes that
+only appear in the edges of a cluster, without being declared in it, are not removed. Every edge
+that crossed the cluster boundary is rerouted to the summary node, and rerouted edges between
+the same pair of nodes are merged into one. The returned `CollapsedCluster` records everything
+that was removed so that the cluster may later be expanded in place of the summary node. A
+cluster cannot be collapsed while another node already uses its identifier.
+
+# Example
+
+```rust
+use simple_dot::graph::{Cluster, Graph};
+use simple_dot::transform::{collapse_cluster, expand_cluster, CollapseOptions};
+use simple_dot::{Identifier, Node, RootGraph};
+
+let id = |s: &str| Identifier::new_unchecked(s);
+let mut graph = RootGraph::new(id("G"), false, true)
+    .add_sub_graph(
+        Cluster::new(id("data"))
+            .add_node(Node::new(id("db")))
+            .add_node(Node::new(id("cache")))
+            .add_edge_between(id("db"), id("cache")),
+    )
+    .add_edge_between(id("api"), id("db"));
+
+let collapsed = collapse_cluster(&mut graph, &id("data"), &CollapseOptions::default()).unwrap();
+assert!(graph.find_edge(&id("api"), &id("data")).is_some());
+assert!(graph.find_sub_graph(&id("data")).is_none());
+
+expand_cluster(&mut graph, collapsed).unwrap();
+assert!(graph.find_edge(&id("api"), &id("db")).is_some());
+assert!(graph.find_sub_graph(&id("data")).is_some());
+```
+
+ */
+
+use crate::attributes::{Attribute, EdgeAttributes, LabelString, NodeAttributes, Shape, Styled};
+use crate::error::{invalid_state, invalid_value, Error};
+use crate::graph::{Graph, SubGraphKind};
+use crate::{Edge, Identified, Identifier, Node, RootGraph};
+use std::collections::HashSet;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CollapseOptions {
+    label: Option<LabelString>,
+    count_labels: bool,
+}
+
+///
+/// A cluster that has been collapsed, used to expand it again.
+///
+#[derive(Clone, Debug)]
+pub struct CollapsedCluster {
+    cluster: SubGraphKind,
+    parent: Option<Identifier>,
+    edges: Vec<(Option<Identifier>, Edge)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Replaces the cluster `id` with a summary node. The node is labeled with the label from
+/// `options`, or else the label of the cluster, or else the cluster identifier. It is an error
+/// if there is no such cluster, or if a node, declared or only the end of an edge, already has
+/// the identifier `id`.
+///
+pub fn collapse_cluster(
+    graph: &mut RootGraph,
+    id: &Identifier,
+    options: &CollapseOptions,
+) -> Result<CollapsedCluster, Error> {
+    let parent = collapsible_parent(graph, id)?;
+    let cluster = graph.remove_sub_graph(id).unwrap();
+
+    let members: HashSet<Identifier> = match &cluster {
+        SubGraphKind::Graph(v) => v.all_nodes(),
+        SubGraphKind::Cluster(v) => v.all_nodes(),
+    }
+    .map(|n| n.item().id().clone())
+    .collect();
+
+    let edges: Vec<(Option<Identifier>, Edge)> = graph
+        .all_edges()
+        .filter(|e| members.contains(e.item().head()) || members.contains(e.item().tail()))
+        .map(|e| ((e.depth() > 0).then(|| e.scope().clone()), e.item().clone()))
+        .collect();
+    graph.retain_edges(|e| !members.contains(e.head()) && !members.contains(e.tail()));
+
+    let crossing: Vec<&Edge> = edges
+        .iter()
+        .map(|(_, e)| e)
+        .chain(
+            match &cluster {
+                SubGraphKind::Graph(v) => v.all_edges(),
+                SubGraphKind::Cluster(v) => v.all_edges(),
+            }
+            .map(|e| e.item()),
+        )
+        .collect();
+    let rerouted = reroute(
+        &crossing,
+        &members,
+        id,
+        graph.is_directed(),
+        options.count_labels,
+    );
+
+    let label = options
+        .label
+        .clone()
+        .or_else(|| cluster_label(&cluster))
+        .unwrap_or_else(|| LabelString::plain_text(id.unquoted()));
+    let summary = Node::new(id.clone())
+        .set_attributes(NodeAttributes::default().label(label).shape(Shape::Box3d));
+    match parent.as_ref() {
+        Some(parent) if graph.find_sub_graph(parent).is_some() => {
+            graph.sub_graph_mut(parent).unwrap().insert_node(summary)
+        }
+        _ => graph.insert_node(summary),
+    }
+    for edge in rerouted {
+        graph.insert_edge(edge);
+    }
+
+    Ok(CollapsedCluster {
+        cluster,
+        parent,
+        edges,
+    })
+}
+
+///
+/// Collapses every cluster at the nesting `depth`, where the clusters of the root graph are at
+/// depth 0; clusters nested more deeply are collapsed along with their parent. Any label in
+/// `options` is used for every summary node. Expanding the returned clusters in reverse order
+/// restores the original graph. Every cluster is checked before any is collapsed, so on error
+/// the graph is unchanged.
+///
+pub fn collapse_at_depth(
+    graph: &mut RootGraph,
+    depth: usize,
+    options: &CollapseOptions,
+) -> Result<Vec<CollapsedCluster>, Error> {
+    let mut ids: Vec<Identifier> = Vec::default();
+    for cluster in graph.all_clusters().filter(|c| c.depth() == depth) {
+        let id = cluster.item().id();
+        if !ids.contains(id) {
+            ids.push(id.clone());
+        }
+    }
+    for id in ids.iter() {
+        let _ = collapsible_parent(graph, id)?;
+    }
+    // Clusters at the same depth are disjoint, so collapsing one neither removes another nor
+    // adds a node that collides with another.
+    ids.iter()
+        .map(|id| collapse_cluster(graph, id, options))
+        .collect()
+}
+
+///
+/// Replaces the summary node of a collapsed cluster, and all edges to or from it, with the
+/// original cluster and edges.
+///
+pub fn expand_cluster(graph: &mut RootGraph, collapsed: CollapsedCluster) -> Result<(), Error> {
+    let id = collapsed.cluster.id().clone();
+    if !graph.contains_node(&id) {
+        return Err(invalid_value("Identifier", &id));
+    }
+    let _ = graph.remove_node(&id, true);
+
+    match collapsed.parent.as_ref() {
+        Some(parent) if graph.find_sub_graph(parent).is_some() => graph
+            .sub_graph_mut(parent)
+            .unwrap()
+            .insert_sub_graph(collapsed.cluster),
+        _ => graph.insert_sub_graph(collapsed.cluster),
+    }
+    for (scope, edge) in collapsed.edges {
+        match scope.as_ref() {
+            Some(scope) if graph.find_sub_graph(scope).is_some() => {
+                graph.sub_graph_mut(scope).unwrap().insert_edge(edge)
+            }
+            _ => graph.insert_edge(edge),
+        }
+    }
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl CollapseOptions {
+    pub fn label(self, label: LabelString) -> Self {
+        let mut self_mut = self;
+        self_mut.label = Some(label);
+        self_mut
+    }
+
+    ///
+    /// Label each merged edge with the number of edges it replaces.
+    ///
+    pub fn count_labels(self) -> Self {
+        let mut self_mut = self;
+        self_mut.count_labels = true;
+        self_mut
+    }
+}
+
+impl Identified for CollapsedCluster {
+    fn id(&self) -> &Identifier {
+        self.cluster.id()
+    }
+}
+
+impl CollapsedCluster {
+    pub fn cluster(&self) -> &SubGraphKind {
+        &self.cluster
+    }
+
+    ///
+    /// The sub-graph or cluster the collapsed cluster was declared in, `None` for the root graph.
+    ///
+    pub fn parent(&self) -> Option<&Identifier> {
+        self.parent.as_ref()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the scope of the cluster `id`, `None` for the root graph, if it may be collapsed.
+///
+fn collapsible_parent(graph: &RootGraph, id: &Identifier) -> Result<Option<Identifier>, Error> {
+    let parent = match graph
+        .all_sub_graphs()
+        .find(|s| s.item().id() == id && s.item().is_cluster_graph())
+    {
+        Some(found) => (found.depth() > 0).then(|| found.scope().clone()),
+        None => return Err(invalid_value("Identifier", id)),
+    };
+    if graph.adjacency().contains_node(id) {
+        return Err(invalid_state(
+            "CollapsedCluster",
+            &format!("a node already has the cluster identifier {}", id),
+        ));
+    }
+    Ok(parent)
+}
+
+///
+/// Reroutes the edges that cross the cluster boundary to the summary node `id`. Edges that
+/// become duplicates, in either order if the graph is undirected, are merged into one edge that
+/// keeps only the attributes they all share.
+///
+fn reroute(
+    edges: &[&Edge],
+    members: &HashSet<Identifier>,
+    id: &Identifier,
+    directed: bool,
+    count_labels: bool,
+) -> Vec<Edge> {
+    let mut rerouted: Vec<(Identifier, Identifier, Vec<Attribute>, usize)> = Vec::default();
+    for edge in edges.iter() {
+        let head_inside = members.contains(edge.head());
+        let tail_inside = members.contains(edge.tail());
+        if head_inside == tail_inside {
+            continue;
+        }
+        let head = if head_inside { id } else { edge.head() };
+        let tail = if tail_inside { id } else { edge.tail() };
+        let attributes: &[Attribute] = edge.attributes().map_or(&[], |a| a.as_ref());
+        match rerouted
+            .iter_mut()
+            .find(|(h, t, _, _)| (h == head && t == tail) || (!directed && h == tail && t == head))
+        {
+            Some((_, _, shared, count)) => {
+                shared.retain(|a| attributes.contains(a));
+                *count += 1;
+            }
+            None => rerouted.push((head.clone(), tail.clone(), attributes.to_vec(), 1)),
+        }
+    }
+    rerouted
+        .into_iter()
+        .map(|(head, tail, shared, count)| {
+            let mut attributes = EdgeAttributes::default();
+            *attributes.as_mut() = shared;
+            if count > 1 && count_labels {
+                attributes
+                    .as_mut()
+                    .retain(|a| !matches!(a, Attribute::Label(_)));
+                attributes = attributes.label(LabelString::new_unchecked(&count.to_string()));
+            }
+            let edge = Edge::new(head, tail);
+            if attributes.as_ref().is_empty() {
+                edge
+            } else {
+                edge.set_attributes(attributes)
+            }
+        })
+        .collect()
+}
+
+fn cluster_label(cluster: &SubGraphKind) -> Option<LabelString> {
+    let attributes = match cluster {
+        SubGraphKind::Graph(v) => v.attributes().map(AsRef::as_ref),
+        SubGraphKind::Cluster(v) => v.attributes().map(AsRef::as_ref),
+    };
+    attributes
+        .into_iter()
+        .flatten()
+        .rev()
+        .find_map(|a| match a {
+            Attribute::Label(label) => Some(label.clone()),
+            _ => None,
+        })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -0,0 +1,109 @@
+/*!
+Changes the direction of a graph, reverses edges, and symmetrizes directed graphs.
+
+Reversing an edge swaps its head and tail and also swaps every attribute that applies to only one
+end of the edge, such as `headlabel` and `taillabel` or `arrowhead` and `arrowtail`, so that
+each stays with the same node. The `dir` attribute is left as it is, so the reversed edge always
+points the other way.
+
+# Example
+
+ */
+
+use crate::graph::Graph;
+use crate::{Edge, Identifier, RootGraph};
+use std::collections::HashSet;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Makes the graph, and every sub-graph, cluster and edge within it, directed.
+///
+pub fn to_directed(graph: &mut RootGraph) {
+    *graph = std::mem::take(graph).convert_direction(true);
+}
+
+///
+/// Makes the graph, and every sub-graph, cluster and edge within it, undirected. In a strict
+/// graph `a -> b` and `b -> a` become duplicates and are merged.
+///
+pub fn to_undirected(graph: &mut RootGraph) {
+    *graph = std::mem::take(graph).convert_direction(false);
+}
+
+///
+/// Reverses every edge in the graph. Returns the number of edges reversed.
+///
+pub fn reverse_edges(graph: &mut RootGraph) -> usize {
+    reverse_edges_where(graph, |_| true)
+}
+
+///
+/// Reverses every edge for which `predicate` returns `true`. Returns the number of edges
+/// reversed.
+///
+pub fn reverse_edges_where<F>(graph: &mut RootGraph, predicate: F) -> usize
+where
+    F: Fn(&Edge) -> bool,
+{
+    let mut reversed = 0;
+    graph.for_each_edge_mut(|edge| {
+        if predicate(edge) {
+            *edge = edge.clone().reverse();
+            reversed += 1;
+        }
+    });
+    reversed
+}
+
+///
+/// Adds, for each edge `a -> b` in a directed graph with no matching `b -> a`, the reversed
+/// edge to the same graph, sub-graph or cluster. Returns the number of edges added; undirected
+/// graphs are unchanged.
+///
+pub fn symmetrize(graph: &mut RootGraph) -> usize {
+    if !graph.is_directed() {
+        return 0;
+    }
+    let mut existing: HashSet<(Identifier, Identifier)> = graph
+        .all_edges()
+        .map(|e| (e.item().head().clone(), e.item().tail().clone()))
+        .collect();
+    let mut added = 0;
+    graph.for_each_edge_list_mut(&mut |edges| {
+        let reversed: Vec<Edge> = edges
+            .iter()
+            .filter(|e| existing.insert((e.tail().clone(), e.head().clone())))
+            .map(|e| e.clone().reverse())
+            .collect();
+        added += reversed.len();
+        edges.extend(reversed);
+    });
+    added
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
@@ -0,0 +1,194 @@
+/*!
+Extracts a focused view of a graph, either the sub-graph induced by a node predicate or the
+neighborhood within a number of hops of a set of nodes.
+
+The extracted graph keeps the identifier, attributes and default attributes of the original
+graph, and each retained node stays in the sub-graph or cluster in which it was declared;
+sub-graphs and clusters with no retained content are dropped. Only edges between two retained
+nodes are kept, although stub nodes may be added to show where edges were cut.
+
+# Example
+
+ */
+
+use crate::attributes::{EdgeAttributes, EdgeStyles, LabelString, NodeAttributes, Shape, Styled};
+use crate::graph::{Adjacency, Graph};
+use crate::{Edge, Identified, Identifier, Node, RootGraph};
+use std::collections::{HashSet, VecDeque};
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The edges followed when computing a neighborhood; for undirected graphs all edges are
+/// followed regardless of this value.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Hops {
+    Outgoing,
+    Incoming,
+    #[default]
+    Both,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExtractOptions {
+    stubs: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the graph induced by all nodes for which `predicate` returns `true`. Nodes that are
+/// only referred to by edges, and never declared, are tested as a node with no attributes.
+///
+pub fn induced_sub_graph<F>(graph: &RootGraph, predicate: F, options: &ExtractOptions) -> RootGraph
+where
+    F: Fn(&Node) -> bool,
+{
+    let retained: HashSet<Identifier> = graph
+        .adjacency()
+        .nodes()
+        .filter(|id| match graph.find_node(id) {
+            Some(node) => predicate(node),
+            None => predicate(&Node::new((*id).clone())),
+        })
+        .cloned()
+        .collect();
+    extract(graph, &retained, options)
+}
+
+///
+/// Returns the graph induced by all nodes within `hops` edges of any of the nodes in `ids`,
+/// following edges in the direction given by `direction`.
+///
+pub fn neighborhood(
+    graph: &RootGraph,
+    ids: &[Identifier],
+    hops: usize,
+    direction: Hops,
+    options: &ExtractOptions,
+) -> RootGraph {
+    let retained = {
+        let adjacency = graph.adjacency();
+        let mut retained: HashSet<Identifier> = HashSet::default();
+        let mut queue: VecDeque<(&Identifier, usize)> = VecDeque::default();
+        for id in ids.iter().filter(|id| adjacency.contains_node(id)) {
+            if retained.insert(id.clone()) {
+                queue.push_back((id, 0));
+            }
+        }
+        while let Some((id, distance)) = queue.pop_front() {
+            if distance == hops {
+                continue;
+            }
+            for next in next_hops(&adjacency, id, direction) {
+                if retained.insert(next.clone()) {
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        retained
+    };
+    extract(graph, &retained, options)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ExtractOptions {
+    ///
+    /// For each retained node with edges that were cut add a stub node, labeled "…", connected
+    /// in the direction of the cut edges.
+    ///
+    pub fn with_stubs(self) -> Self {
+        let mut self_mut = self;
+        self_mut.stubs = true;
+        self_mut
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn next_hops<'a>(
+    adjacency: &Adjacency<'a>,
+    id: &Identifier,
+    direction: Hops,
+) -> Vec<&'a Identifier> {
+    match direction {
+        Hops::Outgoing => adjacency.successors(id),
+        Hops::Incoming => adjacency.predecessors(id),
+        Hops::Both => adjacency.neighbors(id),
+    }
+}
+
+fn extract(
+    graph: &RootGraph,
+    retained: &HashSet<Identifier>,
+    options: &ExtractOptions,
+) -> RootGraph {
+    let mut extracted = graph.filtered(graph.id().clone(), &|id| retained.contains(id), &|edge| {
+        retained.contains(edge.head()) && retained.contains(edge.tail())
+    });
+
+    if options.stubs {
+        let mut cut_from: Vec<&Identifier> = Vec::default();
+        let mut cut_to: Vec<&Identifier> = Vec::default();
+        for edge in graph.all_edges().map(|e| e.item()) {
+            match (
+                retained.contains(edge.head()),
+                retained.contains(edge.tail()),
+            ) {
+                (true, false) if !cut_from.contains(&edge.head()) => cut_from.push(edge.head()),
+                (false, true) if !cut_to.contains(&edge.tail()) => cut_to.push(edge.tail()),
+                _ => {}
+            }
+        }
+        for id in cut_from {
+            let stub = stub_id(id, "out");
+            extracted.insert_node(stub_node(stub.clone()));
+            extracted.insert_edge(stub_edge(id.clone(), stub));
+        }
+        for id in cut_to {
+            let stub = stub_id(id, "in");
+            extracted.insert_node(stub_node(stub.clone()));
+            extracted.insert_edge(stub_edge(stub, id.clone()));
+        }
+    }
+    extracted
+}
+
+#[inline]
+fn stub_id(id: &Identifier, direction: &str) -> Identifier {
+    id.derived("", &format!("_stub_{}", direction))
+}
+
+fn stub_node(id: Identifier) -> Node {
+    Node::new(id).set_attributes(
+        NodeAttributes::default()
+            .label(LabelString::new_unchecked("…"))
+            .shape(Shape::PlainText),
+    )
+}
+
+fn stub_edge(head: Identifier, tail: Identifier) -> Edge {
+    Edge::new(head, tail).set_attributes(EdgeAttributes::default().style(vec![EdgeStyles::Dashed]))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
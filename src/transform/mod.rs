@@ -0,0 +1,58 @@
+/*!
+Transformations that rewrite a graph in place, typically to improve the layout produced by
+Graphviz, equivalent to a number of the command-line tools that ship with it.
+
+# Example
+
+ */
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+pub mod bundle;
+pub use bundle::{bundle_edges, bundle_edges_with, BundleMetric, BundleOptions};
+
+pub mod ccomps;
+pub use ccomps::{connected_components, packed_connected_components};
+
+pub mod collapse;
+pub use collapse::{
+    collapse_at_depth, collapse_cluster, expand_cluster, CollapseOptions, CollapsedCluster,
+};
+
+pub mod direction;
+pub use direction::{reverse_edges, reverse_edges_where, symmetrize, to_directed, to_undirected};
+
+pub mod extract;
+pub use extract::{induced_sub_graph, neighborhood, ExtractOptions, Hops};
+
+pub mod tred;
+pub use tred::{transitive_reduction, TransitiveReduction};
+
+pub mod unflatten;
+pub use unflatten::{unflatten, UnflattenOptions};
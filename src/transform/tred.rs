@@ -0,0 +1,165 @@
+/*!
+Computes the transitive reduction of a directed graph, equivalent to the Graphviz `tred` tool.
+
+An edge `a -> c` is removed if `c` is also reachable from `a` along some other path. Where the
+graph contains cycles the reduction is not unique; as with `tred` the nodes in each strongly
+connected component are treated as a single node, edges within a component are retained, and the
+components themselves are reported so that the caller may warn about them.
+
+# Example
+
+ */
+
+use crate::algo::{tarjan, Indexed};
+use crate::error::{invalid_state, Error};
+use crate::graph::Graph;
+use crate::{Edge, Identifier, RootGraph};
+use std::collections::HashSet;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default)]
+pub struct TransitiveReduction {
+    removed: Vec<Edge>,
+    cycles: Vec<Vec<Identifier>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Removes all redundant transitive edges from `graph`, including those declared inside nested
+/// sub-graphs and clusters. Retained edges keep their attributes, and the removed edges are
+/// returned so that they may be added back with a different style if required.
+///
+pub fn transitive_reduction(graph: &mut RootGraph) -> Result<TransitiveReduction, Error> {
+    if !graph.is_directed() {
+        return Err(invalid_state(
+            "RootGraph",
+            "transitive reduction requires a directed graph",
+        ));
+    }
+
+    let (keep, cycles) = {
+        let indexed = Indexed::new(graph);
+        let components = tarjan(&indexed);
+        let cycles: Vec<Vec<Identifier>> = components
+            .iter()
+            .filter(|c| c.len() > 1)
+            .map(|c| indexed.identifiers(c.clone()))
+            .collect();
+        (redundant_edges(&indexed, &components), cycles)
+    };
+
+    let removed: Vec<Edge> = graph
+        .all_edges()
+        .zip(keep.iter())
+        .filter(|(_, keep)| !**keep)
+        .map(|(edge, _)| edge.item().clone())
+        .collect();
+
+    let mut keep = keep.into_iter();
+    graph.retain_edges(|_| keep.next().unwrap_or(true));
+
+    Ok(TransitiveReduction { removed, cycles })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl TransitiveReduction {
+    pub fn removed_edges(&self) -> &[Edge] {
+        &self.removed
+    }
+
+    pub fn into_removed_edges(self) -> Vec<Edge> {
+        self.removed
+    }
+
+    pub fn has_cycles(&self) -> bool {
+        !self.cycles.is_empty()
+    }
+
+    pub fn cycles(&self) -> &[Vec<Identifier>] {
+        &self.cycles
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns, for each edge in deep traversal order, whether the edge is to be kept.
+///
+fn redundant_edges(indexed: &Indexed<'_>, components: &[Vec<usize>]) -> Vec<bool> {
+    let mut component_of: Vec<usize> = vec![0; indexed.len()];
+    for (component, members) in components.iter().enumerate() {
+        for member in members {
+            component_of[*member] = component;
+        }
+    }
+
+    let mut children: Vec<Vec<usize>> = vec![Vec::default(); components.len()];
+    for (node, successors) in indexed.successors.iter().enumerate() {
+        let from = component_of[node];
+        for successor in successors {
+            let to = component_of[*successor];
+            if from != to && !children[from].contains(&to) {
+                children[from].push(to);
+            }
+        }
+    }
+
+    // a child of a component is redundant if it can also be reached through another child.
+    let mut redundant: Vec<HashSet<usize>> = vec![HashSet::default(); components.len()];
+    for component in 0..components.len() {
+        let mut visited: HashSet<usize> = HashSet::default();
+        let mut stack: Vec<usize> = children[component]
+            .iter()
+            .flat_map(|child| children[*child].iter().copied())
+            .collect();
+        while let Some(next) = stack.pop() {
+            if visited.insert(next) {
+                stack.extend(children[next].iter().copied());
+            }
+        }
+        redundant[component] = children[component]
+            .iter()
+            .filter(|child| visited.contains(child))
+            .copied()
+            .collect();
+    }
+
+    let mut seen_edges: HashSet<(usize, usize)> = HashSet::default();
+    let mut seen_components: HashSet<(usize, usize)> = HashSet::default();
+    (0..indexed.adjacency.edge_count())
+        .map(|edge| {
+            let (head, tail) = indexed.adjacency.edge_ends(edge);
+            let (from, to) = (component_of[head], component_of[tail]);
+            if !seen_edges.insert((head, tail)) {
+                false
+            } else if from == to {
+                true
+            } else {
+                !redundant[from].contains(&to) && seen_components.insert((from, to))
+            }
+        })
+        .collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
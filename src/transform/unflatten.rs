@@ -0,0 +1,212 @@
+/*!
+Adjusts the `minlen` of edges to improve the aspect ratio of wide graphs, equivalent to the
+Graphviz `unflatten` tool.
+
+Graphs where many leaves hang off a single node are laid out by `dot` with all the leaves on
+the same rank, producing very wide output. This transform staggers the minimum length of such
+leaf edges over a number of ranks and can also chain disconnected nodes together, using
+invisible edges, so that they no longer share a single rank.
+
+# Example
+
+ */
+
+use crate::attributes::{Attribute, EdgeAttributes, EdgeStyles, Styled, Unsigned};
+use crate::graph::{Adjacency, Graph};
+use crate::{Edge, Identifier, RootGraph};
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Options for the `unflatten` transform; the default options leave the graph unchanged.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UnflattenOptions {
+    max_min_length: Unsigned,
+    fans: bool,
+    chain_limit: Unsigned,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Staggers the `minlen` of leaf edges, and chains disconnected nodes, as described by
+/// `options`. Edges that already have a `minlen` attribute are left unchanged, and any chain
+/// edges are added to the root graph with the `invis` style.
+///
+pub fn unflatten(graph: &mut RootGraph, options: &UnflattenOptions) {
+    let (min_lengths, chains) = {
+        let adjacency = graph.adjacency();
+        (
+            leaf_min_lengths(&adjacency, options),
+            disconnected_chains(&adjacency, options),
+        )
+    };
+
+    let mut min_lengths = min_lengths.into_iter();
+    graph.for_each_edge_mut(|edge| {
+        if let Some(Some(min_length)) = min_lengths.next() {
+            edge.attributes_or_default_mut()
+                .as_mut()
+                .push(Attribute::MinLength(min_length));
+        }
+    });
+
+    for (from, to) in chains {
+        graph.insert_edge(
+            Edge::new(from, to)
+                .set_attributes(EdgeAttributes::default().style(vec![EdgeStyles::Invisible])),
+        );
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl UnflattenOptions {
+    ///
+    /// Stagger the `minlen` of leaf edges from 1 up to `max_min_length`.
+    ///
+    pub fn max_min_length(self, max_min_length: Unsigned) -> Self {
+        let mut self_mut = self;
+        self_mut.max_min_length = max_min_length;
+        self_mut
+    }
+
+    ///
+    /// Also stagger edges to nodes with exactly one in and one out edge, which is only used
+    /// when `max_min_length` is set.
+    ///
+    pub fn fans(self) -> Self {
+        let mut self_mut = self;
+        self_mut.fans = true;
+        self_mut
+    }
+
+    ///
+    /// Chain disconnected nodes together in chains of at most `chain_limit` nodes.
+    ///
+    pub fn chain_limit(self, chain_limit: Unsigned) -> Self {
+        let mut self_mut = self;
+        self_mut.chain_limit = chain_limit;
+        self_mut
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns, for each edge in deep traversal order, any new `minlen` value.
+///
+fn leaf_min_lengths(
+    adjacency: &Adjacency<'_>,
+    options: &UnflattenOptions,
+) -> Vec<Option<Unsigned>> {
+    let mut min_lengths: Vec<Option<Unsigned>> = vec![None; adjacency.edge_count()];
+    if options.max_min_length < 1 {
+        return min_lengths;
+    }
+
+    let mut in_edges: Vec<Vec<usize>> = vec![Vec::default(); adjacency.node_count()];
+    let mut out_edges: Vec<Vec<usize>> = vec![Vec::default(); adjacency.node_count()];
+    for edge in 0..adjacency.edge_count() {
+        let (head, tail) = adjacency.edge_ends(edge);
+        out_edges[head].push(edge);
+        in_edges[tail].push(edge);
+    }
+    let degree = |node: usize| (in_edges[node].len(), out_edges[node].len());
+    let is_leaf = |node: usize| {
+        let (in_degree, out_degree) = degree(node);
+        in_degree + out_degree == 1
+    };
+    let is_chain_node = |node: usize| degree(node) == (1, 1);
+    let has_min_length: Vec<bool> = adjacency
+        .edges()
+        .map(|e| {
+            e.attributes()
+                .map(|a| {
+                    a.as_ref()
+                        .iter()
+                        .any(|a| matches!(a, Attribute::MinLength(_)))
+                })
+                .unwrap_or_default()
+        })
+        .collect();
+
+    for node in 0..adjacency.node_count() {
+        let (in_degree, out_degree) = degree(node);
+        if in_degree + out_degree < 2 {
+            continue;
+        }
+        let mut count = 0;
+        for edge in in_edges[node].iter() {
+            let (other, _) = adjacency.edge_ends(*edge);
+            if is_leaf(other) && min_lengths[*edge].is_none() && !has_min_length[*edge] {
+                min_lengths[*edge] = Some((count % options.max_min_length) + 1);
+                count += 1;
+            }
+        }
+        let mut count = 0;
+        for edge in out_edges[node].iter() {
+            let (_, other) = adjacency.edge_ends(*edge);
+            if (is_leaf(other) || (options.fans && is_chain_node(other)))
+                && min_lengths[*edge].is_none()
+                && !has_min_length[*edge]
+            {
+                min_lengths[*edge] = Some((count % options.max_min_length) + 1);
+                count += 1;
+            }
+        }
+    }
+    min_lengths
+}
+
+fn disconnected_chains(
+    adjacency: &Adjacency<'_>,
+    options: &UnflattenOptions,
+) -> Vec<(Identifier, Identifier)> {
+    let mut chains: Vec<(Identifier, Identifier)> = Vec::default();
+    if options.chain_limit < 1 {
+        return chains;
+    }
+
+    let mut chain_node: Option<&Identifier> = None;
+    let mut chain_size: Unsigned = 0;
+    for node in adjacency.nodes() {
+        if adjacency.degree(node) != 0 {
+            continue;
+        }
+        if let Some(previous) = chain_node {
+            chains.push((previous.clone(), node.clone()));
+            chain_size += 1;
+            if chain_size < options.chain_limit {
+                chain_node = Some(node);
+            } else {
+                chain_node = None;
+                chain_size = 0;
+            }
+        } else {
+            chain_node = Some(node);
+        }
+    }
+    chains
+}
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
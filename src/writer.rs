@@ -7,11 +7,14 @@ More detailed description, with
 
  */
 
-use crate::attributes::{Attributes, Styled};
-use crate::error::Error;
+use crate::attributes::{
+    Attributes, ClusterAttributes, EdgeAttributes, GraphAttributes, NodeAttributes, Styled,
+};
+use crate::error::{invalid_state, Error};
 use crate::graph::{Cluster, Graph, SubGraph, SubGraphKind};
-use crate::{Identified, Node, RootGraph};
+use crate::{Edge, Identified, Identifier, Node, RootGraph};
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::io::Write;
 
 // ------------------------------------------------------------------------------------------------
@@ -22,6 +25,27 @@ use std::io::Write;
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
+///
+/// An incremental writer that emits DOT statements directly to the underlying `Write`
+/// implementation as they are provided, rather than requiring a complete `RootGraph` to be
+/// built in memory first.
+///
+/// The graph header is written on construction and the closing brace by `finish`; nesting of
+/// sub-graphs and clusters is checked at runtime, so that ending a scope that is not the current
+/// one, or finishing with open scopes, results in an error.
+///
+#[derive(Debug)]
+pub struct DotStreamWriter<W>
+where
+    W: Write,
+{
+    writer: W,
+    indenter: Indenter,
+    directed: bool,
+    scopes: Vec<StreamScope>,
+    last_statement: Option<StreamStatement>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
@@ -52,6 +76,22 @@ pub struct Indenter {
     stack: RefCell<Vec<String>>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum StreamScope {
+    Root,
+    Graph,
+    Cluster,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum StreamStatement {
+    Attributes,
+    Defaults,
+    SubGraph,
+    Node,
+    Edge,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Implementations
 // ------------------------------------------------------------------------------------------------
@@ -112,21 +152,215 @@ impl Indenter {
     }
 }
 
+// ------------------------------------------------------------------------------------------------
+
+impl<W> DotStreamWriter<W>
+where
+    W: Write,
+{
+    pub fn new(writer: W, id: &Identifier, strict: bool, directed: bool) -> Result<Self, Error> {
+        Self::with_indenter(
+            writer,
+            Indenter::pad_with_two_spaces(),
+            id,
+            strict,
+            directed,
+        )
+    }
+
+    pub fn with_indenter(
+        writer: W,
+        indenter: Indenter,
+        id: &Identifier,
+        strict: bool,
+        directed: bool,
+    ) -> Result<Self, Error> {
+        let mut self_mut = Self {
+            writer,
+            indenter,
+            directed,
+            scopes: vec![StreamScope::Root],
+            last_statement: None,
+        };
+        write_root_graph_start(id, strict, directed, &mut self_mut.writer)?;
+        self_mut.indenter.indent();
+        Ok(self_mut)
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    pub fn graph_attributes(&mut self, attributes: &GraphAttributes) -> Result<(), Error> {
+        if self.current_scope() == StreamScope::Cluster {
+            return Err(invalid_state(
+                STREAM_WRITER_TYPE_NAME,
+                "graph attributes may not be written inside a cluster",
+            ));
+        }
+        self.statement(StreamStatement::Attributes)?;
+        write_attributes(attributes, &mut self.writer, &self.indenter, false)
+    }
+
+    pub fn cluster_attributes(&mut self, attributes: &ClusterAttributes) -> Result<(), Error> {
+        if self.current_scope() != StreamScope::Cluster {
+            return Err(invalid_state(
+                STREAM_WRITER_TYPE_NAME,
+                "cluster attributes may only be written inside a cluster",
+            ));
+        }
+        self.statement(StreamStatement::Attributes)?;
+        write_attributes(attributes, &mut self.writer, &self.indenter, false)
+    }
+
+    pub fn default_graph_attributes(&mut self, attributes: &GraphAttributes) -> Result<(), Error> {
+        self.statement(StreamStatement::Defaults)?;
+        write_block_attributes(attributes, &mut self.writer, &self.indenter, "graph")
+    }
+
+    pub fn default_node_attributes(&mut self, attributes: &NodeAttributes) -> Result<(), Error> {
+        self.statement(StreamStatement::Defaults)?;
+        write_block_attributes(attributes, &mut self.writer, &self.indenter, "node")
+    }
+
+    pub fn default_edge_attributes(&mut self, attributes: &EdgeAttributes) -> Result<(), Error> {
+        self.statement(StreamStatement::Defaults)?;
+        write_block_attributes(attributes, &mut self.writer, &self.indenter, "edge")
+    }
+
+    pub fn node(&mut self, node: &Node) -> Result<(), Error> {
+        self.statement(StreamStatement::Node)?;
+        write_node(node, &mut self.writer, &self.indenter)
+    }
+
+    pub fn edge(&mut self, edge: &Edge) -> Result<(), Error> {
+        self.statement(StreamStatement::Edge)?;
+        write_edge(
+            edge.head(),
+            edge.tail(),
+            self.directed,
+            edge.attributes(),
+            &mut self.writer,
+            &self.indenter,
+        )
+    }
+
+    pub fn edge_between(&mut self, from: &Identifier, to: &Identifier) -> Result<(), Error> {
+        self.statement(StreamStatement::Edge)?;
+        write_edge(
+            from,
+            to,
+            self.directed,
+            None,
+            &mut self.writer,
+            &self.indenter,
+        )
+    }
+
+    pub fn begin_sub_graph(&mut self, id: &Identifier) -> Result<(), Error> {
+        self.statement(StreamStatement::SubGraph)?;
+        write_sub_graph_start(Some(id), &mut self.writer, &self.indenter)?;
+        self.push_scope(StreamScope::Graph);
+        Ok(())
+    }
+
+    ///
+    /// Begin an anonymous sub-graph, written as a bare `{ ... }` block and ended with
+    /// `end_sub_graph`.
+    ///
+    pub fn begin_anonymous_sub_graph(&mut self) -> Result<(), Error> {
+        self.statement(StreamStatement::SubGraph)?;
+        write_sub_graph_start(None, &mut self.writer, &self.indenter)?;
+        self.push_scope(StreamScope::Graph);
+        Ok(())
+    }
+
+    pub fn end_sub_graph(&mut self) -> Result<(), Error> {
+        self.pop_scope(StreamScope::Graph)
+    }
+
+    pub fn begin_cluster(&mut self, id: &Identifier) -> Result<(), Error> {
+        self.statement(StreamStatement::SubGraph)?;
+        write_cluster_graph_start(id, &mut self.writer, &self.indenter)?;
+        self.push_scope(StreamScope::Cluster);
+        Ok(())
+    }
+
+    pub fn end_cluster(&mut self) -> Result<(), Error> {
+        self.pop_scope(StreamScope::Cluster)
+    }
+
+    pub fn finish(self) -> Result<W, Error> {
+        let mut self_mut = self;
+        if self_mut.scopes.len() > 1 {
+            return Err(invalid_state(
+                STREAM_WRITER_TYPE_NAME,
+                &format!(
+                    "{} sub-graph or cluster scope(s) still open",
+                    self_mut.scopes.len() - 1
+                ),
+            ));
+        }
+        self_mut.indenter.unindent();
+        writeln!(self_mut.writer, "}}")?;
+        self_mut.writer.flush()?;
+        Ok(self_mut.writer)
+    }
+
+    fn current_scope(&self) -> StreamScope {
+        *self.scopes.last().unwrap()
+    }
+
+    fn statement(&mut self, statement: StreamStatement) -> Result<(), Error> {
+        if let Some(last_statement) = self.last_statement {
+            if last_statement != statement || statement == StreamStatement::SubGraph {
+                writeln!(self.writer)?;
+            }
+        }
+        self.last_statement = Some(statement);
+        Ok(())
+    }
+
+    fn push_scope(&mut self, scope: StreamScope) {
+        self.scopes.push(scope);
+        self.indenter.indent();
+        self.last_statement = None;
+    }
+
+    fn pop_scope(&mut self, scope: StreamScope) -> Result<(), Error> {
+        let current = self.current_scope();
+        if current != scope {
+            return Err(invalid_state(
+                STREAM_WRITER_TYPE_NAME,
+                &format!(
+                    "cannot end {:?} scope, current scope is {:?}",
+                    scope, current
+                ),
+            ));
+        }
+        let _ = self.scopes.pop();
+        self.indenter.unindent();
+        writeln!(self.writer, "{}}}", self.indenter.pad_string())?;
+        self.last_statement = Some(StreamStatement::SubGraph);
+        Ok(())
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+const STREAM_WRITER_TYPE_NAME: &str = "DotStreamWriter";
+
 fn write_root_graph<W>(graph: &RootGraph, w: &mut W, indenter: &Indenter) -> Result<(), Error>
 where
     W: Write,
 {
-    if graph.is_strict() {
-        write!(w, "strict ")?;
-    }
-    if graph.is_directed() {
-        write!(w, "di")?;
-    }
-    writeln!(w, "graph {} {{", graph.id())?;
+    write_root_graph_start(graph.id(), graph.is_strict(), graph.is_directed(), w)?;
     indenter.indent();
     write_graph_common(graph, w, indenter)?;
     indenter.unindent();
@@ -134,12 +368,31 @@ where
     Ok(())
 }
 
+fn write_root_graph_start<W>(
+    id: &Identifier,
+    strict: bool,
+    directed: bool,
+    w: &mut W,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    if strict {
+        write!(w, "strict ")?;
+    }
+    if directed {
+        write!(w, "di")?;
+    }
+    writeln!(w, "graph {} {{", id)?;
+    Ok(())
+}
+
 fn write_sub_graph<W>(graph: &SubGraph, w: &mut W, indenter: &Indenter) -> Result<(), Error>
 where
     W: Write,
 {
     let pad_string = indenter.pad_string();
-    writeln!(w, "{}subgraph {} ", pad_string, graph.id())?;
+    write_sub_graph_start((!graph.is_anonymous()).then(|| graph.id()), w, indenter)?;
     indenter.indent();
     write_graph_common(graph, w, indenter)?;
     indenter.unindent();
@@ -147,12 +400,27 @@ where
     Ok(())
 }
 
+fn write_sub_graph_start<W>(
+    id: Option<&Identifier>,
+    w: &mut W,
+    indenter: &Indenter,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    match id {
+        Some(id) => writeln!(w, "{}subgraph {} {{", indenter.pad_string(), id)?,
+        None => writeln!(w, "{}{{", indenter.pad_string())?,
+    }
+    Ok(())
+}
+
 fn write_cluster_graph<W>(graph: &Cluster, w: &mut W, indenter: &Indenter) -> Result<(), Error>
 where
     W: Write,
 {
     let pad_string = indenter.pad_string();
-    writeln!(w, "{}subgraph cluster_{} {{", pad_string, graph.id())?;
+    write_cluster_graph_start(graph.id(), w, indenter)?;
     indenter.indent();
     write_graph_common(graph, w, indenter)?;
     indenter.unindent();
@@ -160,6 +428,18 @@ where
     Ok(())
 }
 
+fn write_cluster_graph_start<W>(
+    id: &Identifier,
+    w: &mut W,
+    indenter: &Indenter,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    writeln!(w, "{}subgraph cluster_{} {{", indenter.pad_string(), id)?;
+    Ok(())
+}
+
 fn write_graph_common<A, W>(
     graph: &impl Graph<A>,
     w: &mut W,
@@ -169,8 +449,6 @@ where
     A: Attributes,
     W: Write,
 {
-    let pad_string = indenter.pad_string();
-
     if graph.has_attributes() {
         if let Some(attributes) = graph.attributes() {
             write_attributes(attributes, w, indenter, false)?;
@@ -202,29 +480,80 @@ where
         }
     }
 
-    let nodes: Vec<&Node> = graph.nodes().filter(|n| n.has_attributes()).collect();
+    // Nodes without attributes are only needed when no edge in this graph already declares
+    // them, as in rank groups.
+    let endpoints: HashSet<&Identifier> =
+        graph.edges().flat_map(|e| [e.head(), e.tail()]).collect();
+    let nodes: Vec<&Node> = graph
+        .nodes()
+        .filter(|n| n.has_attributes() || !endpoints.contains(n.id()))
+        .collect();
     if !nodes.is_empty() {
         writeln!(w)?;
-        for node in graph.nodes().filter(|n| n.has_attributes()) {
-            write!(w, "{}{}", pad_string, node.id_and_port())?;
-            write_block_attributes(node.attributes().unwrap(), w, indenter, "")?;
+        for node in nodes {
+            write_node(node, w, indenter)?;
         }
     }
 
     if graph.has_edges() {
         writeln!(w)?;
         for edge in graph.edges() {
-            write!(w, "{}{}", pad_string, edge.to_string_no_attributes())?;
-            if edge.has_attributes() {
-                write_block_attributes(edge.attributes().unwrap(), w, indenter, "")?;
-            } else {
-                writeln!(w, ";")?;
-            }
+            write_edge(
+                edge.head(),
+                edge.tail(),
+                edge.is_directed(),
+                edge.attributes(),
+                w,
+                indenter,
+            )?;
         }
     }
     Ok(())
 }
 
+fn write_node<W>(node: &Node, w: &mut W, indenter: &Indenter) -> Result<(), Error>
+where
+    W: Write,
+{
+    write!(w, "{}{}", indenter.pad_string(), node.id_and_port())?;
+    if node.has_attributes() {
+        write_block_attributes(node.attributes().unwrap(), w, indenter, "")?;
+    } else {
+        writeln!(w, ";")?;
+    }
+    Ok(())
+}
+
+fn write_edge<W>(
+    from: &Identifier,
+    to: &Identifier,
+    directed: bool,
+    attributes: Option<&EdgeAttributes>,
+    w: &mut W,
+    indenter: &Indenter,
+) -> Result<(), Error>
+where
+    W: Write,
+{
+    write!(
+        w,
+        "{}{} {} {}",
+        indenter.pad_string(),
+        from,
+        if directed { "->" } else { "--" },
+        to
+    )?;
+    match attributes {
+        Some(attributes) if !attributes.as_ref().is_empty() => {
+            write_block_attributes(attributes, w, indenter, "")
+        }
+        _ => {
+            writeln!(w, ";")?;
+            Ok(())
+        }
+    }
+}
+
 fn write_attributes<A, W>(
     attributes: &A,
     w: &mut W,
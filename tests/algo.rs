@@ -0,0 +1,95 @@
+use simple_dot::algo::{
+    dominators, find_cycle, is_acyclic, reachable, shortest_path, strongly_connected_components,
+    topological_sort, unreachable, weakly_connected_components,
+};
+use simple_dot::graph::{Cluster, Graph};
+use simple_dot::{Identifier, RootGraph};
+
+pub mod common;
+
+fn id(s: &str) -> Identifier {
+    Identifier::new_unchecked(s)
+}
+
+fn ids(vs: &[Identifier]) -> Vec<&str> {
+    vs.iter().map(|v| v.as_ref()).collect()
+}
+
+fn diamond() -> RootGraph {
+    RootGraph::new(id("D"), false, true)
+        .add_sub_graph(
+            Cluster::new(id("left"))
+                .add_edge_between(id("a"), id("b"))
+                .add_edge_between(id("b"), id("d")),
+        )
+        .add_edge_between(id("a"), id("c"))
+        .add_edge_between(id("c"), id("d"))
+        .add_edge_between(id("d"), id("e"))
+}
+
+#[test]
+fn test_cycles_and_topological_sort() {
+    let graph = diamond();
+    assert!(is_acyclic(&graph));
+    let order = topological_sort(&graph).unwrap();
+    let position = |s: &str| order.iter().position(|i| i.as_ref() == s).unwrap();
+    assert!(position("a") < position("b"));
+    assert!(position("b") < position("d"));
+    assert!(position("c") < position("d"));
+    assert!(position("d") < position("e"));
+
+    let graph = diamond().add_edge_between(id("e"), id("b"));
+    assert_eq!(ids(&find_cycle(&graph).unwrap()), vec!["d", "e", "b"]);
+    assert!(topological_sort(&graph).is_none());
+
+    let graph = RootGraph::new(id("U"), false, false)
+        .add_edge_between(id("a"), id("b"))
+        .add_edge_between(id("b"), id("c"));
+    assert!(is_acyclic(&graph));
+    let graph = graph.add_edge_between(id("c"), id("a"));
+    assert_eq!(find_cycle(&graph).unwrap().len(), 3);
+}
+
+#[test]
+fn test_reachability_and_paths() {
+    let graph = diamond().add_edge_between(id("x"), id("y"));
+
+    assert_eq!(ids(&reachable(&graph, &id("c"))), vec!["c", "d", "e"]);
+    assert_eq!(ids(&unreachable(&graph, &[id("a")])), vec!["x", "y"]);
+    assert_eq!(
+        ids(&shortest_path(&graph, &id("a"), &id("e")).unwrap()),
+        vec!["a", "c", "d", "e"]
+    );
+    assert!(shortest_path(&graph, &id("e"), &id("a")).is_none());
+}
+
+#[test]
+fn test_components() {
+    let graph = diamond()
+        .add_edge_between(id("e"), id("c"))
+        .add_edge_between(id("x"), id("y"));
+
+    let strong = strongly_connected_components(&graph);
+    assert_eq!(strong.len(), 5);
+    assert!(strong
+        .iter()
+        .any(|c| c.len() == 3 && c.contains(&id("c")) && c.contains(&id("e"))));
+
+    let weak = weakly_connected_components(&graph);
+    assert_eq!(weak.len(), 2);
+    assert_eq!(ids(&weak[1]), vec!["x", "y"]);
+
+    let gallery = common::gallery_cluster_example();
+    assert_eq!(weakly_connected_components(&gallery).len(), 2);
+}
+
+#[test]
+fn test_dominators() {
+    let graph = diamond();
+    let dominators = dominators(&graph, &id("a"));
+
+    assert_eq!(dominators.len(), 4);
+    assert_eq!(dominators[&id("b")], id("a"));
+    assert_eq!(dominators[&id("d")], id("a"));
+    assert_eq!(dominators[&id("e")], id("d"));
+}
@@ -1,23 +1,95 @@
-use simple_dot::attributes::{ArrowType, EdgeAttributes, FontName, GraphAttributes};
+use simple_dot::attributes::{
+    ArrowShape, ArrowType, ClipSide, EdgeAttributes, FontName, GraphAttributes,
+};
+use std::str::FromStr;
 
 #[test]
 fn test_arrow_types() {
     assert_eq!(ArrowType::default().to_string(), String::from("normal"));
     assert_eq!(ArrowType::a_box().to_string(), String::from("box"));
-    assert_eq!(ArrowType::a_box().open().to_string(), String::from("obox"));
     assert_eq!(
-        ArrowType::diamond().open().clip_to_left().to_string(),
+        ArrowType::a_box().open().unwrap().to_string(),
+        String::from("obox")
+    );
+    assert_eq!(
+        ArrowType::diamond()
+            .open()
+            .unwrap()
+            .clip_to_left()
+            .unwrap()
+            .to_string(),
         String::from("oldiamond")
     );
     assert_eq!(
         ArrowType::tee()
             .clip_to_left()
-            .set_next_shape(ArrowType::diamond().open().clip_to_left())
+            .unwrap()
+            .set_next_shape(ArrowType::diamond().open().unwrap().clip_to_left().unwrap())
+            .unwrap()
             .to_string(),
         String::from("lteeoldiamond")
     );
 }
 
+#[test]
+fn test_arrow_type_grammar() {
+    assert!(ArrowType::crow().open().is_ok());
+    assert!(ArrowType::tee().open().is_ok());
+    assert!(ArrowType::dot().clip_to_left().is_err());
+    assert!(ArrowType::dot().open().is_ok());
+    assert!(ArrowType::none().open().is_err());
+    assert!(ArrowType::new(ClipSide::Right, false, ArrowShape::None).is_err());
+
+    let four = ArrowType::from_str("teenormalteenormal").unwrap();
+    assert_eq!(four.shape_count(), 4);
+    assert!(ArrowType::tee().set_next_shape(four).is_err());
+    assert!(ArrowType::normal()
+        .set_next_shape(ArrowType::none())
+        .is_err());
+    assert_eq!(
+        ArrowType::none()
+            .set_next_shape(ArrowType::normal())
+            .unwrap()
+            .to_string(),
+        "nonenormal"
+    );
+}
+
+#[test]
+fn test_arrow_type_parsing() {
+    let arrow = ArrowType::from_str("lteeoldiamond").unwrap();
+    assert_eq!(arrow.shape(), ArrowShape::Tee);
+    assert_eq!(arrow.clipping(), ClipSide::Left);
+    let next = arrow.next_shape().unwrap();
+    assert_eq!(next.shape(), ArrowShape::Diamond);
+    assert!(next.is_open());
+    assert_eq!(next.clipping(), ClipSide::Left);
+    assert_eq!(arrow.to_string(), "lteeoldiamond");
+
+    assert_eq!(
+        ArrowType::from_str("lodiamond").unwrap(),
+        ArrowType::from_str("oldiamond").unwrap()
+    );
+    assert_eq!(
+        ArrowType::from_str("ediamond").unwrap(),
+        ArrowType::diamond().open().unwrap()
+    );
+    assert_eq!(ArrowType::canonical_name("halfopen").unwrap(), "lvee");
+    assert_eq!(ArrowType::canonical_name("invempty").unwrap(), "oinv");
+
+    for invalid in [
+        "",
+        "o",
+        "ldot",
+        "olrnormal",
+        "normalx",
+        "normalnone",
+        "nonenonenone",
+    ] {
+        assert!(ArrowType::from_str(invalid).is_err(), "{}", invalid);
+    }
+}
+
 #[test]
 fn test_edge_style_default() {
     assert_eq!(EdgeAttributes::default().to_string(), String::from("[]"));
@@ -0,0 +1,168 @@
+use simple_dot::attributes::{Color, ColorScheme, GraphAttributes, Scale};
+use std::str::FromStr;
+
+fn scale(v: f64) -> Scale {
+    Scale::try_from(v).unwrap()
+}
+
+#[test]
+fn test_named_colors_are_validated() {
+    assert!(Color::named("lightgrey").is_ok());
+    assert!(Color::named("crimson").is_ok());
+    assert!(Color::named("3").is_ok());
+    assert!(Color::named("/blues9/3").is_ok());
+    assert!(Color::named("//navy").is_ok());
+    assert!(Color::named("/svg/teal").is_ok());
+
+    assert!(Color::named("notacolor").is_err());
+    assert!(Color::named("/blues9/10").is_err());
+    assert!(Color::named("/blues12/3").is_err());
+    assert!(Color::named("/x11/teal").is_err());
+    assert!(Color::named("").is_err());
+}
+
+#[test]
+fn test_color_schemes() {
+    assert_eq!(ColorScheme::from_str("x11").unwrap(), ColorScheme::X11);
+    assert_eq!(ColorScheme::from_str("SVG").unwrap(), ColorScheme::Svg);
+    assert_eq!(
+        ColorScheme::from_str("accent8").unwrap(),
+        ColorScheme::brewer("accent", 8).unwrap()
+    );
+    assert_eq!(ColorScheme::from_str("set312").unwrap().size(), Some(12));
+    assert_eq!(
+        ColorScheme::from_str("rdbu11").unwrap().to_string(),
+        "rdbu11"
+    );
+
+    assert!(ColorScheme::from_str("accent9").is_err());
+    assert!(ColorScheme::from_str("blues2").is_err());
+    assert!(ColorScheme::from_str("spectral12").is_err());
+    assert!(ColorScheme::from_str("rainbow").is_err());
+
+    assert_eq!(ColorScheme::brewer_names().len(), 265);
+
+    assert_eq!(
+        GraphAttributes::default()
+            .color_scheme(ColorScheme::from_str("accent8").unwrap())
+            .to_string(),
+        "[ colorscheme = accent8 ]"
+    );
+}
+
+#[test]
+fn test_scheme_indexes() {
+    let accent = ColorScheme::from_str("accent8").unwrap();
+    assert!(accent.contains("1"));
+    assert!(accent.contains("8"));
+    assert!(!accent.contains("0"));
+    assert!(!accent.contains("9"));
+
+    let accent3 = ColorScheme::from_str("accent3").unwrap();
+    assert!(!accent3.contains("4"));
+
+    assert_eq!(
+        Color::named_in(&accent, "2").unwrap(),
+        Color::named("/accent8/2").unwrap()
+    );
+    assert!(Color::named_in(&accent3, "4").is_err());
+}
+
+#[test]
+fn test_resolve_names() {
+    assert_eq!(
+        Color::named("navy").unwrap().resolve(None).unwrap(),
+        [0x00, 0x00, 0x80, 0xff]
+    );
+    // X11 and SVG disagree on gray.
+    assert_eq!(
+        Color::named("gray").unwrap().resolve(None).unwrap(),
+        [0xbe, 0xbe, 0xbe, 0xff]
+    );
+    assert_eq!(
+        Color::named("/svg/gray").unwrap().resolve(None).unwrap(),
+        [0x80, 0x80, 0x80, 0xff]
+    );
+    let svg = ColorScheme::Svg;
+    assert_eq!(
+        Color::named("gray").unwrap().resolve(Some(&svg)).unwrap(),
+        [0x80, 0x80, 0x80, 0xff]
+    );
+    // Names missing from the current scheme fall back to X11.
+    let blues = ColorScheme::from_str("blues9").unwrap();
+    assert_eq!(
+        Color::named("red").unwrap().resolve(Some(&blues)).unwrap(),
+        [0xff, 0x00, 0x00, 0xff]
+    );
+    assert!(Color::named("12").unwrap().resolve(Some(&blues)).is_err());
+    assert!(Color::named("3").unwrap().resolve(None).is_err());
+}
+
+#[test]
+fn test_resolve_brewer_sizes() {
+    let resolve = |name: &str| Color::named(name).unwrap().resolve(None).unwrap();
+    assert_eq!(resolve("/blues3/3"), [0x31, 0x82, 0xbd, 0xff]);
+    assert_eq!(resolve("/blues9/9"), [0x08, 0x30, 0x6b, 0xff]);
+    assert_eq!(resolve("/rdbu3/2"), [0xf7, 0xf7, 0xf7, 0xff]);
+    assert_eq!(resolve("/rdbu11/1"), [0x67, 0x00, 0x1f, 0xff]);
+    assert_eq!(resolve("/paired12/12"), [0xb1, 0x59, 0x28, 0xff]);
+}
+
+#[test]
+fn test_conversions() {
+    let red = Color::named("red").unwrap();
+    assert_eq!(red.to_rgb().unwrap(), Color::rgb(255, 0, 0));
+    assert_eq!(red.to_rgba().unwrap(), Color::rgba(255, 0, 0, 255));
+    assert_eq!(
+        red.to_hsv().unwrap(),
+        Color::hsv(scale(0.0), scale(1.0), scale(1.0))
+    );
+    assert_eq!(
+        Color::hsv(scale(2.0 / 3.0), scale(1.0), scale(1.0))
+            .to_rgb()
+            .unwrap(),
+        Color::rgb(0, 0, 255)
+    );
+    assert_eq!(
+        Color::rgb(0, 0, 128).to_name(),
+        Some(Color::named("navy").unwrap())
+    );
+    assert_eq!(Color::rgba(0, 0, 128, 64).to_name(), None);
+    assert_eq!(Color::rgb(1, 2, 3).to_name(), None);
+
+    let orange = Color::from_str("#ffa500").unwrap();
+    let round_trip = orange.to_hsv().unwrap().to_rgb().unwrap();
+    assert_eq!(round_trip, orange);
+}
+
+#[test]
+fn test_contrasting() {
+    let black = Color::named("black").unwrap();
+    let white = Color::named("white").unwrap();
+    assert_eq!(black.contrasting().unwrap(), white);
+    assert_eq!(white.contrasting().unwrap(), black);
+    assert_eq!(
+        Color::named("yellow").unwrap().contrasting().unwrap(),
+        black
+    );
+    assert_eq!(Color::named("navy").unwrap().contrasting().unwrap(), white);
+    assert!((black.contrast_ratio(&white).unwrap() - 21.0).abs() < 1e-9);
+    assert!(Color::named("/accent8/9").is_err());
+}
+
+#[test]
+fn test_color_from_str() {
+    assert_eq!(Color::from_str("#ff8000").unwrap(), Color::rgb(255, 128, 0));
+    assert_eq!(
+        Color::from_str("#ff800080").unwrap(),
+        Color::rgba(255, 128, 0, 128)
+    );
+    assert_eq!(
+        Color::from_str("navy").unwrap(),
+        Color::named("navy").unwrap()
+    );
+    assert!(Color::from_str("0.5 0.5 0.5").is_ok());
+    assert_eq!(Color::from_str("3").unwrap(), Color::named("3").unwrap());
+    assert!(Color::from_str("#ff80").is_err());
+    assert!(Color::from_str("0.5 2.0 0.5").is_err());
+}
@@ -60,11 +60,11 @@ pub fn gallery_cluster_example() -> RootGraph {
         )
         .add_node(
             Node::new(Identifier::from_str("start").unwrap())
-                .set_attributes(NodeAttributes::default().shape(Shape::m_diamond())),
+                .set_attributes(NodeAttributes::default().shape(Shape::MDiamond)),
         )
         .add_node(
             Node::new(Identifier::from_str("end").unwrap())
-                .set_attributes(NodeAttributes::default().shape(Shape::m_square())),
+                .set_attributes(NodeAttributes::default().shape(Shape::MSquare)),
         )
         .add_edge_between(
             Identifier::from_str("a1").unwrap(),
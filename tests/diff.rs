@@ -0,0 +1,190 @@
+use simple_dot::attributes::{
+    Attribute, ClusterAttributes, Color, ColorOrList, LabelString, NodeAttributes, Styled,
+};
+use simple_dot::diff::{diff, diff_graph, AttributeChange, DiffStyle, Difference};
+use simple_dot::graph::{Cluster, Graph, SubGraph};
+use simple_dot::{Identifier, Node, RootGraph};
+
+fn id(s: &str) -> Identifier {
+    Identifier::new_unchecked(s)
+}
+
+fn label(s: &str) -> NodeAttributes {
+    NodeAttributes::default().label(LabelString::new_unchecked(s))
+}
+
+fn before() -> RootGraph {
+    RootGraph::new(id("before"), false, true)
+        .add_sub_graph(
+            Cluster::new(id("api"))
+                .add_node(Node::new(id("gateway")).set_attributes(label("Gateway")))
+                .add_node(Node::new(id("auth"))),
+        )
+        .add_sub_graph(Cluster::new(id("legacy")).add_node(Node::new(id("soap"))))
+        .add_node(Node::new(id("db")))
+        .add_edge_between(id("gateway"), id("auth"))
+        .add_edge_between(id("soap"), id("db"))
+}
+
+fn after() -> RootGraph {
+    RootGraph::new(id("after"), false, true)
+        .add_sub_graph(
+            Cluster::new(id("api"))
+                .set_attributes(
+                    ClusterAttributes::default().label(LabelString::new_unchecked("API")),
+                )
+                .add_node(Node::new(id("gateway")).set_attributes(label("API Gateway"))),
+        )
+        .add_node(Node::new(id("auth")))
+        .add_node(Node::new(id("db")))
+        .add_node(Node::new(id("cache")))
+        .add_edge_between(id("gateway"), id("auth"))
+        .add_edge_between(id("auth"), id("cache"))
+}
+
+#[test]
+fn test_diff_identical() {
+    assert!(diff(&before(), &before()).is_empty());
+}
+
+#[test]
+fn test_diff_nodes() {
+    let differences = diff(&before(), &after());
+    assert_eq!(
+        differences.removed_nodes().collect::<Vec<&Identifier>>(),
+        vec![&id("soap")]
+    );
+    assert_eq!(
+        differences.added_nodes().collect::<Vec<&Identifier>>(),
+        vec![&id("cache")]
+    );
+    assert_eq!(
+        differences.changed_nodes().collect::<Vec<&Identifier>>(),
+        vec![&id("gateway"), &id("auth")]
+    );
+
+    let gateway = &differences.nodes()[0];
+    assert!(!gateway.is_moved());
+    assert_eq!(
+        gateway.attributes(),
+        &[AttributeChange::Changed {
+            from: Attribute::Label(LabelString::new_unchecked("Gateway")),
+            to: Attribute::Label(LabelString::new_unchecked("API Gateway")),
+        }]
+    );
+
+    let auth = &differences.nodes()[1];
+    assert!(auth.is_moved());
+    assert_eq!(auth.from_scope(), Some(&id("api")));
+    assert_eq!(auth.to_scope(), None);
+}
+
+#[test]
+fn test_diff_edges_and_clusters() {
+    let differences = diff(&before(), &after());
+    assert_eq!(
+        differences
+            .edges()
+            .iter()
+            .map(|e| (e.head().to_string(), e.difference()))
+            .collect::<Vec<(String, Difference)>>(),
+        vec![
+            (String::from("soap"), Difference::Removed),
+            (String::from("auth"), Difference::Added)
+        ]
+    );
+    assert_eq!(
+        differences
+            .clusters()
+            .map(|c| (c.id().to_string(), c.difference()))
+            .collect::<Vec<(String, Difference)>>(),
+        vec![
+            (String::from("api"), Difference::Changed),
+            (String::from("legacy"), Difference::Removed)
+        ]
+    );
+    assert!(differences.attributes().is_empty());
+}
+
+#[test]
+fn test_diff_graph() {
+    let style = DiffStyle::default().added(Color::Name(String::from("blue")));
+    let graph = diff_graph(&before(), &after(), &style);
+
+    let color = |id: &Identifier| {
+        graph
+            .find_node(id)
+            .and_then(|n| n.attributes())
+            .and_then(|a| {
+                a.as_ref()
+                    .iter()
+                    .find(|a| matches!(a, Attribute::Color(_)))
+                    .cloned()
+            })
+    };
+    assert_eq!(
+        color(&id("cache")),
+        Some(Attribute::Color(ColorOrList::Color(Color::Name(
+            String::from("blue")
+        ))))
+    );
+    assert_eq!(
+        color(&id("soap")),
+        Some(Attribute::Color(ColorOrList::Color(Color::Name(
+            String::from("red")
+        ))))
+    );
+    assert!(color(&id("db")).is_none());
+
+    let legacy = graph.find_sub_graph(&id("legacy")).unwrap();
+    assert!(legacy.find_node(&id("soap")).is_some());
+    assert!(graph.find_edge(&id("soap"), &id("db")).is_some());
+    assert_eq!(graph.all_edges().count(), 3);
+}
+
+#[test]
+fn test_diff_undirected_edges() {
+    let from = RootGraph::new(id("G"), false, false).add_edge_between(id("a"), id("b"));
+    let to = RootGraph::new(id("G"), false, false).add_edge_between(id("b"), id("a"));
+    assert!(diff(&from, &to).is_empty());
+
+    let from = RootGraph::new(id("G"), false, true).add_edge_between(id("a"), id("b"));
+    let to = RootGraph::new(id("G"), false, true).add_edge_between(id("b"), id("a"));
+    assert_eq!(diff(&from, &to).edges().len(), 2);
+}
+
+#[test]
+fn test_diff_anonymous_sub_graphs() {
+    let graph = || {
+        RootGraph::new(id("G"), false, true)
+            .same_rank(vec![id("a"), id("b")])
+            .add_sub_graph(Cluster::new(id("c")).same_rank(vec![id("d"), id("e")]))
+    };
+    let from = graph();
+    let to = graph();
+    assert!(diff(&from, &to).is_empty());
+
+    let to = to.add_edge_between(id("a"), id("d"));
+    let differences = diff(&from, &to);
+    assert!(differences.nodes().is_empty());
+    assert!(differences.sub_graphs().is_empty());
+    assert_eq!(differences.edges().len(), 1);
+}
+
+#[test]
+fn test_diff_graph_removed_sub_graph() {
+    let from = RootGraph::new(id("G"), false, true)
+        .add_sub_graph(SubGraph::new(id("group")).add_node(Node::new(id("a"))))
+        .add_node(Node::new(id("b")));
+    let to = RootGraph::new(id("G"), false, true).add_node(Node::new(id("b")));
+    let differences = diff(&from, &to);
+    assert_eq!(differences.sub_graphs().len(), 1);
+    assert_eq!(
+        differences.sub_graphs()[0].difference(),
+        Difference::Removed
+    );
+
+    let graph = diff_graph(&from, &to, &DiffStyle::default());
+    assert!(graph.find_sub_graph(&id("group")).is_none());
+    assert!(graph.find_node(&id("a")).is_some());
+}
@@ -0,0 +1,131 @@
+use simple_dot::attributes::{
+    ClusterAttributes, ClusterStyles, Color, Fill, FillKind, NodeAttributes, NodeStyles, Scale,
+    WeightedColor,
+};
+
+fn named(name: &str) -> Color {
+    Color::named(name).unwrap()
+}
+
+fn weighted(name: &str, weight: f64) -> WeightedColor {
+    WeightedColor::weighted(named(name), Scale::try_from(weight).unwrap())
+}
+
+#[test]
+fn test_solid_and_gradient_fills() {
+    assert_eq!(
+        NodeAttributes::default()
+            .fill(Fill::solid(named("red")))
+            .unwrap()
+            .to_string(),
+        "[ fillcolor = red; style = filled ]"
+    );
+    assert_eq!(
+        NodeAttributes::default()
+            .fill(
+                Fill::linear(named("white"), named("blue"))
+                    .weight(Scale::try_from(0.3).unwrap())
+                    .angle(90)
+            )
+            .unwrap()
+            .to_string(),
+        "[ fillcolor = \"white;0.3:blue\"; gradientangle = 90; style = filled ]"
+    );
+    assert_eq!(
+        ClusterAttributes::default()
+            .fill(Fill::radial(named("white"), named("grey")))
+            .unwrap()
+            .to_string(),
+        "[ fillcolor = \"white:grey\"; style = radial ]"
+    );
+}
+
+#[test]
+fn test_striped_and_wedged_fills() {
+    assert_eq!(
+        NodeAttributes::default()
+            .fill(Fill::striped(vec![
+                weighted("red", 0.2),
+                named("green").into(),
+                named("blue").into()
+            ]))
+            .unwrap()
+            .to_string(),
+        "[ fillcolor = \"red;0.2:green:blue\"; style = striped ]"
+    );
+    assert_eq!(
+        ClusterAttributes::default()
+            .fill(Fill::striped(vec![
+                weighted("red", 0.5),
+                weighted("blue", 0.5)
+            ]))
+            .unwrap()
+            .to_string(),
+        "[ fillcolor = \"red;0.5:blue;0.5\"; style = striped ]"
+    );
+
+    let pie = Fill::ratios(vec![(named("green"), 1.0), (named("red"), 3.0)]).unwrap();
+    assert_eq!(pie.kind(), FillKind::Wedged);
+    assert_eq!(
+        NodeAttributes::default()
+            .fill(pie.clone())
+            .unwrap()
+            .to_string(),
+        "[ fillcolor = \"green;0.25:red;0.75\"; style = wedged ]"
+    );
+    assert!(ClusterAttributes::default().fill(pie).is_err());
+
+    assert!(Fill::ratios(vec![(named("green"), 0.0)]).is_err());
+    assert!(Fill::ratios(vec![(named("green"), -1.0), (named("red"), 2.0)]).is_err());
+}
+
+#[test]
+fn test_fill_validation() {
+    assert!(
+        Fill::striped(vec![weighted("red", 0.6), weighted("blue", 0.5)])
+            .validate()
+            .is_err()
+    );
+    assert!(Fill::wedged(vec![]).validate().is_err());
+    assert!(Fill::striped(vec![named("red").into()])
+        .angle(45)
+        .validate()
+        .is_err());
+    assert!(Fill::solid(named("red")).angle(45).validate().is_err());
+    assert!(Fill::radial(named("red"), named("blue"))
+        .angle(45)
+        .validate()
+        .is_ok());
+    assert!(NodeAttributes::default()
+        .fill(Fill::wedged(vec![
+            weighted("red", 0.7),
+            weighted("blue", 0.7)
+        ]))
+        .is_err());
+}
+
+#[test]
+fn test_fill_replaces_fill_style() {
+    let attributes = NodeAttributes::default()
+        .style(vec![NodeStyles::Rounded, NodeStyles::Filled])
+        .fill(Fill::linear(named("white"), named("blue")).angle(90))
+        .unwrap()
+        .fill(Fill::wedged(vec![
+            named("red").into(),
+            named("blue").into(),
+        ]))
+        .unwrap();
+    assert_eq!(
+        attributes.to_string(),
+        "[ style = rounded,wedged; fillcolor = \"red:blue\" ]"
+    );
+
+    let attributes = ClusterAttributes::default()
+        .style(vec![ClusterStyles::Rounded])
+        .fill(Fill::solid(named("lightgrey")))
+        .unwrap();
+    assert_eq!(
+        attributes.to_string(),
+        "[ style = rounded,filled; fillcolor = lightgrey ]"
+    );
+}
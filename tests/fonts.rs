@@ -0,0 +1,181 @@
+use simple_dot::attributes::{
+    EdgeAttributes, FontName, FontNameMapping, GraphAttributes, NodeAttributes, Styled,
+};
+use simple_dot::fonts::{used_fonts, FontCatalog, GenericFamily};
+use simple_dot::graph::Graph;
+use simple_dot::{Edge, Identifier, Node, RootGraph};
+use std::path::PathBuf;
+
+fn id(s: &str) -> Identifier {
+    Identifier::new_unchecked(s)
+}
+
+fn font(family: &str) -> FontName {
+    FontName::family(family).unwrap()
+}
+
+///
+/// A TrueType file containing only a `name` table with a Windows English family name.
+///
+fn true_type_font(family: &str) -> Vec<u8> {
+    let name: Vec<u8> = family
+        .encode_utf16()
+        .flat_map(|c| c.to_be_bytes())
+        .collect();
+    let mut bytes: Vec<u8> = Vec::default();
+    bytes.extend([0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+    bytes.extend(b"name");
+    bytes.extend([0, 0, 0, 0, 0, 0, 0, 28]);
+    bytes.extend((18 + name.len() as u32).to_be_bytes());
+    bytes.extend([0, 0, 0, 1, 0, 18]);
+    bytes.extend([0, 3, 0, 1, 0x04, 0x09, 0, 1]);
+    bytes.extend((name.len() as u16).to_be_bytes());
+    bytes.extend([0, 0]);
+    bytes.extend(name);
+    bytes
+}
+
+fn font_directory() -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("simple_dot_fonts_{}", std::process::id()));
+    let nested = directory.join("truetype/corporate");
+    std::fs::create_dir_all(&nested).unwrap();
+    std::fs::write(
+        nested.join("CorporateSans-Regular.ttf"),
+        true_type_font("Corporate Sans"),
+    )
+    .unwrap();
+    std::fs::write(
+        directory.join("NimbusRoman.pfa"),
+        "%!PS-AdobeFont-1.0: NimbusRoman-Regular\n/FamilyName (Nimbus Roman) readonly def\n",
+    )
+    .unwrap();
+    std::fs::write(directory.join("README"), "not a font").unwrap();
+    std::fs::write(directory.join("Broken.ttf"), [0, 1]).unwrap();
+    directory
+}
+
+#[test]
+fn test_discover_font_families() {
+    let directory = font_directory();
+    let catalog =
+        FontCatalog::discover_in(&[directory.clone(), directory.join("missing")]).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(
+        catalog.families().collect::<Vec<&str>>(),
+        vec!["Corporate Sans", "Nimbus Roman"]
+    );
+    assert!(catalog.contains("corporatesans"));
+    assert!(catalog.contains("CORPORATE SANS"));
+    assert_eq!(catalog.resolve("Times-Roman"), Some("Nimbus Roman"));
+    assert_eq!(catalog.resolve("Helvetica"), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_discover_follows_symbolic_links() {
+    let directory =
+        std::env::temp_dir().join(format!("simple_dot_linked_fonts_{}", std::process::id()));
+    let target = directory.join("target");
+    let links = directory.join("links");
+    std::fs::create_dir_all(&target).unwrap();
+    std::fs::create_dir_all(&links).unwrap();
+    std::fs::write(target.join("Linked.ttf"), true_type_font("Linked Sans")).unwrap();
+    std::os::unix::fs::symlink(&target, links.join("target")).unwrap();
+    std::os::unix::fs::symlink(&links, links.join("loop")).unwrap();
+    std::os::unix::fs::symlink(directory.join("missing"), links.join("dangling")).unwrap();
+
+    let catalog = FontCatalog::discover_in(&[links]).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert_eq!(
+        catalog.families().collect::<Vec<&str>>(),
+        vec!["Linked Sans"]
+    );
+}
+
+#[test]
+fn test_validate_fonts() {
+    let catalog = FontCatalog::default()
+        .add_family("Corporate Sans")
+        .add_family("Liberation Mono");
+
+    assert!(catalog.validate(&font("Corporate Sans").bold()).is_ok());
+    assert!(catalog.validate(&FontName::courier()).is_ok());
+    assert!(catalog.validate(&FontName::helvetica()).is_err());
+    assert!(catalog
+        .validate(&FontName::list(vec![
+            font("Corporate Sans"),
+            FontName::helvetica()
+        ]))
+        .is_ok());
+    assert!(catalog
+        .validate(&FontName::list(vec![
+            FontName::helvetica(),
+            font("Corporate Sans")
+        ]))
+        .is_err());
+}
+
+#[test]
+fn test_fallbacks() {
+    let catalog = FontCatalog::default()
+        .add_family("DejaVu Serif")
+        .add_family("Liberation Serif")
+        .add_family("DejaVu Sans Mono");
+
+    assert_eq!(
+        catalog.fallbacks(&FontName::times_roman()).to_string(),
+        "\"Liberation Serif,DejaVu Serif,serif\""
+    );
+    assert_eq!(
+        catalog
+            .fallbacks(&font("Source Code Pro").italic())
+            .to_string(),
+        "\"DejaVu Sans Mono italic,monospace italic\""
+    );
+    assert_eq!(
+        catalog
+            .fallbacks(&FontName::list(vec![font("Georgia"), font("DejaVu Serif")]))
+            .families(),
+        vec!["DejaVu Serif", "Liberation Serif", "serif"]
+    );
+
+    assert_eq!(GenericFamily::of("Arial"), GenericFamily::SansSerif);
+    assert_eq!(
+        GenericFamily::of("Noto Serif Display"),
+        GenericFamily::Serif
+    );
+    assert_eq!(GenericFamily::of("Fira Mono"), GenericFamily::Monospace);
+}
+
+#[test]
+fn test_font_names_mapping() {
+    let catalog = FontCatalog::default().add_family("Corporate Sans");
+    let graph = RootGraph::new(id("G"), false, true)
+        .set_attributes(GraphAttributes::default().font_name(FontName::helvetica()))
+        .set_default_edge_attributes(EdgeAttributes::default().font_name(FontName::courier()))
+        .add_node(
+            Node::new(id("a"))
+                .set_attributes(NodeAttributes::default().font_name(font("Corporate Sans").bold())),
+        )
+        .add_node(
+            Node::new(id("b"))
+                .set_attributes(NodeAttributes::default().font_name(FontName::helvetica())),
+        )
+        .add_edge(Edge::new(id("a"), id("b")));
+
+    let fonts = used_fonts(&graph);
+    assert_eq!(
+        fonts,
+        vec![
+            FontName::helvetica(),
+            FontName::courier(),
+            font("Corporate Sans").bold()
+        ]
+    );
+    assert_eq!(catalog.font_names(&fonts[..2]), FontNameMapping::Svg);
+    assert_eq!(catalog.font_names(&fonts[2..]), FontNameMapping::FontConfig);
+    assert_eq!(catalog.font_names(&fonts), FontNameMapping::Postscript);
+    assert!(catalog.validate_graph(&graph).is_err());
+}
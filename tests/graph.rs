@@ -1,4 +1,13 @@
-use simple_dot::{attributes::Styled, graph::Graph};
+use simple_dot::attributes::{
+    Attribute, Color, EdgeAttributes, GraphAttributes, LabelString, NodeAttributes, RankDirection,
+    Styled,
+};
+use simple_dot::graph::SelfLoops;
+use simple_dot::graph::{
+    AttributeConflict, Cluster, Graph, MergeOptions, NodeCollision, SubGraphKind,
+};
+use simple_dot::writer::write_graph_to_string;
+use simple_dot::{Edge, Identified, Identifier, Node, RootGraph};
 
 pub mod common;
 
@@ -12,3 +21,466 @@ fn test_cluster_example() {
     assert!(root.has_edges());
     assert!(root.has_sub_graphs());
 }
+
+#[test]
+fn test_find_and_update_node() {
+    let mut root = common::gallery_cluster_example();
+    let a2 = Identifier::new_unchecked("a2");
+
+    assert!(root.contains_node(&a2));
+    assert!(!root.contains_node(&Identifier::new_unchecked("z9")));
+
+    let node = root.node_mut(&a2).unwrap();
+    *node = node
+        .clone()
+        .set_attributes(NodeAttributes::default().label(LabelString::new_unchecked("A2")));
+    assert!(root.find_node(&a2).unwrap().has_attributes());
+}
+
+#[test]
+fn test_remove_node_cascade() {
+    let mut root = common::gallery_cluster_example();
+    let a3 = Identifier::new_unchecked("a3");
+
+    assert!(root.remove_node(&a3, true).is_some());
+    assert!(!root.contains_node(&a3));
+    assert!(root
+        .find_edge(&Identifier::new_unchecked("a2"), &a3)
+        .is_none());
+    assert!(root
+        .find_edge(&a3, &Identifier::new_unchecked("end"))
+        .is_none());
+
+    let mut root = common::gallery_cluster_example();
+    assert!(root.remove_node(&a3, false).is_some());
+    assert!(root
+        .find_edge(&a3, &Identifier::new_unchecked("end"))
+        .is_some());
+}
+
+#[test]
+fn test_retain_nodes_and_edges() {
+    let mut root = common::gallery_cluster_example();
+
+    root.retain_nodes(|n| !n.id().as_ref().starts_with('b'), true);
+    assert!(!root.contains_node(&Identifier::new_unchecked("b0")));
+    assert!(root
+        .find_edge(
+            &Identifier::new_unchecked("a1"),
+            &Identifier::new_unchecked("b3")
+        )
+        .is_none());
+
+    root.retain_edges(|e| e.tail().as_ref() != "end");
+    assert!(root
+        .find_edge(
+            &Identifier::new_unchecked("a3"),
+            &Identifier::new_unchecked("end")
+        )
+        .is_none());
+    assert!(root
+        .find_edge(
+            &Identifier::new_unchecked("a0"),
+            &Identifier::new_unchecked("a1")
+        )
+        .is_some());
+}
+
+#[test]
+fn test_undirected_edges_found_in_either_order() {
+    let a = Identifier::new_unchecked("a");
+    let b = Identifier::new_unchecked("b");
+    let c = Identifier::new_unchecked("c");
+    let mut root = RootGraph::new(Identifier::new_unchecked("G"), false, false)
+        .add_edge_between(a.clone(), b.clone())
+        .add_sub_graph(
+            Cluster::new(Identifier::new_unchecked("x")).add_edge_between(b.clone(), c.clone()),
+        );
+
+    assert!(root.find_edge(&b, &a).is_some());
+    assert!(root.find_edge(&c, &b).is_some());
+    assert!(root.edge_mut(&c, &b).is_some());
+    assert!(root.remove_edge(&b, &a).is_some());
+    assert!(root.remove_edge(&c, &b).is_some());
+    assert!(!root.has_edges());
+    assert_eq!(root.all_edges().count(), 0);
+
+    let directed = RootGraph::new(Identifier::new_unchecked("G"), false, true)
+        .add_edge_between(a.clone(), b.clone());
+    assert!(directed.find_edge(&b, &a).is_none());
+}
+
+#[test]
+fn test_move_node_into_cluster() {
+    let mut root = common::gallery_cluster_example();
+    let start = Identifier::new_unchecked("start");
+    let cluster_id: Identifier = 0i64.into();
+
+    root.move_node(&start, &cluster_id).unwrap();
+    assert!(root.nodes().all(|n| n.id() != &start));
+    assert!(root
+        .find_sub_graph(&cluster_id)
+        .unwrap()
+        .find_node(&start)
+        .is_some());
+
+    assert!(root
+        .move_node(&start, &Identifier::new_unchecked("nowhere"))
+        .is_err());
+    assert!(root.contains_node(&start));
+
+    let missing = Identifier::new_unchecked("missing");
+    assert!(root.move_node(&missing, &cluster_id).is_err());
+    assert!(!root.contains_node(&missing));
+}
+
+#[test]
+fn test_all_nodes_with_path() {
+    let root = common::gallery_cluster_example();
+
+    let nodes: Vec<String> = root
+        .all_nodes()
+        .map(|n| format!("{}/{}", n.scope(), n.item().id()))
+        .collect();
+    assert_eq!(nodes.len(), 10);
+    assert!(nodes.contains(&String::from("G/start")));
+    assert!(nodes.contains(&String::from("1/b2")));
+
+    let b2 = root
+        .all_nodes()
+        .find(|n| n.item().id().as_ref() == "b2")
+        .unwrap();
+    assert_eq!(b2.depth(), 1);
+    assert_eq!(b2.path()[0].as_ref(), "G");
+
+    assert_eq!(root.all_edges().count(), 12);
+    assert_eq!(root.all_clusters().count(), 2);
+}
+
+#[test]
+fn test_adjacency_queries() {
+    let root = common::gallery_cluster_example();
+    let a3 = Identifier::new_unchecked("a3");
+
+    let successors: Vec<&str> = root
+        .successors(&a3)
+        .into_iter()
+        .map(|i| i.as_ref())
+        .collect();
+    assert_eq!(successors, vec!["a0", "end"]);
+
+    let predecessors: Vec<&str> = root
+        .predecessors(&a3)
+        .into_iter()
+        .map(|i| i.as_ref())
+        .collect();
+    assert_eq!(predecessors, vec!["b2", "a2"]);
+
+    assert_eq!(root.incident_edges(&a3).len(), 5);
+    assert_eq!(root.degree(&a3), 5);
+
+    let adjacency = root.adjacency();
+    assert_eq!(adjacency.in_degree(&a3), 3);
+    assert_eq!(adjacency.out_degree(&a3), 2);
+    assert_eq!(adjacency.node_count(), 10);
+    assert!(adjacency
+        .successors(&Identifier::new_unchecked("end"))
+        .is_empty());
+}
+
+fn fragment(name: &str, label: &str) -> RootGraph {
+    let id = |s: &str| Identifier::new_unchecked(s);
+    RootGraph::new(id(name), false, true)
+        .set_attributes(GraphAttributes::default().label(LabelString::new_unchecked(name)))
+        .add_node(
+            Node::new(id("db"))
+                .set_attributes(NodeAttributes::default().label(LabelString::new_unchecked(label))),
+        )
+        .add_node(Node::new(id(name)))
+        .add_edge_between(id(name), id("db"))
+}
+
+#[test]
+fn test_merge_unify() {
+    let db = Identifier::new_unchecked("db");
+    let merged = RootGraph::merge_all(
+        Identifier::new_unchecked("system"),
+        vec![
+            fragment("orders", "Orders DB"),
+            fragment("billing", "Billing DB"),
+        ],
+        &MergeOptions::default().attribute_conflict(AttributeConflict::FirstWins),
+    )
+    .unwrap();
+
+    assert_eq!(merged.nodes().filter(|n| n.id() == &db).count(), 1);
+    assert_eq!(
+        merged
+            .find_node(&db)
+            .unwrap()
+            .attributes()
+            .unwrap()
+            .as_ref(),
+        &vec![Attribute::Label(LabelString::new_unchecked("Orders DB"))]
+    );
+    assert_eq!(merged.degree(&db), 2);
+    assert_eq!(
+        merged.attributes().unwrap().as_ref(),
+        &vec![Attribute::Label(LabelString::new_unchecked("orders"))]
+    );
+}
+
+#[test]
+fn test_merge_prefix_in_clusters() {
+    let merged = fragment("orders", "Orders DB")
+        .merge(
+            fragment("billing", "Billing DB"),
+            &MergeOptions::default()
+                .node_collision(NodeCollision::Prefix)
+                .wrap_in_clusters(),
+        )
+        .unwrap();
+
+    let billing_db = Identifier::new_unchecked("billing_db");
+    assert!(merged.contains_node(&Identifier::new_unchecked("db")));
+    assert!(merged
+        .find_sub_graph(&Identifier::new_unchecked("billing"))
+        .unwrap()
+        .find_node(&billing_db)
+        .is_some());
+    assert!(merged
+        .find_edge(&Identifier::new_unchecked("billing"), &billing_db)
+        .is_some());
+    assert_eq!(merged.degree(&Identifier::new_unchecked("db")), 1);
+}
+
+#[test]
+fn test_merge_errors() {
+    let options = MergeOptions::default().node_collision(NodeCollision::Error);
+    assert!(fragment("orders", "DB")
+        .merge(fragment("billing", "DB"), &options)
+        .is_err());
+
+    let options = MergeOptions::default().attribute_conflict(AttributeConflict::Error);
+    assert!(fragment("orders", "Orders DB")
+        .merge(fragment("billing", "Billing DB"), &options)
+        .is_err());
+
+    let undirected = RootGraph::new(Identifier::new_unchecked("u"), false, false);
+    assert!(fragment("orders", "DB")
+        .merge(undirected, &MergeOptions::default())
+        .is_err());
+
+    let rank = RootGraph::new(Identifier::new_unchecked("r"), false, true)
+        .set_attributes(GraphAttributes::default().rank_direction(RankDirection::LeftToRight));
+    assert!(fragment("orders", "DB").merge(rank, &options).is_ok());
+
+    // Nodes only referenced by edges are not declared, so do not collide.
+    let referenced = RootGraph::new(Identifier::new_unchecked("r"), false, true).add_edge_between(
+        Identifier::new_unchecked("x"),
+        Identifier::new_unchecked("db"),
+    );
+    assert!(fragment("orders", "DB")
+        .merge(
+            referenced,
+            &MergeOptions::default().node_collision(NodeCollision::Error)
+        )
+        .is_ok());
+
+    // Renaming `db` in `orders` would collide with the node `orders_db` already there.
+    let prefixed =
+        fragment("billing", "DB").add_node(Node::new(Identifier::new_unchecked("orders_db")));
+    assert!(prefixed
+        .merge(
+            fragment("orders", "DB"),
+            &MergeOptions::default().node_collision(NodeCollision::Prefix)
+        )
+        .is_err());
+}
+
+#[test]
+fn test_merge_quoted_and_scoped_defaults() {
+    let quoted = RootGraph::new(Identifier::new_unchecked("\"order service\""), false, true)
+        .set_default_node_attributes(
+            NodeAttributes::default().color(Color::named("red").unwrap().into()),
+        )
+        .add_node(Node::new(Identifier::new_unchecked("db")));
+    let merged = fragment("billing", "DB")
+        .set_default_node_attributes(
+            NodeAttributes::default().color(Color::named("blue").unwrap().into()),
+        )
+        .merge(
+            quoted,
+            &MergeOptions::default()
+                .node_collision(NodeCollision::Prefix)
+                .attribute_conflict(AttributeConflict::Error)
+                .wrap_in_clusters(),
+        )
+        .unwrap();
+
+    let renamed = Identifier::new_unchecked("\"order service_db\"");
+    let cluster = merged
+        .find_sub_graph(&Identifier::new_unchecked("\"order service\""))
+        .unwrap();
+    assert!(cluster.find_node(&renamed).is_some());
+    match cluster {
+        SubGraphKind::Cluster(cluster) => assert_eq!(
+            cluster.default_node_attributes().unwrap().to_string(),
+            "[ color = red ]"
+        ),
+        SubGraphKind::Graph(_) => panic!("expected a cluster"),
+    }
+    assert_eq!(
+        merged.default_node_attributes().unwrap().to_string(),
+        "[ color = blue ]"
+    );
+}
+
+fn labeled_edge(from: &str, to: &str, label: &str) -> Edge {
+    Edge::new(
+        Identifier::new_unchecked(from),
+        Identifier::new_unchecked(to),
+    )
+    .set_attributes(EdgeAttributes::default().label(LabelString::new_unchecked(label)))
+}
+
+#[test]
+fn test_strict_graph_merges_edges() {
+    let a = Identifier::new_unchecked("a");
+    let b = Identifier::new_unchecked("b");
+    let mut graph = RootGraph::new(Identifier::new_unchecked("G"), true, false)
+        .add_edge(labeled_edge("a", "b", "first"))
+        .add_edge(labeled_edge("b", "a", "second"))
+        .add_edge_between(a.clone(), a.clone());
+    assert_eq!(graph.all_edges().count(), 2);
+    assert_eq!(
+        graph
+            .find_edge(&a, &b)
+            .unwrap()
+            .attributes()
+            .unwrap()
+            .as_ref(),
+        &vec![Attribute::Label(LabelString::new_unchecked("second"))]
+    );
+
+    graph = graph.set_self_loops(SelfLoops::Drop);
+    assert_eq!(graph.all_edges().count(), 1);
+
+    graph = graph.set_self_loops(SelfLoops::Reject);
+    assert!(graph
+        .try_insert_edge(Edge::new(b.clone(), b.clone()))
+        .is_err());
+    assert!(graph
+        .try_insert_edge(Edge::new(b.clone(), a.clone()))
+        .is_ok());
+    assert_eq!(graph.all_edges().count(), 1);
+
+    assert!(graph
+        .clone()
+        .try_add_edge(Edge::new(a.clone(), a.clone()))
+        .is_err());
+    graph.insert_edge(Edge::new(a.clone(), a.clone()));
+    assert_eq!(graph.all_edges().count(), 2);
+    assert!(graph.check_self_loops().is_err());
+    assert!(write_graph_to_string(&graph).is_ok());
+}
+
+#[test]
+fn test_strict_graph_merges_edges_changed_in_place() {
+    let id = Identifier::new_unchecked;
+    let edges = |graph: &RootGraph| {
+        graph
+            .all_edges()
+            .map(|e| format!("{}->{}", e.item().head(), e.item().tail()))
+            .collect::<Vec<_>>()
+    };
+    let mut graph = RootGraph::new(id("G"), true, true);
+    graph.insert_edge(Edge::new(id("a"), id("b")));
+    graph.insert_edge(Edge::new(id("c"), id("d")));
+    *graph.edge_mut(&id("a"), &id("b")).unwrap() = Edge::new(id("x"), id("y"));
+    graph.insert_edge(Edge::new(id("a"), id("b")));
+    assert_eq!(edges(&graph), vec!["x->y", "c->d", "a->b"]);
+
+    *graph.edge_mut(&id("a"), &id("b")).unwrap() = Edge::new(id("c"), id("d"));
+    assert_eq!(edges(&graph), vec!["x->y", "c->d"]);
+}
+
+#[test]
+fn test_strict_graph_merges_sub_graph_edges_changed_in_place() {
+    let id = Identifier::new_unchecked;
+    let cluster_id = id("cluster_0");
+    let mut graph = RootGraph::new(id("G"), true, true)
+        .add_edge(Edge::new(id("c"), id("d")))
+        .add_sub_graph(Cluster::new(cluster_id.clone()).add_edge(Edge::new(id("a"), id("b"))));
+    *graph
+        .sub_graph_mut(&cluster_id)
+        .unwrap()
+        .edge_mut(&id("a"), &id("b"))
+        .unwrap() = Edge::new(id("x"), id("y"));
+    graph.insert_edge(Edge::new(id("a"), id("b")));
+    assert_eq!(graph.all_edges().count(), 3);
+
+    *graph
+        .sub_graph_mut(&cluster_id)
+        .unwrap()
+        .edge_mut(&id("x"), &id("y"))
+        .unwrap() = Edge::new(id("c"), id("d"));
+    assert_eq!(graph.all_edges().count(), 2);
+}
+
+#[test]
+fn test_strict_graph_merges_sub_graph_edges() {
+    let a = Identifier::new_unchecked("a");
+    let b = Identifier::new_unchecked("b");
+    let cluster_id = Identifier::new_unchecked("cluster_0");
+    let mut graph = RootGraph::new(Identifier::new_unchecked("G"), true, true)
+        .add_edge(labeled_edge("a", "b", "first"))
+        .add_sub_graph(
+            Cluster::new(cluster_id.clone())
+                .add_edge(labeled_edge("a", "b", "second"))
+                .add_edge(labeled_edge("b", "a", "reverse")),
+        );
+    assert_eq!(graph.all_edges().count(), 2);
+
+    graph
+        .sub_graph_mut(&cluster_id)
+        .unwrap()
+        .insert_edge(labeled_edge("a", "b", "third"));
+    graph
+        .sub_graph_mut(&cluster_id)
+        .unwrap()
+        .insert_edge(labeled_edge("b", "c", "new"));
+    assert_eq!(graph.all_edges().count(), 3);
+    assert_eq!(
+        graph
+            .find_edge(&a, &b)
+            .unwrap()
+            .attributes()
+            .unwrap()
+            .as_ref(),
+        &vec![Attribute::Label(LabelString::new_unchecked("third"))]
+    );
+}
+
+#[test]
+fn test_into_strict() {
+    let graph = RootGraph::new(Identifier::new_unchecked("G"), false, true)
+        .add_edge(labeled_edge("a", "b", "first"))
+        .add_edge(labeled_edge("a", "b", "second"))
+        .add_edge(labeled_edge("b", "a", "reverse"))
+        .add_edge_between(
+            Identifier::new_unchecked("a"),
+            Identifier::new_unchecked("a"),
+        );
+    assert_eq!(graph.all_edges().count(), 4);
+
+    assert!(graph
+        .clone()
+        .set_self_loops(SelfLoops::Reject)
+        .into_strict()
+        .is_err());
+
+    let strict = graph.into_strict().unwrap();
+    assert!(strict.is_strict());
+    assert_eq!(strict.all_edges().count(), 3);
+}
@@ -0,0 +1,188 @@
+use simple_dot::attributes::{
+    ArrowType, Color, EdgeAttributes, EdgeStyles, LabelString, NodeAttributes, RankType, Shape,
+    Styled,
+};
+use simple_dot::graph::Graph;
+use simple_dot::legend::{LegendBuilder, LegendForm, LegendKey};
+use simple_dot::writer::write_graph_to_string;
+use simple_dot::{Edge, Identified, Identifier, Node, RootGraph};
+
+fn id(s: &str) -> Identifier {
+    Identifier::new_unchecked(s)
+}
+
+fn services_graph() -> RootGraph {
+    RootGraph::new(id("G"), false, true)
+        .add_node(
+            Node::new(id("users")).set_attributes(
+                NodeAttributes::default()
+                    .shape(Shape::Cylinder)
+                    .class(vec!["database".to_string()]),
+            ),
+        )
+        .add_node(
+            Node::new(id("api")).set_attributes(
+                NodeAttributes::default()
+                    .shape(Shape::Box)
+                    .fill_color(Color::named("lightblue").unwrap().into()),
+            ),
+        )
+        .add_node(Node::new(id("web")))
+        .add_edge(Edge::new(id("web"), id("api")))
+        .add_edge(
+            Edge::new(id("api"), id("users")).set_attributes(
+                EdgeAttributes::default()
+                    .style(vec![EdgeStyles::Dashed])
+                    .arrow_head(ArrowType::diamond()),
+            ),
+        )
+}
+
+fn described() -> LegendBuilder {
+    LegendBuilder::new(id("legend"))
+        .title("Legend")
+        .describe_class("database", "Data store")
+        .describe(LegendKey::Shape(Shape::Box), "Service")
+        .describe(
+            LegendKey::FillColor(Color::named("lightblue").unwrap().into()),
+            "Owned by us",
+        )
+        .describe(LegendKey::EdgeStyle(EdgeStyles::Dashed), "Asynchronous")
+        .describe(LegendKey::Arrow(ArrowType::diamond()), "Writes to")
+}
+
+#[test]
+fn test_undescribed_styles_are_reported() {
+    let legend = LegendBuilder::new(id("legend"))
+        .describe_class("database", "Data store")
+        .build(&services_graph());
+    assert!(!legend.is_complete());
+    // The cylinder is explained by the database class.
+    assert_eq!(
+        legend.undescribed(),
+        &[
+            LegendKey::Shape(Shape::Box),
+            LegendKey::FillColor(Color::named("lightblue").unwrap().into()),
+            LegendKey::EdgeStyle(EdgeStyles::Dashed),
+            LegendKey::Arrow(ArrowType::diamond()),
+        ]
+    );
+    assert!(LegendBuilder::new(id("legend"))
+        .apply(services_graph())
+        .is_err());
+}
+
+#[test]
+fn test_default_attributes_are_inspected() {
+    let graph = services_graph()
+        .set_default_node_attributes(NodeAttributes::default().shape(Shape::Octagon));
+    let legend = described().build(&graph);
+    assert_eq!(legend.undescribed(), &[LegendKey::Shape(Shape::Octagon)]);
+}
+
+#[test]
+fn test_legend_entries() {
+    let legend = described().build(&services_graph());
+    assert!(legend.is_complete());
+    let entries: Vec<(String, &str)> = legend
+        .entries()
+        .map(|(key, description)| (key.to_string(), description))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            ("class=database".to_string(), "Data store"),
+            ("shape=box".to_string(), "Service"),
+            ("fillcolor=lightblue".to_string(), "Owned by us"),
+            ("style=dashed".to_string(), "Asynchronous"),
+            ("arrow=diamond".to_string(), "Writes to"),
+        ]
+    );
+}
+
+#[test]
+fn test_cluster_legend() {
+    let graph = described().apply(services_graph()).unwrap();
+    let legend = graph.all_clusters().next().unwrap().item();
+    assert_eq!(legend.nodes().count(), 7);
+    assert_eq!(legend.edges().count(), 2);
+    assert_eq!(legend.sub_graphs().count(), 1);
+
+    let dot = write_graph_to_string(&graph).unwrap();
+    assert!(dot.contains("newrank = true"));
+    assert!(dot.contains("rank = sink"));
+    assert!(dot.contains(
+        "    {\n      rank = sink;\n\n      legend_0;\n      legend_1;\n      legend_2;\n"
+    ));
+    assert!(dot.contains(
+        "    legend_0 [\n      shape = cylinder;\n      class = database;\n      label = \"Data store\";\n    ];"
+    ));
+}
+
+#[test]
+fn test_table_legend() {
+    let builder = described()
+        .form(LegendForm::Table)
+        .placement(RankType::Source);
+    let node = builder.build(&services_graph()).to_table_node();
+    let label = node
+        .attributes()
+        .unwrap()
+        .as_ref()
+        .iter()
+        .find(|a| a.name() == "label")
+        .unwrap()
+        .to_string();
+    assert!(label.starts_with("label = <<TABLE"));
+    assert!(label.contains("<TR><TD COLSPAN=\"2\"><B>Legend</B></TD></TR>"));
+    assert!(label
+        .contains("<TR><TD BGCOLOR=\"lightblue\"> </TD><TD ALIGN=\"LEFT\">Owned by us</TD></TR>"));
+
+    let dot = write_graph_to_string(&builder.apply(services_graph()).unwrap()).unwrap();
+    assert!(dot.contains("rank = source"));
+    assert!(!dot.contains("newrank"));
+}
+
+#[test]
+fn test_html_labels() {
+    assert_eq!(
+        LabelString::html("<B>bold text</B>").to_string(),
+        "<<B>bold text</B>>"
+    );
+    assert_eq!(
+        LabelString::new_unchecked("some text").to_string(),
+        "\"some text\""
+    );
+}
+
+#[test]
+fn test_quoted_legend_entry_ids() {
+    let legend = LegendBuilder::new(id("\"my legend\""))
+        .describe(LegendKey::Shape(Shape::Box), "Service")
+        .describe(LegendKey::EdgeStyle(EdgeStyles::Dashed), "Asynchronous")
+        .build(&services_graph());
+    let cluster = legend.to_cluster();
+    let ids: Vec<String> = cluster.nodes().map(|n| n.id().to_string()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            "\"my legend_0\"".to_string(),
+            "\"my legend_1\"".to_string(),
+            "\"my legend_1_to\"".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_legend_text_with_punctuation() {
+    let legend = LegendBuilder::new(id("legend"))
+        .title("p95/latency")
+        .describe(LegendKey::Shape(Shape::Box), "read-only")
+        .describe(LegendKey::Shape(Shape::Cylinder), "Store")
+        .build(&services_graph());
+    let graph = RootGraph::new(id("G"), false, true).add_sub_graph(legend.to_cluster());
+    let dot = write_graph_to_string(&graph).unwrap();
+    assert!(dot.contains("label = \"p95/latency\";"));
+    assert!(dot.contains("label = \"read-only\";"));
+    assert!(dot.contains("label = Store;"));
+}
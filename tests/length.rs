@@ -0,0 +1,94 @@
+use simple_dot::attributes::{
+    Attribute, ClusterAttributes, EdgeAttributes, GraphAttributes, Length, LengthUnit, Margin,
+    NodeAttributes, RankSeparation, Size,
+};
+
+fn mm(value: f64) -> Length {
+    Length::millimeters(value).unwrap()
+}
+
+#[test]
+fn test_length_conversions() {
+    let inch = Length::inches(1.0).unwrap();
+    assert_eq!(inch.to_points(), 72.0);
+    assert_eq!(inch.to_millimeters(), 25.4);
+    assert_eq!(mm(25.4).to_inches(), 1.0);
+    assert_eq!(Length::points(36.0).unwrap().to_inches(), 0.5);
+    assert_eq!(Length::pixels(48.0, 96.0).unwrap().to_inches(), 0.5);
+
+    let converted = mm(50.8).to_unit(LengthUnit::Inches);
+    assert_eq!(converted.unit(), LengthUnit::Inches);
+    assert_eq!(converted.value(), 2.0);
+
+    assert!(Length::inches(-1.0).is_err());
+    assert!(Length::points(f64::INFINITY).is_err());
+    assert!(Length::pixels(10.0, 0.0).is_err());
+}
+
+#[test]
+fn test_setters_convert_units() {
+    assert_eq!(
+        NodeAttributes::default()
+            .width(mm(25.4))
+            .height(Length::points(36.0).unwrap())
+            .font_size(Length::inches(0.125).unwrap())
+            .pen_width(mm(0.0))
+            .to_string(),
+        "[ width = 1; height = 0.5; fontsize = 9; penwidth = 0 ]"
+    );
+    assert_eq!(
+        EdgeAttributes::default()
+            .pen_width(Length::inches(0.5).unwrap())
+            .label_font_size(Length::points(8.0).unwrap())
+            .to_string(),
+        "[ penwidth = 36; labelfontsize = 8 ]"
+    );
+    assert_eq!(
+        GraphAttributes::default()
+            .node_separation(Length::points(18.0).unwrap())
+            .rank_separation(RankSeparation::new_one(mm(12.7)))
+            .to_string(),
+        "[ nodesep = 0.25; ranksep = 0.5 ]"
+    );
+}
+
+#[test]
+fn test_margins_and_sizes() {
+    let margin = Margin::sides(mm(25.4), Length::points(36.0).unwrap());
+    assert_eq!(
+        NodeAttributes::default().margin(margin.clone()).to_string(),
+        "[ margin = \"1,0.5\" ]"
+    );
+    assert_eq!(
+        ClusterAttributes::default().margin(margin).to_string(),
+        "[ margin = \"72,36\" ]"
+    );
+
+    let letter = Size::new(Length::inches(8.5).unwrap(), Length::inches(11.0).unwrap()).fill();
+    assert_eq!(
+        GraphAttributes::default()
+            .size(letter.clone())
+            .page_size(letter)
+            .pad(Margin::all(Length::points(9.0).unwrap()))
+            .to_string(),
+        "[ size = \"8.5,11!\"; page = \"8.5,11\"; pad = 0.125 ]"
+    );
+    assert_eq!(
+        GraphAttributes::default()
+            .size(Size::square(mm(127.0)))
+            .to_string(),
+        "[ size = \"5,5\" ]"
+    );
+}
+
+#[test]
+fn test_attributes_written_in_inches() {
+    assert_eq!(
+        Attribute::Margin(Margin::all(mm(25.4))).to_string(),
+        "margin = 1"
+    );
+    assert_eq!(
+        Attribute::Size(Size::new(mm(127.0), Length::points(36.0).unwrap())).to_string(),
+        "size = \"5,0.5\""
+    );
+}
@@ -0,0 +1,247 @@
+use simple_dot::attributes::{
+    Attribute, Color, ColorOrList, ColorScheme, NodeAttributes, NodeStyles, Positive, Style, Styled,
+};
+use simple_dot::graph::Graph;
+use simple_dot::scale::{
+    CategoricalScale, Channel, ContinuousScale, ScaleMapping, ScaleValue, ValueScale,
+};
+use simple_dot::transform::to_undirected;
+use simple_dot::{Edge, Identified, Identifier, Node, RootGraph};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+fn id(s: &str) -> Identifier {
+    Identifier::new_unchecked(s)
+}
+
+fn node_attributes(graph: &RootGraph, name: &str) -> Vec<Attribute> {
+    graph
+        .find_node(&id(name))
+        .and_then(|n| n.attributes())
+        .map(|a| a.as_ref().clone())
+        .unwrap_or_default()
+}
+
+fn services_graph() -> RootGraph {
+    RootGraph::new(id("G"), false, true)
+        .add_node(
+            Node::new(id("api"))
+                .set_attributes(NodeAttributes::default().style(vec![NodeStyles::Rounded])),
+        )
+        .add_node(Node::new(id("db")))
+        .add_node(Node::new(id("cache")))
+        .add_edge(Edge::new(id("api"), id("db")))
+        .add_edge(Edge::new(id("api"), id("cache")))
+}
+
+#[test]
+fn test_continuous_numbers() {
+    let scale = ContinuousScale::linear(0.0, 100.0).numbers(1.0, 5.0);
+    assert_eq!(scale.map(&0.0).unwrap(), ScaleValue::Number(1.0));
+    assert_eq!(scale.map(&50.0).unwrap(), ScaleValue::Number(3.0));
+    assert_eq!(scale.map(&250.0).unwrap(), ScaleValue::Number(5.0));
+    assert_eq!(scale.map(&-1.0).unwrap(), ScaleValue::Number(1.0));
+    assert!(scale.map(&f64::NAN).is_err());
+
+    let scale = ContinuousScale::log(1.0, 1000.0).numbers(0.0, 3.0);
+    match scale.map(&100.0).unwrap() {
+        ScaleValue::Number(v) => assert!((v - 2.0).abs() < 1e-9),
+        v => panic!("unexpected {:?}", v),
+    }
+    assert!(ContinuousScale::log(0.0, 10.0).map(&1.0).is_err());
+    assert!(ContinuousScale::linear(10.0, 1.0).map(&1.0).is_err());
+
+    let scale = ContinuousScale::linear(0.0, 1.0).fit(vec![3.0, 7.0, 5.0]);
+    assert_eq!(scale.domain(), (3.0, 7.0));
+}
+
+#[test]
+fn test_continuous_colors() {
+    let scale = ContinuousScale::linear(0.0, 10.0).colors(vec![
+        Color::named("white").unwrap(),
+        Color::rgb(255, 0, 0),
+        Color::named("black").unwrap(),
+    ]);
+    assert_eq!(
+        scale.map(&0.0).unwrap(),
+        ScaleValue::Color(Color::named("white").unwrap())
+    );
+    assert_eq!(
+        scale.map(&5.0).unwrap(),
+        ScaleValue::Color(Color::rgb(255, 0, 0))
+    );
+    assert_eq!(
+        scale.map(&2.5).unwrap(),
+        ScaleValue::Color(Color::rgb(255, 128, 128))
+    );
+    assert_eq!(
+        scale.map(&7.5).unwrap(),
+        ScaleValue::Color(Color::rgb(128, 0, 0))
+    );
+    assert!(ContinuousScale::linear(0.0, 1.0)
+        .colors(vec![])
+        .map(&0.5)
+        .is_err());
+}
+
+#[test]
+fn test_categorical() {
+    let scale = CategoricalScale::new()
+        .category("payments", Color::named("red").unwrap())
+        .category("identity", Color::named("blue").unwrap());
+    assert_eq!(
+        scale.map("identity").unwrap(),
+        ScaleValue::Color(Color::named("blue").unwrap())
+    );
+    assert!(scale.map("search").is_err());
+    let scale = scale.default_value(Color::named("grey").unwrap());
+    assert_eq!(
+        scale.map("search").unwrap(),
+        ScaleValue::Color(Color::named("grey").unwrap())
+    );
+
+    let scheme = ColorScheme::from_str("set13").unwrap();
+    let scale = CategoricalScale::from_scheme(&scheme, &["a", "b", "c"]).unwrap();
+    assert_eq!(
+        scale.map("c").unwrap(),
+        ScaleValue::Color(Color::named("/set13/3").unwrap())
+    );
+    assert!(CategoricalScale::from_scheme(&scheme, &["a", "b", "c", "d"]).is_err());
+}
+
+#[test]
+fn test_apply_to_nodes() {
+    let latency: HashMap<Identifier, f64> = [(id("api"), 0.0), (id("db"), 100.0)].into();
+    let mapping = ScaleMapping::new(
+        ContinuousScale::linear(0.0, 100.0).colors(vec![
+            Color::named("white").unwrap(),
+            Color::named("red").unwrap(),
+        ]),
+        Channel::FillColor,
+    );
+    let graph = mapping.apply_to_nodes(services_graph(), &latency).unwrap();
+
+    assert_eq!(
+        node_attributes(&graph, "api"),
+        vec![
+            Attribute::Style(vec![
+                Style::Node(NodeStyles::Rounded),
+                Style::Node(NodeStyles::Filled)
+            ]),
+            Attribute::FillColor(ColorOrList::Color(Color::named("white").unwrap())),
+        ]
+    );
+    assert_eq!(
+        node_attributes(&graph, "db"),
+        vec![
+            Attribute::Style(vec![Style::Node(NodeStyles::Filled)]),
+            Attribute::FillColor(ColorOrList::Color(Color::named("red").unwrap())),
+        ]
+    );
+    assert!(node_attributes(&graph, "cache").is_empty());
+    assert!(!graph.has_sub_graphs());
+
+    let mismatched = ScaleMapping::new(ContinuousScale::linear(0.0, 1.0), Channel::Color);
+    assert!(mismatched
+        .apply_to_nodes(services_graph(), &latency)
+        .is_err());
+}
+
+#[test]
+fn test_apply_to_edges() {
+    let owners: HashMap<(Identifier, Identifier), &str> =
+        [((id("db"), id("api")), "storage")].into();
+    let mapping = ScaleMapping::new(
+        CategoricalScale::new().category("storage", 3.0),
+        Channel::PenWidth,
+    );
+    // Directed edges only match their own head and tail.
+    let graph = mapping.apply_to_edges(services_graph(), &owners).unwrap();
+    assert!(graph.all_edges().all(|e| !e.item().has_attributes()));
+
+    let mut graph = services_graph();
+    to_undirected(&mut graph);
+    let graph = mapping.apply_to_edges(graph, &owners).unwrap();
+    let widths: Vec<Vec<Attribute>> = graph
+        .all_edges()
+        .map(|e| {
+            e.item()
+                .attributes()
+                .map(|a| a.as_ref().clone())
+                .unwrap_or_default()
+        })
+        .collect();
+    assert_eq!(
+        widths,
+        vec![
+            vec![Attribute::PenWidth(Positive::try_from(3.0).unwrap())],
+            vec![]
+        ]
+    );
+}
+
+#[test]
+fn test_legend() {
+    let latency: HashMap<Identifier, f64> = [(id("api"), 5.0)].into();
+    let mapping = ScaleMapping::new(
+        ContinuousScale::linear(0.0, 10.0)
+            .colors(vec![
+                Color::named("white").unwrap(),
+                Color::named("navy").unwrap(),
+            ])
+            .legend_ticks(3),
+        Channel::FillColor,
+    )
+    .with_legend(id("latency"), "Latency (ms)");
+    let graph = mapping.apply_to_nodes(services_graph(), &latency).unwrap();
+
+    let legend = graph.all_clusters().next().unwrap().item();
+    assert_eq!(legend.id(), &id("latency"));
+    let labels: Vec<String> = legend
+        .nodes()
+        .map(|n| {
+            n.attributes()
+                .unwrap()
+                .as_ref()
+                .iter()
+                .find(|a| a.name() == "label")
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(labels, vec!["label = 0", "label = 5", "label = 10"]);
+    assert!(node_attributes(&graph, "latency_2")
+        .contains(&Attribute::FontColor(Color::named("white").unwrap())));
+    assert!(node_attributes(&graph, "latency_0")
+        .contains(&Attribute::FontColor(Color::named("black").unwrap())));
+}
+
+#[test]
+fn test_legend_quotes_ids_and_labels() {
+    let mapping = ScaleMapping::new(
+        CategoricalScale::new()
+            .category("team-a", Color::named("red").unwrap())
+            .category("b", Color::named("blue").unwrap()),
+        Channel::FillColor,
+    );
+    let legend = mapping.legend(id("\"by team\""), "p95/team").unwrap();
+    let ids: Vec<String> = legend.nodes().map(|n| n.id().to_string()).collect();
+    assert_eq!(ids, vec!["\"by team_0\"", "\"by team_1\""]);
+    let labels: Vec<String> = legend
+        .nodes()
+        .map(|n| {
+            n.attributes()
+                .unwrap()
+                .as_ref()
+                .iter()
+                .find(|a| a.name() == "label")
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(labels, vec!["label = \"team-a\"", "label = b"]);
+    assert_eq!(
+        legend.attributes().unwrap().as_ref()[0].to_string(),
+        "label = \"p95/team\""
+    );
+}
@@ -0,0 +1,64 @@
+use simple_dot::attributes::{Degrees, NodeAttributes, PolygonShape, Shape};
+use std::str::FromStr;
+
+#[test]
+fn test_shape_names() {
+    assert_eq!(Shape::default().to_string(), "ellipse");
+    assert_eq!(Shape::None.to_string(), "none");
+    assert_eq!(Shape::PlainText.to_string(), "plaintext");
+    assert_eq!(Shape::MRecord.to_string(), "Mrecord");
+    assert_eq!(Shape::FivePOverhang.to_string(), "fivepoverhang");
+    assert!(Shape::Record.is_record());
+
+    assert_eq!(Shape::from_str("rect").unwrap(), Shape::Box);
+    assert_eq!(Shape::from_str("Mrecord").unwrap(), Shape::MRecord);
+    assert_eq!(Shape::from_str("none").unwrap(), Shape::None);
+    assert_eq!(
+        Shape::from_str("polygon").unwrap(),
+        Shape::Polygon(PolygonShape::default())
+    );
+    assert!(Shape::from_str("custom").is_err());
+    assert!(Shape::from_str("blob").is_err());
+}
+
+#[test]
+fn test_polygon_parameters() {
+    let polygon = PolygonShape::new(7)
+        .unwrap()
+        .skew(-0.5)
+        .unwrap()
+        .distortion(0.25)
+        .unwrap()
+        .orientation(Degrees::try_from(30.0).unwrap());
+    assert_eq!(
+        NodeAttributes::default().shape(polygon.into()).to_string(),
+        "[ shape = polygon; sides = 7; skew = -0.5; distortion = 0.25; orientation = 30 ]"
+    );
+
+    assert!(PolygonShape::new(2).is_err());
+    assert!(PolygonShape::new(101).is_err());
+    assert!(PolygonShape::default().skew(-100.5).is_err());
+    assert!(PolygonShape::default().distortion(f64::NAN).is_err());
+}
+
+#[test]
+fn test_shape_replaces_parameters() {
+    let attributes = NodeAttributes::default()
+        .shape(PolygonShape::new(5).unwrap().regular().into())
+        .peripheries(2)
+        .shape(Shape::Ellipse);
+    assert_eq!(
+        attributes.to_string(),
+        "[ peripheries = 2; shape = ellipse ]"
+    );
+
+    let attributes = NodeAttributes::default().shape(Shape::custom("images/server.png"));
+    assert_eq!(
+        attributes.to_string(),
+        "[ shape = custom; shapefile = \"images/server.png\" ]"
+    );
+    assert_eq!(
+        attributes.shape(Shape::epsf("logo.ps")).to_string(),
+        "[ shape = epsf; shapefile = \"logo.ps\" ]"
+    );
+}
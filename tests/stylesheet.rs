@@ -0,0 +1,202 @@
+use simple_dot::attributes::{
+    Attribute, ClusterAttributes, Color, ColorOrList, EdgeAttributes, NodeAttributes, NodeStyles,
+    Shape, Style, Styled, Unsigned,
+};
+use simple_dot::graph::{Cluster, Graph};
+use simple_dot::stylesheet::{Selector, Stylesheet};
+use simple_dot::{Edge, Identified, Identifier, Node, RootGraph};
+
+fn id(s: &str) -> Identifier {
+    Identifier::new_unchecked(s)
+}
+
+fn color(name: &str) -> Attribute {
+    Attribute::Color(ColorOrList::Color(Color::named(name).unwrap()))
+}
+
+fn node_attributes(graph: &RootGraph, name: &str) -> Vec<Attribute> {
+    graph
+        .find_node(&id(name))
+        .and_then(|n| n.attributes())
+        .map(|a| a.as_ref().clone())
+        .unwrap_or_default()
+}
+
+fn services_graph() -> RootGraph {
+    RootGraph::new(id("G"), false, true)
+        .add_node(
+            Node::new(id("user_db")).set_attributes(
+                NodeAttributes::default()
+                    .class(vec!["database".to_string()])
+                    .color(Color::named("black").unwrap().into()),
+            ),
+        )
+        .add_node(Node::new(id("user_api")))
+        .add_sub_graph(
+            Cluster::new(id("billing"))
+                .set_attributes(ClusterAttributes::default().class(vec!["team".to_string()]))
+                .add_node(Node::new(id("billing_api")))
+                .add_edge(Edge::new(id("billing_api"), id("user_api")).set_attributes(
+                    EdgeAttributes::default().class(vec!["deprecated".to_string()]),
+                )),
+        )
+        .add_edge_between(id("user_api"), id("user_db"))
+        .add_edge_between(id("gateway"), id("user_api"))
+}
+
+#[test]
+fn test_selectors() {
+    let sheet = Stylesheet::default()
+        .rule(
+            Selector::nodes().id("*_api"),
+            vec![Attribute::Peripheries(2)],
+        )
+        .rule(
+            Selector::nodes().class("database"),
+            vec![Attribute::Shape(Shape::Cylinder)],
+        )
+        .rule(Selector::any().within(id("billing")), vec![color("green")])
+        .rule(
+            Selector::edges().class("deprecated"),
+            vec![Attribute::Style(vec![])],
+        )
+        .rule(Selector::edges().head("gateway"), vec![color("blue")])
+        .rule(
+            Selector::clusters().class("team"),
+            vec![Attribute::Peripheries(0)],
+        );
+    let graph = sheet.apply(services_graph());
+
+    assert_eq!(
+        node_attributes(&graph, "user_db"),
+        vec![
+            Attribute::Shape(Shape::Cylinder),
+            Attribute::Class(vec!["database".to_string()]),
+            color("black"),
+        ]
+    );
+    assert_eq!(
+        node_attributes(&graph, "user_api"),
+        vec![Attribute::Peripheries(2)]
+    );
+    assert_eq!(
+        node_attributes(&graph, "billing_api"),
+        vec![color("green"), Attribute::Peripheries(2)]
+    );
+
+    let edge_attributes: Vec<Vec<Attribute>> = graph
+        .all_edges()
+        .map(|e| {
+            e.item()
+                .attributes()
+                .map(|a| a.as_ref().clone())
+                .unwrap_or_default()
+        })
+        .collect();
+    assert_eq!(
+        edge_attributes,
+        vec![
+            vec![],
+            vec![color("blue")],
+            vec![
+                color("green"),
+                Attribute::Style(vec![]),
+                Attribute::Class(vec!["deprecated".to_string()]),
+            ],
+        ]
+    );
+
+    let cluster = graph.all_clusters().next().unwrap();
+    assert_eq!(cluster.item().id(), &id("billing"));
+    assert_eq!(
+        cluster.item().attributes().unwrap().as_ref(),
+        &vec![
+            Attribute::Peripheries(0),
+            Attribute::Class(vec!["team".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn test_specificity_and_order() {
+    let peripheries = |n: Unsigned| vec![Attribute::Peripheries(n)];
+    let sheet = Stylesheet::default()
+        .rule(Selector::nodes().id("user_api"), peripheries(1))
+        .rule(Selector::nodes().id("user_*"), peripheries(2))
+        .rule(Selector::nodes(), peripheries(3))
+        .rule(Selector::any().min_degree(2), peripheries(4))
+        .rule(Selector::nodes().min_degree(2), peripheries(5));
+    assert!(
+        Selector::nodes().id("user_api").specificity()
+            > Selector::nodes().id("user_*").specificity()
+    );
+
+    let graph = sheet.apply(services_graph());
+    assert_eq!(node_attributes(&graph, "user_api"), peripheries(1));
+    assert_eq!(node_attributes(&graph, "billing_api"), peripheries(3));
+    assert!(node_attributes(&graph, "user_db").contains(&Attribute::Peripheries(2)));
+
+    let graph = Stylesheet::default()
+        .rule(Selector::nodes().max_degree(1), peripheries(0))
+        .rule(Selector::nodes().has_attribute("color"), vec![color("red")])
+        .rule(
+            Selector::nodes().attribute(Attribute::Class(vec!["database".to_string()])),
+            peripheries(9),
+        )
+        .apply(services_graph());
+    assert_eq!(
+        node_attributes(&graph, "user_db"),
+        vec![
+            Attribute::Peripheries(9),
+            Attribute::Class(vec!["database".to_string()]),
+            color("black"),
+        ]
+    );
+    assert_eq!(node_attributes(&graph, "billing_api"), peripheries(0));
+}
+
+#[test]
+fn test_glob_patterns() {
+    let graph = RootGraph::new(id("G"), false, false)
+        .add_node(Node::new(id("a1")))
+        .add_node(Node::new(id("a12")))
+        .add_node(Node::new(id("b1")));
+    let graph = Stylesheet::default()
+        .rule(Selector::nodes().id("a?"), vec![Attribute::Peripheries(1)])
+        .rule(Selector::nodes().id("*1*"), vec![color("red")])
+        .apply(graph);
+    assert_eq!(
+        node_attributes(&graph, "a1"),
+        vec![Attribute::Peripheries(1), color("red")]
+    );
+    assert_eq!(node_attributes(&graph, "a12"), vec![color("red")]);
+    assert_eq!(node_attributes(&graph, "b1"), vec![color("red")]);
+}
+
+#[test]
+fn test_attributes_filtered_by_element_kind() {
+    let sheet = Stylesheet::default().rule(
+        Selector::any(),
+        vec![
+            Attribute::Shape(Shape::Box),
+            Attribute::Style(vec![Style::Node(NodeStyles::Filled)]),
+            color("blue"),
+        ],
+    );
+    let graph = sheet.apply(services_graph());
+
+    assert!(node_attributes(&graph, "user_api").contains(&Attribute::Shape(Shape::Box)));
+    for edge in graph.all_edges() {
+        let attributes = edge.item().attributes().unwrap().as_ref();
+        assert!(attributes.contains(&color("blue")));
+        assert!(!attributes
+            .iter()
+            .any(|a| a.name() == "shape" || a.name() == "style"));
+    }
+    let cluster = graph.all_clusters().next().unwrap().item();
+    let attributes = cluster.attributes().unwrap().as_ref();
+    assert!(attributes.contains(&color("blue")));
+    assert!(!attributes
+        .iter()
+        .any(|a| a.name() == "shape" || a.name() == "style"));
+}
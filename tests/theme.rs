@@ -0,0 +1,193 @@
+use simple_dot::attributes::{
+    Attribute, ClusterAttributes, Color, ColorOrList, EdgeAttributes, NodeAttributes, Shape, Styled,
+};
+use simple_dot::graph::{Cluster, Graph};
+use simple_dot::theme::{Theme, ThemeRole};
+use simple_dot::{Edge, Identifier, Node, RootGraph};
+
+fn id(s: &str) -> Identifier {
+    Identifier::new_unchecked(s)
+}
+
+fn classed(name: &str, class: &str) -> Node {
+    Node::new(id(name)).set_attributes(NodeAttributes::default().class(vec![class.to_string()]))
+}
+
+#[test]
+fn test_built_in_themes() {
+    for theme in [Theme::light(), Theme::dark(), Theme::print()] {
+        assert!(theme.role("database").is_some());
+        assert!(theme.role("external").is_some());
+        assert!(theme.role("deprecated").is_some());
+        assert!(!theme.node_attributes().as_ref().is_empty());
+    }
+    assert_eq!(Theme::dark().name(), "dark");
+}
+
+#[test]
+fn test_apply_theme() {
+    let theme = Theme::new("test")
+        .set_node_attributes(NodeAttributes::default().shape(Shape::Box))
+        .set_cluster_attributes(
+            ClusterAttributes::default().color(Color::named("grey").unwrap().into()),
+        )
+        .add_role(
+            "database",
+            ThemeRole::default()
+                .set_node_attributes(NodeAttributes::default().shape(Shape::Cylinder)),
+        )
+        .add_role(
+            "deprecated",
+            ThemeRole::default().set_edge_attributes(
+                EdgeAttributes::default().color(Color::named("red").unwrap().into()),
+            ),
+        );
+    let graph = RootGraph::new(id("G"), false, true)
+        .set_default_node_attributes(NodeAttributes::default().peripheries(2))
+        .add_node(classed("db", "database"))
+        .add_sub_graph(
+            Cluster::new(id("c"))
+                .set_attributes(
+                    ClusterAttributes::default().color(Color::named("blue").unwrap().into()),
+                )
+                .add_sub_graph(Cluster::new(id("inner")).add_node(classed("store", "database"))),
+        )
+        .add_edge(
+            Edge::new(id("a"), id("db"))
+                .set_attributes(EdgeAttributes::default().class(vec!["deprecated".to_string()])),
+        );
+
+    let graph = theme.apply(graph);
+    assert_eq!(
+        graph.default_node_attributes().unwrap().as_ref(),
+        &vec![Attribute::Shape(Shape::Box), Attribute::Peripheries(2)]
+    );
+    for node in graph.all_nodes() {
+        assert!(node
+            .item()
+            .attributes()
+            .unwrap()
+            .as_ref()
+            .contains(&Attribute::Shape(Shape::Cylinder)));
+    }
+    let cluster_colors: Vec<&Attribute> = graph
+        .all_clusters()
+        .filter_map(|c| c.item().attributes())
+        .flat_map(|a| a.as_ref().iter())
+        .collect();
+    assert_eq!(
+        cluster_colors,
+        vec![
+            &Attribute::Color(ColorOrList::Color(Color::named("blue").unwrap())),
+            &Attribute::Color(ColorOrList::Color(Color::named("grey").unwrap())),
+        ]
+    );
+    assert!(graph
+        .edges()
+        .next()
+        .unwrap()
+        .attributes()
+        .unwrap()
+        .as_ref()
+        .contains(&Attribute::Color(ColorOrList::Color(
+            Color::named("red").unwrap()
+        ))));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_theme_from_toml() {
+    let theme = Theme::from_toml_str(
+        r##"
+name = "corporate"
+
+[graph]
+fontname = ["Helvetica", "Arial"]
+
+[node]
+shape = "box"
+style = ["rounded", "filled"]
+fillcolor = "#f5f5f5"
+fontsize = 12
+
+[roles.database.node]
+shape = "cylinder"
+
+[roles.deprecated.edge]
+style = "dashed"
+arrowhead = "ediamond"
+"##,
+    )
+    .unwrap();
+    assert_eq!(theme.name(), "corporate");
+    assert_eq!(theme.node_attributes().as_ref().len(), 4);
+    assert_eq!(
+        theme
+            .role("database")
+            .unwrap()
+            .node_attributes()
+            .unwrap()
+            .as_ref(),
+        &vec![Attribute::Shape(Shape::Cylinder)]
+    );
+    assert_eq!(
+        theme
+            .role("deprecated")
+            .unwrap()
+            .edge_attributes()
+            .unwrap()
+            .to_string(),
+        "[ arrowhead = odiamond; style = dashed ]"
+    );
+
+    assert!(Theme::from_toml_str("[node]\nshape = \"blob\"").is_err());
+    assert!(Theme::from_toml_str("[edge]\narrowhead = \"ldot\"").is_err());
+    assert!(Theme::from_toml_str("[widget]\ncolor = \"red\"").is_err());
+    assert!(Theme::from_toml_str("[node\n").is_err());
+    assert!(Theme::from_toml_str("[node]\nbgcolor = \"red\"").is_err());
+    assert!(Theme::from_toml_str("[graph]\npenwidth = 2").is_err());
+    assert!(Theme::from_toml_str("[node]\nlabel = \"text\"").is_err());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_theme_attributes_from_toml() {
+    let theme = Theme::from_toml_str(
+        r##"
+[graph]
+bgcolor = "white"
+gradientangle = 90
+
+[edge]
+colorscheme = "blues9"
+labelfontcolor = "3"
+labelfontname = "Helvetica"
+labelfontsize = 8
+"##,
+    )
+    .unwrap();
+    assert_eq!(
+        theme.graph_attributes().to_string(),
+        "[ bgcolor = white; gradientangle = 90 ]"
+    );
+    assert_eq!(theme.edge_attributes().as_ref().len(), 4);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_theme_from_json() {
+    let theme = Theme::from_json_str(
+        r##"{
+            "name": "corporate",
+            "edge": { "color": "#333333", "penwidth": 1.5 },
+            "cluster": { "style": "filled,rounded" },
+            "roles": { "external": { "node": { "style": "dashed" } } }
+        }"##,
+    )
+    .unwrap();
+    assert_eq!(theme.edge_attributes().as_ref().len(), 2);
+    assert_eq!(theme.cluster_attributes().as_ref().len(), 1);
+    assert!(theme.role("external").is_some());
+
+    assert!(Theme::from_json_str(r#"{ "edge": { "shape": "box" } }"#).is_err());
+}
@@ -0,0 +1,612 @@
+use simple_dot::attributes::{
+    ArrowType, Attribute, ClusterAttributes, Color, Direction, EdgeAttributes, EdgeStyles,
+    GraphAttributes, LabelString, NodeAttributes, PackMode, Styled,
+};
+use simple_dot::graph::{Cluster, Graph, SubGraphKind};
+use simple_dot::transform::{
+    bundle_edges, bundle_edges_with, collapse_at_depth, collapse_cluster, connected_components,
+    expand_cluster, induced_sub_graph, neighborhood, packed_connected_components, reverse_edges,
+    reverse_edges_where, symmetrize, to_directed, to_undirected, transitive_reduction, unflatten,
+    BundleMetric, BundleOptions, CollapseOptions, ExtractOptions, Hops, UnflattenOptions,
+};
+use simple_dot::{Edge, Identified, Identifier, Node, RootGraph};
+
+fn id(s: &str) -> Identifier {
+    Identifier::new_unchecked(s)
+}
+
+fn edge_strings(graph: &RootGraph) -> Vec<String> {
+    graph
+        .all_edges()
+        .map(|e| e.item().to_string_no_attributes())
+        .collect()
+}
+
+#[test]
+fn test_transitive_reduction() {
+    let mut graph = RootGraph::new(id("G"), false, true)
+        .add_sub_graph(
+            Cluster::new(id("c"))
+                .add_edge_between(id("a"), id("b"))
+                .add_edge_between(id("b"), id("c")),
+        )
+        .add_edge(Edge::new(id("a"), id("c")))
+        .add_edge(
+            Edge::new(id("c"), id("d"))
+                .set_attributes(EdgeAttributes::default().label(LabelString::new_unchecked("cd"))),
+        )
+        .add_edge_between(id("a"), id("d"));
+
+    let result = transitive_reduction(&mut graph).unwrap();
+    assert!(!result.has_cycles());
+    assert_eq!(
+        result
+            .removed_edges()
+            .iter()
+            .map(Edge::to_string_no_attributes)
+            .collect::<Vec<String>>(),
+        vec!["a -> c", "a -> d"]
+    );
+    assert_eq!(edge_strings(&graph), vec!["c -> d", "a -> b", "b -> c"]);
+    assert!(graph
+        .find_edge(&id("c"), &id("d"))
+        .unwrap()
+        .has_attributes());
+
+    for edge in result.into_removed_edges() {
+        graph.insert_edge(
+            edge.set_attributes(EdgeAttributes::default().style(vec![EdgeStyles::Dashed])),
+        );
+    }
+    assert_eq!(graph.all_edges().count(), 5);
+}
+
+#[test]
+fn test_transitive_reduction_with_cycle() {
+    let mut graph = RootGraph::new(id("G"), false, true)
+        .add_edge_between(id("a"), id("b"))
+        .add_edge_between(id("b"), id("a"))
+        .add_edge_between(id("b"), id("c"))
+        .add_edge_between(id("a"), id("c"))
+        .add_edge_between(id("c"), id("d"))
+        .add_edge_between(id("a"), id("d"));
+
+    let result = transitive_reduction(&mut graph).unwrap();
+    assert_eq!(result.cycles().len(), 1);
+    assert_eq!(
+        edge_strings(&graph),
+        vec!["a -> b", "b -> a", "b -> c", "c -> d"]
+    );
+
+    let mut graph = RootGraph::new(id("U"), false, false).add_edge_between(id("a"), id("b"));
+    assert!(transitive_reduction(&mut graph).is_err());
+}
+
+fn components_graph() -> RootGraph {
+    RootGraph::new(id("G"), false, true)
+        .set_attributes(GraphAttributes::default().label(LabelString::new_unchecked("G")))
+        .set_default_node_attributes(
+            NodeAttributes::default().label(LabelString::new_unchecked("n")),
+        )
+        .add_node(Node::new(id("x")))
+        .add_sub_graph(
+            Cluster::new(id("c"))
+                .add_node(Node::new(id("a")))
+                .add_node(Node::new(id("y")))
+                .add_edge_between(id("a"), id("b")),
+        )
+        .add_edge_between(id("y"), id("x"))
+        .add_node(Node::new(id("z")))
+}
+
+#[test]
+fn test_connected_components() {
+    let components = connected_components(&components_graph());
+    assert_eq!(components.len(), 3);
+    assert_eq!(
+        components
+            .iter()
+            .map(|c| c.id().to_string())
+            .collect::<Vec<String>>(),
+        vec!["G_0", "G_1", "G_2"]
+    );
+    for component in components.iter() {
+        assert!(component.is_directed());
+        assert!(component.has_attributes());
+        assert!(component.default_node_attributes().is_some());
+    }
+
+    let first = &components[0];
+    assert!(first.contains_node(&id("x")));
+    assert_eq!(edge_strings(first), vec!["y -> x"]);
+    match first.find_sub_graph(&id("c")) {
+        Some(SubGraphKind::Cluster(cluster)) => {
+            assert!(cluster.contains_node(&id("y")));
+            assert!(!cluster.contains_node(&id("a")));
+        }
+        _ => panic!("expected cluster c"),
+    }
+
+    assert!(components[1].contains_node(&id("z")));
+    assert!(components[1].find_sub_graph(&id("c")).is_none());
+    assert_eq!(edge_strings(&components[2]), vec!["a -> b"]);
+
+    let quoted = RootGraph::new(id("\"my graph\""), false, false).add_node(Node::new(id("a")));
+    assert_eq!(
+        connected_components(&quoted)[0].id().to_string(),
+        "\"my graph_0\""
+    );
+    let numeric = RootGraph::new(12i64.into(), false, false).add_node(Node::new(id("a")));
+    assert_eq!(
+        connected_components(&numeric)[0].id().to_string(),
+        "\"12_0\""
+    );
+}
+
+#[test]
+fn test_packed_connected_components() {
+    let packed = packed_connected_components(&components_graph(), PackMode::Graph);
+    assert_eq!(packed.all_clusters().count(), 5);
+    assert_eq!(packed.sub_graphs().count(), 3);
+    assert_eq!(packed.all_edges().count(), 2);
+    let attributes = packed.attributes().unwrap().as_ref();
+    assert!(attributes.contains(&Attribute::Pack(true)));
+    assert!(attributes.contains(&Attribute::PackMode(PackMode::Graph)));
+}
+
+fn min_length(graph: &RootGraph, from: &str, to: &str) -> Option<Attribute> {
+    graph
+        .find_edge(&id(from), &id(to))
+        .and_then(|e| e.attributes())
+        .and_then(|a| {
+            a.as_ref()
+                .iter()
+                .find(|a| matches!(a, Attribute::MinLength(_)))
+                .cloned()
+        })
+}
+
+#[test]
+fn test_unflatten() {
+    let mut graph = RootGraph::new(id("G"), false, true)
+        .add_edge_between(id("r"), id("a"))
+        .add_edge_between(id("r"), id("b"))
+        .add_edge_between(id("r"), id("c"))
+        .add_edge(
+            Edge::new(id("r"), id("d")).set_attributes(EdgeAttributes::default().minimum_length(5)),
+        )
+        .add_node(Node::new(id("x")))
+        .add_node(Node::new(id("y")))
+        .add_node(Node::new(id("z")));
+
+    unflatten(
+        &mut graph,
+        &UnflattenOptions::default().max_min_length(2).chain_limit(2),
+    );
+    assert_eq!(min_length(&graph, "r", "a"), Some(Attribute::MinLength(1)));
+    assert_eq!(min_length(&graph, "r", "b"), Some(Attribute::MinLength(2)));
+    assert_eq!(min_length(&graph, "r", "c"), Some(Attribute::MinLength(1)));
+    assert_eq!(min_length(&graph, "r", "d"), Some(Attribute::MinLength(5)));
+    assert!(graph.find_edge(&id("x"), &id("y")).is_some());
+    assert!(graph.find_edge(&id("y"), &id("z")).is_some());
+}
+
+#[test]
+fn test_unflatten_default_is_unchanged() {
+    let mut graph = RootGraph::new(id("G"), false, true)
+        .add_edge_between(id("r"), id("a"))
+        .add_edge_between(id("r"), id("b"))
+        .add_node(Node::new(id("x")));
+
+    unflatten(&mut graph, &UnflattenOptions::default());
+    assert!(min_length(&graph, "r", "a").is_none());
+    assert_eq!(graph.all_edges().count(), 2);
+}
+
+fn services_graph() -> RootGraph {
+    let db = |s: &str| {
+        Node::new(id(s)).set_attributes(NodeAttributes::default().class(vec![String::from("db")]))
+    };
+    RootGraph::new(id("G"), false, true)
+        .set_default_node_attributes(
+            NodeAttributes::default().label(LabelString::new_unchecked("n")),
+        )
+        .add_sub_graph(
+            Cluster::new(id("storage"))
+                .add_node(db("orders_db"))
+                .add_node(db("users_db")),
+        )
+        .add_sub_graph(Cluster::new(id("web")).add_node(Node::new(id("frontend"))))
+        .add_edge_between(id("frontend"), id("api"))
+        .add_edge_between(id("api"), id("orders"))
+        .add_edge_between(id("orders"), id("orders_db"))
+        .add_edge_between(id("orders_db"), id("users_db"))
+        .add_edge_between(id("api"), id("users_db"))
+}
+
+#[test]
+fn test_induced_sub_graph() {
+    let graph = induced_sub_graph(
+        &services_graph(),
+        |node| {
+            node.attributes()
+                .map(|a| {
+                    a.as_ref()
+                        .contains(&Attribute::Class(vec![String::from("db")]))
+                })
+                .unwrap_or_default()
+        },
+        &ExtractOptions::default(),
+    );
+    assert_eq!(edge_strings(&graph), vec!["orders_db -> users_db"]);
+    assert!(graph.find_sub_graph(&id("storage")).is_some());
+    assert!(graph.find_sub_graph(&id("web")).is_none());
+    assert!(graph.default_node_attributes().is_some());
+}
+
+#[test]
+fn test_neighborhood() {
+    let graph = neighborhood(
+        &services_graph(),
+        &[id("api")],
+        1,
+        Hops::Outgoing,
+        &ExtractOptions::default(),
+    );
+    assert_eq!(
+        edge_strings(&graph),
+        vec!["api -> orders", "api -> users_db"]
+    );
+
+    let graph = neighborhood(
+        &services_graph(),
+        &[id("api")],
+        2,
+        Hops::Both,
+        &ExtractOptions::default(),
+    );
+    assert_eq!(graph.all_edges().count(), 5);
+}
+
+#[test]
+fn test_neighborhood_with_stubs() {
+    let graph = neighborhood(
+        &services_graph(),
+        &[id("orders")],
+        1,
+        Hops::Both,
+        &ExtractOptions::default().with_stubs(),
+    );
+    assert_eq!(
+        edge_strings(&graph),
+        vec![
+            "api -> orders",
+            "orders -> orders_db",
+            "orders_db -> orders_db_stub_out",
+            "api -> api_stub_out",
+            "api_stub_in -> api",
+        ]
+    );
+    assert!(graph.contains_node(&id("api_stub_out")));
+
+    let quoted =
+        RootGraph::new(id("G"), false, true).add_edge_between(id("\"web app\""), id("api"));
+    let graph = neighborhood(
+        &quoted,
+        &[id("\"web app\"")],
+        0,
+        Hops::Both,
+        &ExtractOptions::default().with_stubs(),
+    );
+    assert_eq!(
+        edge_strings(&graph),
+        vec!["\"web app\" -> \"web app_stub_out\""]
+    );
+}
+
+fn nested_graph() -> RootGraph {
+    RootGraph::new(id("G"), false, true)
+        .add_sub_graph(
+            Cluster::new(id("backend"))
+                .set_attributes(
+                    ClusterAttributes::default().label(LabelString::new_unchecked("Backend")),
+                )
+                .add_node(Node::new(id("api")))
+                .add_sub_graph(
+                    Cluster::new(id("data"))
+                        .add_node(Node::new(id("db")))
+                        .add_node(Node::new(id("cache"))),
+                )
+                .add_edge_between(id("api"), id("db")),
+        )
+        .add_node(Node::new(id("client")))
+        .add_edge_between(id("client"), id("api"))
+        .add_edge_between(id("client"), id("db"))
+        .add_edge_between(id("client"), id("cache"))
+}
+
+#[test]
+fn test_collapse_and_expand_cluster() {
+    let mut graph = nested_graph();
+    let collapsed = collapse_cluster(
+        &mut graph,
+        &id("data"),
+        &CollapseOptions::default().count_labels(),
+    )
+    .unwrap();
+    assert_eq!(collapsed.parent(), Some(&id("backend")));
+    assert!(graph.find_sub_graph(&id("data")).is_none());
+    assert!(graph
+        .find_sub_graph(&id("backend"))
+        .unwrap()
+        .find_node(&id("data"))
+        .is_some());
+    assert_eq!(
+        edge_strings(&graph),
+        vec!["client -> api", "client -> data", "api -> data"]
+    );
+    assert_eq!(
+        graph
+            .find_edge(&id("client"), &id("data"))
+            .unwrap()
+            .attributes()
+            .unwrap()
+            .as_ref(),
+        &vec![Attribute::Label(LabelString::new_unchecked("2"))]
+    );
+
+    expand_cluster(&mut graph, collapsed).unwrap();
+    assert!(!graph.contains_node(&id("data")));
+    assert!(graph.find_sub_graph(&id("data")).is_some());
+    assert_eq!(graph.all_edges().count(), 4);
+    assert!(graph.find_edge(&id("client"), &id("cache")).is_some());
+}
+
+#[test]
+fn test_collapse_at_depth() {
+    let mut graph = nested_graph();
+    let collapsed = collapse_at_depth(&mut graph, 0, &CollapseOptions::default()).unwrap();
+    assert_eq!(collapsed.len(), 1);
+    assert_eq!(graph.all_clusters().count(), 0);
+    assert_eq!(edge_strings(&graph), vec!["client -> backend"]);
+    assert_eq!(
+        graph
+            .find_node(&id("backend"))
+            .unwrap()
+            .attributes()
+            .unwrap()
+            .as_ref()[0],
+        Attribute::Label(LabelString::new_unchecked("Backend"))
+    );
+
+    for cluster in collapsed.into_iter().rev() {
+        expand_cluster(&mut graph, cluster).unwrap();
+    }
+    assert_eq!(graph.all_clusters().count(), 2);
+    assert_eq!(graph.all_edges().count(), 4);
+
+    assert!(collapse_cluster(&mut graph, &id("missing"), &CollapseOptions::default()).is_err());
+}
+
+#[test]
+fn test_collapse_undirected_cluster() {
+    let blue = || EdgeAttributes::default().color(Color::named("blue").unwrap().into());
+    let mut graph = RootGraph::new(id("G"), false, false)
+        .add_sub_graph(
+            Cluster::new(id("\"my group\""))
+                .add_node(Node::new(id("x")))
+                .add_node(Node::new(id("y")))
+                .add_edge_between(id("x"), id("z")),
+        )
+        .add_edge(
+            Edge::new(id("a"), id("x")).set_attributes(blue().style(vec![EdgeStyles::Dashed])),
+        )
+        .add_edge(Edge::new(id("y"), id("a")).set_attributes(blue()));
+    let _ = collapse_cluster(&mut graph, &id("\"my group\""), &CollapseOptions::default()).unwrap();
+
+    assert_eq!(
+        edge_strings(&graph),
+        vec!["a -- \"my group\"", "\"my group\" -- z"]
+    );
+    assert_eq!(
+        graph
+            .find_edge(&id("a"), &id("\"my group\""))
+            .unwrap()
+            .attributes()
+            .unwrap()
+            .as_ref(),
+        blue().as_ref()
+    );
+    assert_eq!(
+        graph
+            .find_node(&id("\"my group\""))
+            .unwrap()
+            .attributes()
+            .unwrap()
+            .as_ref()[0]
+            .to_string(),
+        "label = \"my group\""
+    );
+}
+
+#[test]
+fn test_collapse_identifier_collision() {
+    let mut graph = nested_graph().add_edge_between(id("client"), id("data"));
+    assert!(collapse_cluster(&mut graph, &id("data"), &CollapseOptions::default()).is_err());
+    assert!(graph.find_sub_graph(&id("data")).is_some());
+
+    let mut graph = nested_graph()
+        .add_sub_graph(Cluster::new(id("frontend")).add_node(Node::new(id("web"))))
+        .add_node(Node::new(id("frontend")));
+    assert!(collapse_at_depth(&mut graph, 0, &CollapseOptions::default()).is_err());
+    assert_eq!(graph.all_clusters().count(), 3);
+    assert!(!graph.contains_node(&id("backend")));
+    assert_eq!(graph.all_edges().count(), 4);
+}
+
+#[test]
+fn test_bundle_edges() {
+    let mut graph = RootGraph::new(id("G"), false, true)
+        .add_edge_between(id("a"), id("b"))
+        .add_edge_between(id("a"), id("b"))
+        .add_edge_between(id("b"), id("a"))
+        .add_sub_graph(
+            Cluster::new(id("c"))
+                .add_edge_between(id("a"), id("b"))
+                .add_edge_between(id("x"), id("y"))
+                .add_edge_between(id("x"), id("y"))
+                .add_edge_between(id("x"), id("y")),
+        );
+
+    let removed = bundle_edges(
+        &mut graph,
+        &BundleOptions::default().metrics(vec![BundleMetric::Label, BundleMetric::Weight]),
+    );
+    assert_eq!(removed, 3);
+    assert_eq!(
+        edge_strings(&graph),
+        vec!["a -> b", "b -> a", "a -> b", "x -> y"]
+    );
+    assert_eq!(
+        graph
+            .find_edge(&id("x"), &id("y"))
+            .unwrap()
+            .attributes()
+            .unwrap()
+            .as_ref(),
+        &vec![
+            Attribute::Label(LabelString::new_unchecked("3")),
+            Attribute::Weight(3)
+        ]
+    );
+}
+
+#[test]
+fn test_bundle_edges_strict_graph() {
+    let mut graph = RootGraph::new(id("G"), true, true)
+        .add_edge_between(id("a"), id("b"))
+        .add_sub_graph(Cluster::new(id("c")).add_edge_between(id("c"), id("d")));
+
+    assert_eq!(bundle_edges(&mut graph, &BundleOptions::default()), 0);
+    graph.insert_edge(Edge::new(id("c"), id("d")));
+    graph.insert_edge(Edge::new(id("a"), id("b")));
+    assert_eq!(edge_strings(&graph), vec!["a -> b", "c -> d"]);
+}
+
+#[test]
+fn test_bundle_edges_options() {
+    let styled = |style| {
+        Edge::new(id("a"), id("b")).set_attributes(EdgeAttributes::default().style(vec![style]))
+    };
+    let mut graph = RootGraph::new(id("G"), false, false)
+        .add_edge(styled(EdgeStyles::Dashed))
+        .add_edge(styled(EdgeStyles::Bold))
+        .add_edge(
+            Edge::new(id("b"), id("a"))
+                .set_attributes(EdgeAttributes::default().style(vec![EdgeStyles::Dashed])),
+        );
+
+    let removed = bundle_edges_with(
+        &mut graph,
+        &BundleOptions::default().same_attributes().unordered(),
+        |bundle| {
+            Edge::new(id("a"), id("b")).set_attributes(
+                EdgeAttributes::default()
+                    .label(LabelString::new_unchecked(&format!("x{}", bundle.len()))),
+            )
+        },
+    );
+    assert_eq!(removed, 1);
+    assert_eq!(graph.all_edges().count(), 2);
+    assert_eq!(
+        graph.edges().next().unwrap().attributes().unwrap().as_ref(),
+        &vec![Attribute::Label(LabelString::new_unchecked("x2"))]
+    );
+}
+
+#[test]
+fn test_reverse_edges_swaps_attributes() {
+    let mut graph = RootGraph::new(id("G"), false, true)
+        .add_edge(
+            Edge::new(id("a"), id("b")).set_attributes(
+                EdgeAttributes::default()
+                    .head_label(LabelString::new_unchecked("h"))
+                    .arrow_tail(ArrowType::dot())
+                    .dont_clip_head()
+                    .direction(Direction::Back)
+                    .label(LabelString::new_unchecked("ab")),
+            ),
+        )
+        .add_sub_graph(Cluster::new(id("c")).add_edge_between(id("b"), id("c")));
+
+    assert_eq!(reverse_edges(&mut graph), 2);
+    assert_eq!(edge_strings(&graph), vec!["b -> a", "c -> b"]);
+    assert_eq!(
+        graph.edges().next().unwrap().attributes().unwrap().as_ref(),
+        &vec![
+            Attribute::TailLabel(LabelString::new_unchecked("h")),
+            Attribute::ArrowHead(ArrowType::dot()),
+            Attribute::TailClip(true),
+            Attribute::Direction(Direction::Back),
+            Attribute::Label(LabelString::new_unchecked("ab")),
+        ]
+    );
+
+    assert_eq!(reverse_edges_where(&mut graph, |e| e.head() == &id("c")), 1);
+    assert_eq!(edge_strings(&graph), vec!["b -> a", "b -> c"]);
+}
+
+#[test]
+fn test_change_direction() {
+    let mut graph = RootGraph::new(id("G"), true, true)
+        .add_edge_between(id("a"), id("b"))
+        .add_edge_between(id("b"), id("a"))
+        .add_sub_graph(Cluster::new(id("c")).add_edge_between(id("b"), id("c")));
+    assert_eq!(graph.all_edges().count(), 3);
+
+    to_undirected(&mut graph);
+    assert!(!graph.is_directed());
+    assert_eq!(edge_strings(&graph), vec!["a -- b", "b -- c"]);
+
+    to_directed(&mut graph);
+    assert!(graph.is_directed());
+    assert_eq!(edge_strings(&graph), vec!["a -> b", "b -> c"]);
+}
+
+#[test]
+fn test_symmetrize() {
+    let mut graph = RootGraph::new(id("G"), false, true)
+        .add_edge_between(id("a"), id("b"))
+        .add_edge_between(id("b"), id("a"))
+        .add_edge(
+            Edge::new(id("a"), id("c"))
+                .set_attributes(EdgeAttributes::default().logical_head("x".to_string())),
+        )
+        .add_edge_between(id("c"), id("c"))
+        .add_sub_graph(Cluster::new(id("x")).add_edge_between(id("c"), id("d")));
+
+    assert_eq!(symmetrize(&mut graph), 2);
+    assert_eq!(
+        edge_strings(&graph),
+        vec!["a -> b", "b -> a", "a -> c", "c -> c", "c -> a", "c -> d", "d -> c"]
+    );
+    assert!(matches!(
+        graph.edges().nth(4).unwrap().attributes().unwrap().as_ref()[0],
+        Attribute::LogicalTail(_)
+    ));
+    assert_eq!(symmetrize(&mut graph), 0);
+}
+
+#[test]
+fn test_symmetrize_strict_graph() {
+    let mut graph = RootGraph::new(id("G"), true, true)
+        .add_edge_between(id("a"), id("b"))
+        .add_edge_between(id("c"), id("d"));
+
+    assert_eq!(symmetrize(&mut graph), 2);
+    graph.insert_edge(Edge::new(id("b"), id("a")));
+    assert_eq!(
+        edge_strings(&graph),
+        vec!["a -> b", "c -> d", "b -> a", "d -> c"]
+    );
+}
@@ -1,4 +1,9 @@
-use simple_dot::writer::{write_graph, Indenter};
+use simple_dot::attributes::{
+    ClusterAttributes, GraphAttributes, LabelString, NodeAttributes, RankType,
+};
+use simple_dot::graph::{Graph, SubGraphKind};
+use simple_dot::writer::{write_graph, write_graph_to_string, DotStreamWriter, Indenter};
+use simple_dot::{Identifier, Node, RootGraph};
 
 pub mod common;
 
@@ -25,3 +30,130 @@ fn test_indenter() {
     indent.unindent();
     assert_eq!(indent.pad_string(), String::from("    "));
 }
+
+#[test]
+fn test_stream_writer() {
+    let a = Identifier::new_unchecked("a");
+    let b = Identifier::new_unchecked("b");
+    let c = Identifier::new_unchecked("c");
+
+    let mut stream =
+        DotStreamWriter::new(Vec::new(), &Identifier::new_unchecked("G"), false, true).unwrap();
+    stream
+        .default_node_attributes(&NodeAttributes::default().label(LabelString::new_unchecked("n")))
+        .unwrap();
+    stream
+        .begin_cluster(&Identifier::new_unchecked("x"))
+        .unwrap();
+    stream
+        .cluster_attributes(
+            &ClusterAttributes::default().label(LabelString::new_unchecked("inner")),
+        )
+        .unwrap();
+    stream.node(&Node::new(a.clone())).unwrap();
+    stream.node(&Node::new(b.clone())).unwrap();
+    stream.edge_between(&a, &b).unwrap();
+    stream.end_cluster().unwrap();
+    stream.edge_between(&b, &c).unwrap();
+
+    let output = String::from_utf8(stream.finish().unwrap()).unwrap();
+    assert_eq!(
+        output,
+        r#"digraph G {
+  node [
+    label = n;
+  ];
+
+  subgraph cluster_x {
+    label = inner;
+
+    a;
+    b;
+
+    a -> b;
+  }
+
+  b -> c;
+}
+"#
+    );
+}
+
+#[test]
+fn test_stream_writer_nesting() {
+    let mut stream =
+        DotStreamWriter::new(Vec::new(), &Identifier::new_unchecked("G"), false, false).unwrap();
+    assert!(stream.end_sub_graph().is_err());
+
+    stream
+        .begin_sub_graph(&Identifier::new_unchecked("s"))
+        .unwrap();
+    assert!(stream.end_cluster().is_err());
+    assert!(stream
+        .cluster_attributes(&ClusterAttributes::default())
+        .is_err());
+    assert_eq!(stream.depth(), 2);
+
+    assert!(stream.finish().is_err());
+}
+
+#[test]
+fn test_rank_sub_graphs() {
+    let id = Identifier::new_unchecked;
+    let graph = RootGraph::new(id("G"), false, true)
+        .add_edge_between(id("a"), id("b"))
+        .same_rank(vec![id("b"), id("c")])
+        .sink_rank(vec![id("d")]);
+    assert!(graph.sub_graphs().all(|s| match s {
+        SubGraphKind::Graph(g) => g.is_anonymous(),
+        SubGraphKind::Cluster(_) => false,
+    }));
+
+    // Nodes already declared by an edge are not repeated.
+    assert_eq!(
+        write_graph_to_string(&graph).unwrap(),
+        r#"digraph G {
+
+  {
+    rank = same;
+
+    b;
+    c;
+  }
+  {
+    rank = sink;
+
+    d;
+  }
+
+  a -> b;
+}
+"#
+    );
+}
+
+#[test]
+fn test_stream_writer_anonymous_sub_graph() {
+    let a = Identifier::new_unchecked("a");
+    let mut stream =
+        DotStreamWriter::new(Vec::new(), &Identifier::new_unchecked("G"), false, false).unwrap();
+    stream.begin_anonymous_sub_graph().unwrap();
+    stream
+        .graph_attributes(&GraphAttributes::default().rank(RankType::Min))
+        .unwrap();
+    stream.node(&Node::new(a)).unwrap();
+    stream.end_sub_graph().unwrap();
+
+    let output = String::from_utf8(stream.finish().unwrap()).unwrap();
+    assert_eq!(
+        output,
+        r#"graph G {
+  {
+    rank = min;
+
+    a;
+  }
+}
+"#
+    );
+}